@@ -12,7 +12,7 @@
 use coinpayments::{
     AddressType, CoinPaymentsClient, CreateInvoiceRequest, CreateSpendRequest, CreateWalletRequest,
     CurrencyCapability, CurrencyStatus, InvoiceStatus, RateQuery, Result, WalletStatus,
-    WalletWebhookEvent, WebhookConfig,
+    WebhookConfig, WebhookEvent,
 };
 
 #[tokio::main]
@@ -197,6 +197,7 @@ async fn main() -> Result<()> {
                     match wallet.address_type {
                         AddressType::Temporary => "Temp",
                         AddressType::Permanent => "Perm",
+                        AddressType::Unknown(_) => "?",
                     }
                 );
             }
@@ -302,8 +303,8 @@ async fn main() -> Result<()> {
     let webhook_config = WebhookConfig {
         url: "https://your-server.com/wallet-webhook".to_string(),
         events: vec![
-            WalletWebhookEvent::UtxoExternalReceive,
-            WalletWebhookEvent::ExternalSpend,
+            WebhookEvent::UtxoExternalReceive,
+            WebhookEvent::ExternalSpend,
         ],
         secret: Some("webhook_secret_123".to_string()),
     };
@@ -407,34 +408,33 @@ async fn handle_webhook(
     Ok(())
 }
 
-/// Example of using the legacy API for backward compatibility
+/// Example of using the legacy API signing scheme for backward compatibility
 #[allow(dead_code)]
 async fn legacy_api_example() -> Result<()> {
-    use coinpayments::endpoints::{
-        CoinPaymentsLegacyClient, CreateTransactionRequest, CreateWithdrawalRequest,
-    };
+    use coinpayments::LegacySigner;
+    use std::sync::Arc;
 
-    // Create legacy client
-    let legacy_client = CoinPaymentsLegacyClient::new("public_key", "private_key");
+    // Create a client signing requests the way the v1 API expects
+    let legacy_client =
+        CoinPaymentsClient::new("client_id", "client_secret").with_signer(Arc::new(LegacySigner));
 
-    // Get rates (legacy API)
-    match legacy_client.get_rates(Some(true)).await {
+    // Get rates (legacy signatures)
+    match legacy_client.get_rates(None).await {
         Ok(rates) => {
             println!("📊 Legacy API rates: {} currencies", rates.rates.len());
         }
         Err(e) => println!("❌ Legacy API error: {}", e),
     }
 
-    // Create transaction (legacy API)
-    let transaction_request = CreateTransactionRequest::new(10.0, "USD", "BTC")
-        .with_buyer_email("customer@example.com")
-        .with_item("Test Item", Some("SKU-123".to_string()));
+    // Create invoice (legacy signatures)
+    let invoice_request = CreateInvoiceRequest::new("10.00", "USD", "Test Item")
+        .with_buyer("customer@example.com", None);
 
-    match legacy_client.create_transaction(&transaction_request).await {
-        Ok(transaction) => {
-            println!("✅ Legacy transaction created: {}", transaction.txn_id);
+    match legacy_client.create_invoice(invoice_request).await {
+        Ok(invoice_response) => {
+            println!("✅ Legacy invoice created: {}", invoice_response.invoice.id);
         }
-        Err(e) => println!("❌ Legacy transaction failed: {}", e),
+        Err(e) => println!("❌ Legacy invoice failed: {}", e),
     }
 
     Ok(())
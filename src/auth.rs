@@ -0,0 +1,252 @@
+//! OAuth bearer-token authentication for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Pluggable token sources that fetch OAuth access tokens
+//! - Caching tokens and refreshing them shortly before they expire
+//! - Forcing a refresh after the API rejects a token with `401 Unauthorized`
+
+use crate::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
+
+/// Tokens are refreshed this long before they expire
+pub const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// Future returned by [`TokenSource::fetch_token`]
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<AccessToken>> + Send + 'a>>;
+
+// === Token Types ===
+
+/// OAuth access token with an optional expiry
+#[derive(Clone)]
+pub struct AccessToken {
+    /// Bearer token sent in the `Authorization` header, zeroized on drop
+    pub token: Zeroizing<String>,
+    /// When the token expires (`None` = never)
+    pub expires_at: Option<Instant>,
+}
+
+impl AccessToken {
+    /// Create a token that never expires
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: Zeroizing::new(token.into()),
+            expires_at: None,
+        }
+    }
+
+    /// Set the token lifetime (e.g. the `expires_in` of an OAuth token response)
+    pub fn with_expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_at = Some(Instant::now() + expires_in);
+        self
+    }
+
+    /// Check whether the token is expired or about to expire
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| Instant::now() + TOKEN_REFRESH_MARGIN >= expires_at)
+    }
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("token", &"***")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Source of OAuth access tokens
+///
+/// Implemented for closures returning a token future, so a token endpoint can be
+/// plugged in without a dedicated type.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{AccessToken, AuthType, CoinPaymentsClient, Result};
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// # fn example() {
+/// # struct OAuthToken { access_token: String, expires_in: Duration }
+/// # struct OAuthClient;
+/// # impl OAuthClient {
+/// #     async fn exchange_refresh_token(&self) -> Result<OAuthToken> { unimplemented!() }
+/// # }
+/// # static my_oauth_client: OAuthClient = OAuthClient;
+/// let source = Arc::new(|| async {
+///     let token = my_oauth_client.exchange_refresh_token().await?;
+///     Ok(AccessToken::new(token.access_token).with_expires_in(token.expires_in))
+/// });
+/// let client = CoinPaymentsClient::with_auth(AuthType::TokenSource(source));
+/// # }
+/// ```
+pub trait TokenSource: Send + Sync {
+    /// Fetch a new access token
+    fn fetch_token(&self) -> TokenFuture<'_>;
+}
+
+impl<F, Fut> TokenSource for F
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<AccessToken>> + Send + 'static,
+{
+    fn fetch_token(&self) -> TokenFuture<'_> {
+        Box::pin(self())
+    }
+}
+
+/// Token source returning a fixed token
+#[derive(Clone)]
+pub struct StaticTokenSource {
+    token: Zeroizing<String>,
+}
+
+impl StaticTokenSource {
+    /// Create a token source for a fixed token
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: Zeroizing::new(token.into()),
+        }
+    }
+
+    /// Create a token source from a token that is already zeroized on drop
+    pub(crate) fn from_secret(token: Zeroizing<String>) -> Self {
+        Self { token }
+    }
+}
+
+impl TokenSource for StaticTokenSource {
+    fn fetch_token(&self) -> TokenFuture<'_> {
+        let token = AccessToken {
+            token: self.token.clone(),
+            expires_at: None,
+        };
+        Box::pin(async move { Ok(token) })
+    }
+}
+
+// === Token Cache ===
+
+/// Caches the current token of a source, shared by all clones of a client
+#[derive(Clone)]
+pub(crate) struct TokenCache {
+    source: Arc<dyn TokenSource>,
+    // Held while fetching so concurrent requests share a single refresh
+    current: Arc<tokio::sync::Mutex<Option<AccessToken>>>,
+}
+
+impl TokenCache {
+    pub(crate) fn new(source: Arc<dyn TokenSource>) -> Self {
+        Self {
+            source,
+            current: Arc::new(tokio::sync::Mutex::new(None)),
+        }
+    }
+
+    /// Get a valid token, fetching a new one if none is cached or it has expired
+    pub(crate) async fn token(&self) -> Result<Zeroizing<String>> {
+        let mut current = self.current.lock().await;
+        if let Some(token) = current.as_ref().filter(|token| !token.is_expired()) {
+            return Ok(token.token.clone());
+        }
+
+        let token = self.source.fetch_token().await?;
+        let value = token.token.clone();
+        *current = Some(token);
+        Ok(value)
+    }
+
+    /// Drop a token the API rejected, unless it was already replaced
+    pub(crate) async fn invalidate(&self, rejected: &str) {
+        let mut current = self.current.lock().await;
+        if current
+            .as_ref()
+            .is_some_and(|token| token.token.as_str() == rejected)
+        {
+            *current = None;
+        }
+    }
+}
+
+impl std::fmt::Debug for TokenCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenCache").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn counting_source(expires_in: Option<Duration>) -> (Arc<AtomicU32>, Arc<dyn TokenSource>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let source = move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                let token = AccessToken::new(format!("token-{}", n));
+                Ok(match expires_in {
+                    Some(expires_in) => token.with_expires_in(expires_in),
+                    None => token,
+                })
+            }
+        };
+        (calls, Arc::new(source))
+    }
+
+    #[test]
+    fn test_access_token_expiry() {
+        assert!(!AccessToken::new("abc").is_expired());
+        assert!(!AccessToken::new("abc")
+            .with_expires_in(Duration::from_secs(3600))
+            .is_expired());
+        // Inside the refresh margin counts as expired
+        assert!(AccessToken::new("abc")
+            .with_expires_in(Duration::from_secs(10))
+            .is_expired());
+    }
+
+    #[test]
+    fn test_debug_redacts_token() {
+        let debug = format!("{:?}", AccessToken::new("very-secret-token"));
+        assert!(!debug.contains("very-secret-token"));
+    }
+
+    #[tokio::test]
+    async fn test_cache_reuses_valid_token() {
+        let (calls, source) = counting_source(Some(Duration::from_secs(3600)));
+        let cache = TokenCache::new(source);
+
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-1");
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_refreshes_expired_token() {
+        let (calls, source) = counting_source(Some(Duration::from_secs(5)));
+        let cache = TokenCache::new(source);
+
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-1");
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_only_drops_rejected_token() {
+        let (_, source) = counting_source(None);
+        let cache = TokenCache::new(source);
+
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-1");
+        cache.invalidate("some-older-token").await;
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-1");
+
+        cache.invalidate("token-1").await;
+        assert_eq!(cache.token().await.unwrap().as_str(), "token-2");
+    }
+}
@@ -0,0 +1,604 @@
+//! Blocking (synchronous) CoinPayments API client
+//!
+//! This module provides functionality for:
+//! - Calling every API endpoint without an async runtime in the caller
+//! - Wrapping an async [`crate::CoinPaymentsClient`] with its own single-threaded runtime
+//!
+//! Enabled with the `blocking` cargo feature.
+
+use crate::*;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Synchronous CoinPayments API client
+///
+/// Each call blocks the current thread until the request completes. Must not be
+/// used from within an async runtime; use the async client there instead.
+///
+/// # Example
+/// ```rust,no_run
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use coinpayments::blocking::CoinPaymentsClient;
+///
+/// let client = CoinPaymentsClient::new("your_client_id", "your_client_secret");
+/// let ping = client.ping()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CoinPaymentsClient {
+    inner: crate::CoinPaymentsClient,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl CoinPaymentsClient {
+    /// Create a new blocking client with client credentials
+    ///
+    /// # Arguments
+    /// * `client_id` - Your CoinPayments client ID
+    /// * `client_secret` - Your CoinPayments client secret
+    ///
+    /// # Panics
+    /// Panics if the internal runtime cannot be created.
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self::from_async(crate::CoinPaymentsClient::new(client_id, client_secret))
+    }
+
+    /// Create a blocking client from the `COINPAYMENTS_*` environment variables
+    pub fn from_env() -> Result<Self> {
+        crate::CoinPaymentsClient::from_env().map(Self::from_async)
+    }
+
+    /// Wrap a configured async client (e.g. one built with [`CoinPaymentsClientBuilder`])
+    ///
+    /// # Panics
+    /// Panics if the internal runtime cannot be created.
+    pub fn from_async(inner: crate::CoinPaymentsClient) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to create tokio runtime for blocking client");
+
+        Self {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// Get the underlying async client
+    pub fn inner(&self) -> &crate::CoinPaymentsClient {
+        &self.inner
+    }
+
+    /// Get the latest rate limit information reported by the API
+    pub fn rate_limit_info(&self) -> Option<RateLimitInfo> {
+        self.inner.rate_limit_info()
+    }
+
+    /// Get the estimated offset of the server clock from the local clock
+    pub fn clock_offset(&self) -> Option<chrono::Duration> {
+        self.inner.clock_offset()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    // === Client ===
+
+    /// Get client information
+    pub fn get_client_info(&self) -> Result<ClientInfo> {
+        self.block_on(self.inner.get_client_info())
+    }
+
+    /// Test API connectivity and authentication
+    pub fn ping(&self) -> Result<PingResponse> {
+        self.block_on(self.inner.ping())
+    }
+
+    /// Check connectivity, credentials, clock skew and permissions
+    pub fn self_check(&self, required: &[Permission]) -> SelfCheckReport {
+        self.block_on(self.inner.self_check(required))
+    }
+
+    /// Execute an endpoint
+    pub fn execute<E: Endpoint>(&self, endpoint: E) -> Result<E::Response> {
+        self.block_on(self.inner.execute(endpoint))
+    }
+
+    // === Currencies ===
+
+    /// Get list of supported currencies
+    pub fn get_currencies(
+        &self,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<GetCurrenciesResponse> {
+        self.block_on(self.inner.get_currencies(page, per_page))
+    }
+
+    /// Get currency by ID
+    pub fn get_currency_by_id(&self, currency_id: &str) -> Result<CurrencyV2> {
+        self.block_on(self.inner.get_currency_by_id(currency_id))
+    }
+
+    /// Get merchant's currently accepted currencies
+    pub fn get_merchant_currencies(&self) -> Result<GetMerchantCurrenciesResponse> {
+        self.block_on(self.inner.get_merchant_currencies())
+    }
+
+    /// Get latest blockchain block number by currency
+    pub fn get_latest_block_number(&self, currency_id: &str) -> Result<BlockchainNodeInfo> {
+        self.block_on(self.inner.get_latest_block_number(currency_id))
+    }
+
+    /// Get required confirmations for each currency
+    pub fn get_required_confirmations(&self) -> Result<GetRequiredConfirmationsResponse> {
+        self.block_on(self.inner.get_required_confirmations())
+    }
+
+    /// Get list of all possible currency conversions
+    pub fn get_currency_conversions(&self) -> Result<GetCurrencyConversionsResponse> {
+        self.block_on(self.inner.get_currency_conversions())
+    }
+
+    /// Get conversion limits by currency pair
+    pub fn get_currency_limits(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+    ) -> Result<CurrencyLimits> {
+        self.block_on(self.inner.get_currency_limits(from_currency, to_currency))
+    }
+
+    // === Rates ===
+
+    /// Get current conversion rates between currencies
+    pub fn get_rates(&self, query: Option<RateQuery>) -> Result<GetRatesResponse> {
+        self.block_on(self.inner.get_rates(query))
+    }
+
+    /// Get rate for a specific currency pair
+    pub fn get_rate(&self, from_currency: &str, to_currency: &str) -> Result<ExchangeRate> {
+        self.block_on(self.inner.get_rate(from_currency, to_currency))
+    }
+
+    /// Get all rates for a specific currency
+    pub fn get_currency_rates(
+        &self,
+        currency_id: &str,
+        as_base: bool,
+    ) -> Result<Vec<ExchangeRate>> {
+        self.block_on(self.inner.get_currency_rates(currency_id, as_base))
+    }
+
+    /// Get rates for multiple specific currencies
+    pub fn get_multiple_currency_rates(
+        &self,
+        currency_ids: Vec<String>,
+    ) -> Result<Vec<ExchangeRate>> {
+        self.block_on(self.inner.get_multiple_currency_rates(currency_ids))
+    }
+
+    // === Fees ===
+
+    /// Calculate blockchain fee for a transaction
+    pub fn calculate_blockchain_fee(
+        &self,
+        currency_id: &str,
+        request: Option<FeeCalculationRequest>,
+    ) -> Result<FeeCalculationResponse> {
+        self.block_on(self.inner.calculate_blockchain_fee(currency_id, request))
+    }
+
+    /// Get gas fee information for EVM-based currencies
+    pub fn get_gas_fee(&self, currency_id: &str, gas_limit: Option<u64>) -> Result<GasFee> {
+        self.block_on(self.inner.get_gas_fee(currency_id, gas_limit))
+    }
+
+    /// Get network status for fee estimation
+    pub fn get_network_status(&self, currency_id: &str) -> Result<NetworkStatus> {
+        self.block_on(self.inner.get_network_status(currency_id))
+    }
+
+    /// Get recommended fee for optimal confirmation time
+    pub fn get_recommended_fee(
+        &self,
+        currency_id: &str,
+        target_confirmation_time: u32,
+    ) -> Result<BlockchainFee> {
+        self.block_on(
+            self.inner
+                .get_recommended_fee(currency_id, target_confirmation_time),
+        )
+    }
+
+    // === Wallets ===
+
+    /// Get list of merchant wallets
+    pub fn get_wallets(
+        &self,
+        page: Option<u32>,
+        per_page: Option<u32>,
+        currency_id: Option<&str>,
+        status: Option<WalletStatus>,
+    ) -> Result<GetWalletsResponse> {
+        self.block_on(self.inner.get_wallets(page, per_page, currency_id, status))
+    }
+
+    /// Create or retrieve a wallet by external IDs
+    pub fn create_wallet(&self, request: CreateWalletRequest) -> Result<WalletResponse> {
+        self.block_on(self.inner.create_wallet(request))
+    }
+
+    /// Get wallet count
+    pub fn get_wallet_count(&self) -> Result<WalletCountResponse> {
+        self.block_on(self.inner.get_wallet_count())
+    }
+
+    /// Get wallet addresses
+    pub fn get_wallet_addresses(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<GetAddressesResponse> {
+        self.block_on(
+            self.inner
+                .get_wallet_addresses(wallet_label, currency_id, page, per_page),
+        )
+    }
+
+    /// Get wallet address count
+    pub fn get_wallet_address_count(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+    ) -> Result<AddressCountResponse> {
+        self.block_on(
+            self.inner
+                .get_wallet_address_count(wallet_label, currency_id),
+        )
+    }
+
+    /// Get specific address by label
+    pub fn get_address_by_label(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        address_label: &str,
+    ) -> Result<WalletAddress> {
+        self.block_on(
+            self.inner
+                .get_address_by_label(wallet_label, currency_id, address_label),
+        )
+    }
+
+    /// Update wallet webhook
+    pub fn update_wallet_webhook(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        webhook_config: WebhookConfig,
+    ) -> Result<()> {
+        self.block_on(
+            self.inner
+                .update_wallet_webhook(wallet_label, currency_id, webhook_config),
+        )
+    }
+
+    /// Update address webhook
+    pub fn update_address_webhook(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        address_label: &str,
+        webhook_config: WebhookConfig,
+    ) -> Result<()> {
+        self.block_on(self.inner.update_address_webhook(
+            wallet_label,
+            currency_id,
+            address_label,
+            webhook_config,
+        ))
+    }
+
+    // === Transactions ===
+
+    /// Get transaction count for a wallet
+    pub fn get_transaction_count(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+    ) -> Result<TransactionCountResponse> {
+        self.block_on(self.inner.get_transaction_count(wallet_label, currency_id))
+    }
+
+    /// Get transactions for a wallet
+    pub fn get_transactions(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        page: Option<u32>,
+        per_page: Option<u32>,
+        status: Option<TransactionStatus>,
+        transaction_type: Option<TransactionType>,
+    ) -> Result<GetTransactionsResponse> {
+        self.block_on(self.inner.get_transactions(
+            wallet_label,
+            currency_id,
+            page,
+            per_page,
+            status,
+            transaction_type,
+        ))
+    }
+
+    /// Get a specific transaction
+    pub fn get_transaction(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        transaction_id: Option<&str>,
+        spend_request_id: Option<&str>,
+    ) -> Result<Transaction> {
+        self.block_on(self.inner.get_transaction(
+            wallet_label,
+            currency_id,
+            transaction_id,
+            spend_request_id,
+        ))
+    }
+
+    /// Create a spend request
+    pub fn create_spend_request(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        request: CreateSpendRequest,
+    ) -> Result<SpendRequestResponse> {
+        self.block_on(
+            self.inner
+                .create_spend_request(wallet_label, currency_id, request),
+        )
+    }
+
+    /// Confirm a spend request
+    pub fn confirm_spend_request(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        spend_request_id: &str,
+    ) -> Result<Transaction> {
+        self.block_on(
+            self.inner
+                .confirm_spend_request(wallet_label, currency_id, spend_request_id),
+        )
+    }
+
+    /// Get wallet consolidation information
+    pub fn get_wallet_consolidation(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<Vec<ConsolidationInfo>> {
+        self.block_on(self.inner.get_wallet_consolidation(
+            wallet_label,
+            currency_id,
+            page,
+            per_page,
+        ))
+    }
+
+    /// Execute wallet consolidation
+    pub fn execute_wallet_consolidation(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        target_wallet_label: &str,
+        request: ConsolidationRequest,
+    ) -> Result<ConsolidationInfo> {
+        self.block_on(self.inner.execute_wallet_consolidation(
+            wallet_label,
+            currency_id,
+            target_wallet_label,
+            request,
+        ))
+    }
+
+    /// Execute multi-wallet consolidation
+    pub fn execute_multi_wallet_consolidation(
+        &self,
+        target_wallet_label: &str,
+        request: ConsolidationRequest,
+    ) -> Result<ConsolidationInfo> {
+        self.block_on(
+            self.inner
+                .execute_multi_wallet_consolidation(target_wallet_label, request),
+        )
+    }
+
+    /// Preview consolidation operation
+    pub fn preview_consolidation(
+        &self,
+        request: ConsolidationPreviewRequest,
+    ) -> Result<ConsolidationPreviewResponse> {
+        self.block_on(self.inner.preview_consolidation(request))
+    }
+
+    /// Get consolidation transactions
+    pub fn get_consolidation_transactions(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        consolidation_id: &str,
+    ) -> Result<Vec<Transaction>> {
+        self.block_on(self.inner.get_consolidation_transactions(
+            wallet_label,
+            currency_id,
+            consolidation_id,
+        ))
+    }
+
+    // === Invoices ===
+
+    /// Create a new invoice
+    pub fn create_invoice(&self, request: CreateInvoiceRequest) -> Result<CreateInvoiceResponse> {
+        self.block_on(self.inner.create_invoice(request))
+    }
+
+    /// Cancel an invoice
+    pub fn cancel_invoice(&self, invoice_id: &str) -> Result<()> {
+        self.block_on(self.inner.cancel_invoice(invoice_id))
+    }
+
+    /// Get list of invoices
+    pub fn get_invoices(
+        &self,
+        page: Option<u32>,
+        per_page: Option<u32>,
+        status: Option<InvoiceStatus>,
+        currency: Option<&str>,
+    ) -> Result<GetInvoicesResponse> {
+        self.block_on(self.inner.get_invoices(page, per_page, status, currency))
+    }
+
+    /// Get invoice payment information for a specific currency
+    pub fn get_invoice_payment_info(
+        &self,
+        invoice_id: &str,
+        currency_id: &str,
+    ) -> Result<PaymentInfo> {
+        self.block_on(self.inner.get_invoice_payment_info(invoice_id, currency_id))
+    }
+
+    /// Get invoice payment status for a specific currency
+    pub fn get_invoice_payment_status(
+        &self,
+        invoice_id: &str,
+        currency_id: &str,
+    ) -> Result<PaymentStatus> {
+        self.block_on(
+            self.inner
+                .get_invoice_payment_status(invoice_id, currency_id),
+        )
+    }
+
+    /// Get invoice by ID
+    pub fn get_invoice(&self, invoice_id: &str, include_payments: Option<bool>) -> Result<Invoice> {
+        self.block_on(self.inner.get_invoice(invoice_id, include_payments))
+    }
+
+    /// Get invoice payouts
+    pub fn get_invoice_payouts(&self, invoice_id: &str) -> Result<GetInvoicePayoutsResponse> {
+        self.block_on(self.inner.get_invoice_payouts(invoice_id))
+    }
+
+    /// Get invoice history
+    pub fn get_invoice_history(&self, invoice_id: &str) -> Result<GetInvoiceHistoryResponse> {
+        self.block_on(self.inner.get_invoice_history(invoice_id))
+    }
+
+    // === Webhooks ===
+
+    /// Create a client webhook
+    pub fn create_client_webhook(
+        &self,
+        client_id: &str,
+        request: CreateClientWebhookRequest,
+    ) -> Result<ClientWebhook> {
+        self.block_on(self.inner.create_client_webhook(client_id, request))
+    }
+
+    /// Update wallet webhook (v2 API - by ID)
+    pub fn update_wallet_webhook_v2(
+        &self,
+        wallet_id: &str,
+        request: UpdateWebhookRequest,
+    ) -> Result<()> {
+        self.block_on(self.inner.update_wallet_webhook_v2(wallet_id, request))
+    }
+
+    /// Update address webhook (v2 API - by ID)
+    pub fn update_address_webhook_v2(
+        &self,
+        wallet_id: &str,
+        address_id: &str,
+        request: UpdateWebhookRequest,
+    ) -> Result<()> {
+        self.block_on(
+            self.inner
+                .update_address_webhook_v2(wallet_id, address_id, request),
+        )
+    }
+
+    /// Update wallet webhook (v3 API - by label)
+    pub fn update_wallet_webhook_v3(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        request: UpdateWebhookRequest,
+    ) -> Result<()> {
+        self.block_on(
+            self.inner
+                .update_wallet_webhook_v3(wallet_label, currency_id, request),
+        )
+    }
+
+    /// Update address webhook (v3 API - by label)
+    pub fn update_address_webhook_v3(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        address_label: &str,
+        request: UpdateWebhookRequest,
+    ) -> Result<()> {
+        self.block_on(self.inner.update_address_webhook_v3(
+            wallet_label,
+            currency_id,
+            address_label,
+            request,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PONG: &str = r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#;
+
+    #[test]
+    fn test_blocking_call_without_runtime() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, PONG);
+
+        let client = CoinPaymentsClient::from_async(
+            crate::CoinPaymentsClient::new("test_client", "test_secret")
+                .with_transport(transport.clone()),
+        );
+
+        assert_eq!(client.ping().unwrap().message, "pong");
+        assert_eq!(transport.requests()[0].path(), "/api/v1/ping");
+    }
+
+    #[test]
+    fn test_blocking_errors_are_returned() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(404, r#"{"error":"Invoice not found"}"#);
+
+        let client = CoinPaymentsClient::from_async(
+            crate::CoinPaymentsClient::new("test_client", "test_secret")
+                .with_transport(transport)
+                .with_retry_policy(RetryPolicy::none()),
+        );
+
+        assert!(matches!(
+            client.get_invoice("missing", None),
+            Err(CoinPaymentsError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,453 @@
+//! Builder for configuring a CoinPayments API client
+//!
+//! This module provides functionality for:
+//! - Configuring HTTP timeouts, proxies, user agent, TLS roots and default headers
+//! - Setting the API base URL, retry policy and rate limiter
+//! - Loading credentials and base URL from the environment
+
+use crate::{
+    utils, AuthType, CircuitBreaker, CoinPaymentsClient, CoinPaymentsError, CredentialProvider,
+    Credentials, IdempotencyCache, Middleware, RateLimiter, ResponseCache, Result, RetryPolicy,
+    ServerClock, Signer, StaticCredentials, Transport, API_BASE_URL,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, Proxy};
+use std::sync::Arc;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Environment variable holding the client ID
+pub const ENV_CLIENT_ID: &str = "COINPAYMENTS_CLIENT_ID";
+/// Environment variable holding the client secret
+pub const ENV_CLIENT_SECRET: &str = "COINPAYMENTS_CLIENT_SECRET";
+/// Environment variable holding an optional base URL override
+pub const ENV_BASE_URL: &str = "COINPAYMENTS_BASE_URL";
+
+/// Builder for [`CoinPaymentsClient`]
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::CoinPaymentsClient;
+/// # use std::time::Duration;
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = CoinPaymentsClient::builder()
+///     .with_credentials("client_id", "client_secret")
+///     .with_connect_timeout(Duration::from_secs(5))
+///     .with_timeout(Duration::from_secs(20))
+///     .with_user_agent("my-shop/2.1")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CoinPaymentsClientBuilder {
+    auth: Option<AuthType>,
+    base_url: String,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: String,
+    root_certificates: Vec<Certificate>,
+    built_in_root_certs: bool,
+    default_headers: HeaderMap,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    server_clock: Option<ServerClock>,
+    signer: Option<Arc<dyn Signer>>,
+    transport: Option<Arc<dyn Transport>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    idempotency_cache: Option<IdempotencyCache>,
+    response_cache: Option<ResponseCache>,
+    circuit_breaker: Option<CircuitBreaker>,
+}
+
+impl Default for CoinPaymentsClientBuilder {
+    fn default() -> Self {
+        Self {
+            auth: None,
+            base_url: API_BASE_URL.to_string(),
+            connect_timeout: Some(Duration::from_secs(10)),
+            read_timeout: None,
+            timeout: Some(Duration::from_secs(30)),
+            proxy: None,
+            user_agent: utils::DEFAULT_USER_AGENT.to_string(),
+            root_certificates: Vec::new(),
+            built_in_root_certs: true,
+            default_headers: HeaderMap::new(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            server_clock: None,
+            signer: None,
+            transport: None,
+            middleware: Vec::new(),
+            idempotency_cache: None,
+            response_cache: None,
+            circuit_breaker: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for CoinPaymentsClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CoinPaymentsClientBuilder")
+            .field("auth", &self.auth)
+            .field("base_url", &self.base_url)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("timeout", &self.timeout)
+            .field("proxy", &self.proxy)
+            .field("user_agent", &self.user_agent)
+            .field("root_certificates", &self.root_certificates.len())
+            .field("built_in_root_certs", &self.built_in_root_certs)
+            .field("default_headers", &self.default_headers)
+            .field("retry_policy", &self.retry_policy)
+            .field("signer", &self.signer)
+            .field("transport", &self.transport)
+            .field("middleware", &self.middleware)
+            .field("idempotency_cache", &self.idempotency_cache)
+            .field("response_cache", &self.response_cache)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .finish()
+    }
+}
+
+impl CoinPaymentsClientBuilder {
+    /// Create a builder with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder from `COINPAYMENTS_CLIENT_ID`, `COINPAYMENTS_CLIENT_SECRET`
+    /// and the optional `COINPAYMENTS_BASE_URL` environment variables
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Create a builder from variables resolved by `lookup` instead of the process environment
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let credentials = Credentials::from_secret(
+            required_var(ENV_CLIENT_ID, lookup(ENV_CLIENT_ID))?.as_str(),
+            required_var(ENV_CLIENT_SECRET, lookup(ENV_CLIENT_SECRET))?,
+        );
+
+        let mut builder =
+            Self::new().with_credential_provider(Arc::new(StaticCredentials::from(credentials)));
+        if let Some(base_url) = lookup(ENV_BASE_URL) {
+            if !base_url.trim().is_empty() {
+                builder = builder.with_base_url(base_url.trim());
+            }
+        }
+        Ok(builder)
+    }
+
+    /// Set the client credentials
+    pub fn with_credentials(
+        mut self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        self.auth = Some(AuthType::ClientCredentials(Credentials::new(
+            client_id,
+            client_secret,
+        )));
+        self
+    }
+
+    /// Load the client credentials from a provider (environment, file, ...)
+    pub fn with_credential_provider(mut self, provider: Arc<dyn CredentialProvider>) -> Self {
+        self.auth = Some(AuthType::Provider(provider));
+        self
+    }
+
+    /// Set the authentication type (e.g. OAuth bearer tokens)
+    pub fn with_auth(mut self, auth: AuthType) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Set the API base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set the timeout for establishing connections
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for each read on an established connection
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the total timeout of a single HTTP attempt (`None` disables it)
+    pub fn with_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Route requests through a proxy
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Set the `User-Agent` header
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Trust an additional root certificate
+    pub fn with_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Enable or disable the built-in (webpki) root certificates
+    pub fn with_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.built_in_root_certs = enabled;
+        self
+    }
+
+    /// Add a header sent with every request
+    pub fn with_default_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            CoinPaymentsError::InvalidParameters(format!("Invalid header name: {}", name))
+        })?;
+        let value = HeaderValue::from_str(value).map_err(|_| {
+            CoinPaymentsError::InvalidParameters(format!("Invalid value for header {}", name))
+        })?;
+        self.default_headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Replace all default headers
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Use a specific (possibly shared) rate limiter
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Use a specific (possibly shared) server clock for signing timestamps
+    pub fn with_server_clock(mut self, server_clock: ServerClock) -> Self {
+        self.server_clock = Some(server_clock);
+        self
+    }
+
+    /// Use a different request signing scheme (defaults to [`V2Signer`](crate::V2Signer))
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Send requests through a custom transport
+    ///
+    /// HTTP settings (timeouts, proxy, TLS, user agent, default headers) only apply
+    /// to the default reqwest transport and are ignored when a transport is set.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Add a middleware to the end of the chain
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Replay original responses for repeated idempotency keys
+    pub fn with_idempotency_cache(mut self, cache: IdempotencyCache) -> Self {
+        self.idempotency_cache = Some(cache);
+        self
+    }
+
+    /// Cache reference data responses
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Fail fast with a circuit breaker while the API is failing
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Build the configured HTTP client
+    fn build_http_client(&self) -> Result<Client> {
+        let mut builder = Client::builder()
+            .user_agent(&self.user_agent)
+            .default_headers(self.default_headers.clone())
+            .tls_built_in_root_certs(self.built_in_root_certs);
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+
+        builder.build().map_err(CoinPaymentsError::Http)
+    }
+
+    /// Build the client
+    pub fn build(self) -> Result<CoinPaymentsClient> {
+        let auth = self.auth.clone().ok_or_else(|| {
+            CoinPaymentsError::InvalidParameters("Credentials are required".to_string())
+        })?;
+
+        let http_client = self.build_http_client()?;
+        let mut client = CoinPaymentsClient::with_client_and_auth(http_client, auth)
+            .with_base_url(self.base_url)
+            .with_retry_policy(self.retry_policy);
+        if let Some(rate_limiter) = self.rate_limiter {
+            client = client.with_rate_limiter(rate_limiter);
+        }
+        if let Some(server_clock) = self.server_clock {
+            client = client.with_server_clock(server_clock);
+        }
+        if let Some(signer) = self.signer {
+            client = client.with_signer(signer);
+        }
+        if let Some(transport) = self.transport {
+            client = client.with_transport(transport);
+        }
+        if let Some(cache) = self.idempotency_cache {
+            client = client.with_idempotency_cache(cache);
+        }
+        if let Some(cache) = self.response_cache {
+            client = client.with_response_cache(cache);
+        }
+        if let Some(circuit_breaker) = self.circuit_breaker {
+            client = client.with_circuit_breaker(circuit_breaker);
+        }
+        for middleware in self.middleware {
+            client = client.with_middleware(middleware);
+        }
+
+        Ok(client)
+    }
+}
+
+/// Read a required, non-empty environment variable, zeroizing it on drop
+pub(crate) fn read_env(name: &str) -> Result<Zeroizing<String>> {
+    required_var(name, std::env::var(name).ok())
+}
+
+/// Check that a required variable is set and non-empty, zeroizing it on drop
+fn required_var(name: &str, value: Option<String>) -> Result<Zeroizing<String>> {
+    let value = Zeroizing::new(value.unwrap_or_default());
+    if value.trim().is_empty() {
+        return Err(CoinPaymentsError::InvalidParameters(format!(
+            "Environment variable {} is not set",
+            name
+        )));
+    }
+    Ok(Zeroizing::new(value.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_requires_credentials() {
+        let result = CoinPaymentsClientBuilder::new().build();
+        assert!(matches!(
+            result,
+            Err(CoinPaymentsError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_builder_configuration() {
+        let client = CoinPaymentsClientBuilder::new()
+            .with_credentials("test_client", "test_secret")
+            .with_base_url("https://sandbox.example.com/api/")
+            .with_connect_timeout(Duration::from_secs(2))
+            .with_read_timeout(Duration::from_secs(5))
+            .with_timeout(None)
+            .with_user_agent("my-service/1.0")
+            .with_default_header("X-Tenant", "shop-1")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(client.base_url, "https://sandbox.example.com/api");
+    }
+
+    #[test]
+    fn test_default_header_validation() {
+        assert!(CoinPaymentsClientBuilder::new()
+            .with_default_header("Bad Header", "value")
+            .is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let builder =
+            CoinPaymentsClientBuilder::new().with_credentials("test_client", "super_secret");
+        let debug = format!("{:?}", builder);
+        assert!(debug.contains("test_client"));
+        assert!(!debug.contains("super_secret"));
+    }
+
+    #[test]
+    fn test_builder_with_oauth() {
+        let client = CoinPaymentsClientBuilder::new()
+            .with_auth(AuthType::OAuth("access_token".to_string().into()))
+            .build()
+            .unwrap();
+        assert!(client.token_cache.is_some());
+    }
+
+    #[test]
+    fn test_from_env() {
+        // Variables are looked up in a map so parallel tests never touch the process environment
+        let mut vars = std::collections::HashMap::from([
+            (ENV_CLIENT_ID, "env_client"),
+            (ENV_CLIENT_SECRET, " env_secret "),
+            (ENV_BASE_URL, "http://localhost:8080/api/"),
+        ]);
+        let lookup = |vars: std::collections::HashMap<&'static str, &'static str>| {
+            move |name: &str| vars.get(name).map(|value| value.to_string())
+        };
+
+        let client = CoinPaymentsClientBuilder::from_lookup(lookup(vars.clone()))
+            .unwrap()
+            .build()
+            .unwrap();
+        let credentials = client.credentials.get().unwrap();
+        assert_eq!(credentials.client_id(), "env_client");
+        assert_eq!(credentials.client_secret(), "env_secret");
+        assert_eq!(client.base_url, "http://localhost:8080/api");
+
+        vars.remove(ENV_CLIENT_SECRET);
+        assert!(CoinPaymentsClientBuilder::from_lookup(lookup(vars)).is_err());
+    }
+}
@@ -0,0 +1,512 @@
+//! Response caching for slow-changing CoinPayments reference data
+//!
+//! This module provides functionality for:
+//! - Caching currencies, required confirmations, conversions and rates with per-endpoint TTLs
+//! - Serving stale entries while they are refreshed in the background (stale-while-revalidate)
+//! - Deduplicating concurrent misses for the same request (single flight)
+//! - Pluggable storage: in-memory by default, or file-backed
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// === Cache Policies ===
+
+/// Endpoints whose responses can be cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CachedEndpoint {
+    /// `get_currencies`
+    Currencies,
+    /// `get_currency_by_id`
+    Currency,
+    /// `get_required_confirmations`
+    RequiredConfirmations,
+    /// `get_currency_conversions`
+    CurrencyConversions,
+    /// `get_rates` (and the rate helpers built on it)
+    Rates,
+}
+
+impl CachedEndpoint {
+    /// All cacheable endpoints
+    pub const ALL: [CachedEndpoint; 5] = [
+        CachedEndpoint::Currencies,
+        CachedEndpoint::Currency,
+        CachedEndpoint::RequiredConfirmations,
+        CachedEndpoint::CurrencyConversions,
+        CachedEndpoint::Rates,
+    ];
+
+    /// Prefix of the cache keys of this endpoint
+    fn key_prefix(self) -> &'static str {
+        match self {
+            CachedEndpoint::Currencies => "currencies:",
+            CachedEndpoint::Currency => "currency:",
+            CachedEndpoint::RequiredConfirmations => "required_confirmations:",
+            CachedEndpoint::CurrencyConversions => "currency_conversions:",
+            CachedEndpoint::Rates => "rates:",
+        }
+    }
+
+    /// Default policy: an hour for reference data, 30 seconds for rates
+    fn default_policy(self) -> CachePolicy {
+        match self {
+            CachedEndpoint::Rates => CachePolicy::new(Duration::from_secs(30))
+                .with_stale_while_revalidate(Duration::from_secs(30)),
+            _ => CachePolicy::new(Duration::from_secs(60 * 60))
+                .with_stale_while_revalidate(Duration::from_secs(60 * 60)),
+        }
+    }
+}
+
+/// How long responses of an endpoint are cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// How long an entry is served without contacting the API
+    pub ttl: Duration,
+    /// How long after `ttl` an entry is still served while it is refreshed in the background
+    pub stale_while_revalidate: Duration,
+}
+
+impl CachePolicy {
+    /// Create a policy without a stale window
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            stale_while_revalidate: Duration::ZERO,
+        }
+    }
+
+    /// Serve expired entries for up to `window` while refreshing them
+    pub fn with_stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = window;
+        self
+    }
+}
+
+// === Storage ===
+
+/// A cached response body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    /// Response body (JSON)
+    pub body: String,
+    /// When the response was received
+    pub stored_at: SystemTime,
+}
+
+/// Storage backend of a [`ResponseCache`]
+///
+/// Storage is best effort: failures should be treated as misses, not errors.
+pub trait CacheStore: Send + Sync + std::fmt::Debug {
+    /// Get the entry for a key
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Store an entry, replacing any previous one
+    fn set(&self, key: &str, entry: CacheEntry);
+
+    /// Remove the entry for a key
+    fn remove(&self, key: &str);
+
+    /// All stored keys
+    fn keys(&self) -> Vec<String>;
+}
+
+/// In-memory cache storage
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl MemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, entry: CacheEntry) {
+        self.entries.lock().unwrap().insert(key.to_string(), entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.entries.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+/// Cache storage keeping one JSON file per entry in a directory
+///
+/// Entries survive restarts, so a fresh process can start from cached reference data.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    directory: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    key: String,
+    stored_at_ms: u64,
+    body: String,
+}
+
+impl FileStore {
+    /// Create a store in `directory`, creating the directory if needed
+    pub fn new(directory: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let name = hex::encode(Sha256::digest(key.as_bytes()));
+        self.directory.join(format!("{}.json", name))
+    }
+
+    fn read(path: &std::path::Path) -> Option<StoredEntry> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+}
+
+impl CacheStore for FileStore {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let stored = Self::read(&self.path(key)).filter(|stored| stored.key == key)?;
+        Some(CacheEntry {
+            body: stored.body,
+            stored_at: UNIX_EPOCH + Duration::from_millis(stored.stored_at_ms),
+        })
+    }
+
+    fn set(&self, key: &str, entry: CacheEntry) {
+        let stored = StoredEntry {
+            key: key.to_string(),
+            stored_at_ms: entry
+                .stored_at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_millis() as u64),
+            body: entry.body,
+        };
+        if let Ok(contents) = serde_json::to_string(&stored) {
+            // Write then rename so readers never see a partial file; every write gets its
+            // own temporary file so concurrent writers of a key do not clobber each other
+            let path = self.path(key);
+            let temporary =
+                path.with_extension(format!("{}.tmp", crate::generate_random_string(12)));
+            if std::fs::write(&temporary, contents).is_ok() {
+                let _ = std::fs::rename(&temporary, &path);
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = std::fs::remove_file(self.path(key));
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.directory) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| Self::read(&entry.path()))
+            .map(|stored| stored.key)
+            .collect()
+    }
+}
+
+// === Response Cache ===
+
+/// Opt-in cache for reference data responses, shared by all clones of a client
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CachePolicy, CachedEndpoint, CoinPaymentsClient, ResponseCache};
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cache = ResponseCache::new()
+///     .with_policy(CachedEndpoint::Rates, CachePolicy::new(Duration::from_secs(10)));
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_response_cache(cache.clone());
+///
+/// client.get_currencies(None, None).await?; // fetched
+/// client.get_currencies(None, None).await?; // served from cache
+/// cache.invalidate(CachedEndpoint::Currencies);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    policies: HashMap<CachedEndpoint, CachePolicy>,
+    // One lock per key, held while fetching so concurrent misses share one request
+    flights: Flights,
+}
+
+type Flights = Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// Fetch of a key in progress; its lock is pruned once no other call waits for it
+pub(crate) struct Flight {
+    key: String,
+    flights: Flights,
+    guard: Option<tokio::sync::OwnedMutexGuard<()>>,
+}
+
+impl Drop for Flight {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        let mut flights = self.flights.lock().unwrap();
+        if flights
+            .get(&self.key)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            flights.remove(&self.key);
+        }
+    }
+}
+
+/// Result of a cache lookup
+pub(crate) enum Lookup {
+    Fresh(String),
+    Stale(String),
+    Miss,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponseCache {
+    /// Create an in-memory cache with the default policies for all cacheable endpoints
+    pub fn new() -> Self {
+        Self::with_store(Arc::new(MemoryStore::new()))
+    }
+
+    /// Create a cache using the given storage
+    pub fn with_store(store: Arc<dyn CacheStore>) -> Self {
+        Self {
+            store,
+            policies: CachedEndpoint::ALL
+                .into_iter()
+                .map(|endpoint| (endpoint, endpoint.default_policy()))
+                .collect(),
+            flights: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Set the policy of an endpoint
+    pub fn with_policy(mut self, endpoint: CachedEndpoint, policy: CachePolicy) -> Self {
+        self.policies.insert(endpoint, policy);
+        self
+    }
+
+    /// Stop caching an endpoint
+    pub fn without_endpoint(mut self, endpoint: CachedEndpoint) -> Self {
+        self.policies.remove(&endpoint);
+        self
+    }
+
+    /// Policy of an endpoint, if it is cached
+    pub fn policy(&self, endpoint: CachedEndpoint) -> Option<CachePolicy> {
+        self.policies.get(&endpoint).copied()
+    }
+
+    /// Drop all cached responses of an endpoint
+    pub fn invalidate(&self, endpoint: CachedEndpoint) {
+        for key in self.store.keys() {
+            if key.starts_with(endpoint.key_prefix()) {
+                self.store.remove(&key);
+            }
+        }
+    }
+
+    /// Drop all cached responses
+    pub fn invalidate_all(&self) {
+        for endpoint in CachedEndpoint::ALL {
+            self.invalidate(endpoint);
+        }
+    }
+
+    /// Cache key of a request
+    pub(crate) fn key(endpoint: CachedEndpoint, path_and_query: &str) -> String {
+        format!("{}{}", endpoint.key_prefix(), path_and_query)
+    }
+
+    /// Look up a key, classifying the entry by its age
+    pub(crate) fn lookup(&self, key: &str, policy: CachePolicy) -> Lookup {
+        let Some(entry) = self.store.get(key) else {
+            return Lookup::Miss;
+        };
+        // Entries from the future (clock changes) count as just stored
+        let age = entry.stored_at.elapsed().unwrap_or(Duration::ZERO);
+        if age < policy.ttl {
+            Lookup::Fresh(entry.body)
+        } else if age < policy.ttl + policy.stale_while_revalidate {
+            Lookup::Stale(entry.body)
+        } else {
+            Lookup::Miss
+        }
+    }
+
+    /// Store a response body
+    pub(crate) fn store(&self, key: &str, body: String) {
+        self.store.set(
+            key,
+            CacheEntry {
+                body,
+                stored_at: SystemTime::now(),
+            },
+        );
+    }
+
+    /// Wait for the fetch of a key in progress, if any, and start a new one
+    pub(crate) async fn join_flight(&self, key: &str) -> Flight {
+        let lock = self.flight_lock(key);
+        Flight {
+            key: key.to_string(),
+            flights: self.flights.clone(),
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+
+    /// Start a fetch of a key unless one is already in progress
+    pub(crate) fn try_flight(&self, key: &str) -> Option<Flight> {
+        let flight = Flight {
+            key: key.to_string(),
+            flights: self.flights.clone(),
+            guard: self.flight_lock(key).try_lock_owned().ok(),
+        };
+        // A failed attempt is still dropped as a flight, so it cannot leak the lock
+        flight.guard.is_some().then_some(flight)
+    }
+
+    /// Lock shared by all fetches of a key
+    fn flight_lock(&self, key: &str) -> Arc<tokio::sync::Mutex<()>> {
+        self.flights
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(body: &str, age: Duration) -> CacheEntry {
+        CacheEntry {
+            body: body.to_string(),
+            stored_at: SystemTime::now() - age,
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_age() {
+        let store = Arc::new(MemoryStore::new());
+        let cache = ResponseCache::with_store(store.clone());
+        let policy = CachePolicy::new(Duration::from_secs(60))
+            .with_stale_while_revalidate(Duration::from_secs(60));
+
+        store.set("fresh", entry("1", Duration::from_secs(10)));
+        store.set("stale", entry("2", Duration::from_secs(90)));
+        store.set("expired", entry("3", Duration::from_secs(200)));
+
+        assert!(matches!(cache.lookup("fresh", policy), Lookup::Fresh(body) if body == "1"));
+        assert!(matches!(cache.lookup("stale", policy), Lookup::Stale(body) if body == "2"));
+        assert!(matches!(cache.lookup("expired", policy), Lookup::Miss));
+        assert!(matches!(cache.lookup("unknown", policy), Lookup::Miss));
+    }
+
+    #[test]
+    fn test_invalidation_by_endpoint() {
+        let cache = ResponseCache::new();
+        let rates = ResponseCache::key(CachedEndpoint::Rates, "v2/rates?from=BTC");
+        let currencies = ResponseCache::key(CachedEndpoint::Currencies, "v2/currencies");
+        cache.store(&rates, "{}".to_string());
+        cache.store(&currencies, "{}".to_string());
+
+        cache.invalidate(CachedEndpoint::Rates);
+        assert!(cache.store.get(&rates).is_none());
+        assert!(cache.store.get(&currencies).is_some());
+
+        cache.invalidate_all();
+        assert!(cache.store.keys().is_empty());
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let directory = std::env::temp_dir().join(format!(
+            "coinpayments-cache-test-{}",
+            crate::generate_random_string(12)
+        ));
+        let store = FileStore::new(&directory).unwrap();
+        let stored = entry(r#"{"currencies":[]}"#, Duration::ZERO);
+
+        store.set("currencies:v2/currencies", stored.clone());
+        let reopened = FileStore::new(&directory).unwrap();
+        let loaded = reopened.get("currencies:v2/currencies").unwrap();
+        assert_eq!(loaded.body, stored.body);
+        assert_eq!(reopened.keys(), vec!["currencies:v2/currencies"]);
+
+        reopened.remove("currencies:v2/currencies");
+        assert!(store.get("currencies:v2/currencies").is_none());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flights_are_shared_and_pruned() {
+        let cache = ResponseCache::new();
+        let flight = cache.join_flight("rates:v2/rates").await;
+        assert!(cache.try_flight("rates:v2/rates").is_none());
+        assert!(cache.try_flight("rates:v2/rates?from=4").is_some());
+        assert_eq!(cache.flights.lock().unwrap().len(), 1);
+
+        drop(flight);
+        assert!(cache.flights.lock().unwrap().is_empty());
+        drop(cache.try_flight("rates:v2/rates").unwrap());
+        assert!(cache.flights.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_store_concurrent_writes_of_one_key() {
+        let directory = std::env::temp_dir().join(format!(
+            "coinpayments-cache-test-{}",
+            crate::generate_random_string(12)
+        ));
+        let store = Arc::new(FileStore::new(&directory).unwrap());
+
+        let writers: Vec<_> = (0..8)
+            .map(|index| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..20 {
+                        store.set("rates:v2/rates", entry(&index.to_string(), Duration::ZERO));
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert!(store.get("rates:v2/rates").is_some());
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,543 @@
+//! Record-and-replay HTTP cassettes for deterministic tests
+//!
+//! This module provides functionality for:
+//! - Recording real request/response pairs to a JSON cassette file
+//! - Scrubbing secrets, signatures and timestamps before anything is written
+//! - Replaying recorded responses offline, matched on method, endpoint, query and body
+
+use crate::transport::TransportFuture;
+use crate::{CoinPaymentsError, HttpRequest, HttpResponse, Result, Transport};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Replacement for scrubbed values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values are scrubbed from recorded requests and responses
+pub const SCRUBBED_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "set-cookie",
+    "x-coinpayments-client",
+    "x-coinpayments-signature",
+    "x-coinpayments-timestamp",
+];
+
+/// Headers left out of recordings because they change on every run
+const VOLATILE_HEADERS: &[&str] = &["date", "idempotency-key", "x-request-id"];
+
+/// JSON fields and query parameters scrubbed by default
+///
+/// Names are matched case-insensitively and ignoring `_` and `-`, so `client_secret`
+/// also scrubs `clientSecret`.
+const DEFAULT_SCRUBBED_FIELDS: &[&str] = &[
+    "access_token",
+    "client_secret",
+    "password",
+    "private_key",
+    "secret",
+    "signature",
+    "token",
+];
+
+// === Cassette Types ===
+
+/// Recorded interactions, as stored in a cassette file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Interactions in the order they were recorded
+    pub interactions: Vec<Interaction>,
+}
+
+/// One recorded request and the response it received
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Scrubbed request
+    pub request: RecordedRequest,
+    /// Scrubbed response
+    pub response: RecordedResponse,
+}
+
+/// Request as stored in a cassette
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Path from the API version on (e.g. `/v2/merchant/invoices`), independent of the base URL
+    pub endpoint: String,
+    /// Query parameters, sorted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<(String, String)>,
+    /// Body as JSON, or as a string when it is not JSON (`null` when empty)
+    #[serde(default)]
+    pub body: Value,
+    /// Request headers
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// Response as stored in a cassette
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Body as JSON, or as a string when it is not JSON (`null` when empty)
+    #[serde(default)]
+    pub body: Value,
+}
+
+impl Cassette {
+    /// Load a cassette file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            CoinPaymentsError::InvalidParameters(format!(
+                "Cannot read cassette {}: {}",
+                path.display(),
+                error
+            ))
+        })?;
+        serde_json::from_str(&contents).map_err(|error| {
+            CoinPaymentsError::InvalidParameters(format!(
+                "Invalid cassette {}: {}",
+                path.display(),
+                error
+            ))
+        })
+    }
+
+    /// Write the cassette as pretty-printed JSON, creating parent directories
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut contents = serde_json::to_string_pretty(self)?;
+            contents.push('\n');
+            std::fs::write(path, contents)
+        };
+        write().map_err(|error| {
+            CoinPaymentsError::InvalidParameters(format!(
+                "Cannot write cassette {}: {}",
+                path.display(),
+                error
+            ))
+        })
+    }
+}
+
+impl RecordedRequest {
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method
+            && self.endpoint == other.endpoint
+            && self.query == other.query
+            && self.body == other.body
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> HttpResponse {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = match &self.body {
+            Value::Null => String::new(),
+            Value::String(text) => text.clone(),
+            json => json.to_string(),
+        };
+        self.headers.iter().fold(
+            HttpResponse::new(status, body),
+            |response, (name, value)| response.with_header(name, value),
+        )
+    }
+}
+
+// === Cassette Transport ===
+
+/// Whether a [`CassetteTransport`] records or replays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests through the inner transport and record them
+    Record,
+    /// Answer requests from the cassette without network access
+    Replay,
+}
+
+/// Transport recording interactions to, or replaying them from, a cassette file
+///
+/// In record mode every request is sent through the inner transport and the cassette
+/// file is rewritten after each response, so nothing is lost if a test panics. Before
+/// anything is written, authentication headers are redacted, volatile headers (`Date`,
+/// idempotency keys, request IDs) are dropped, and JSON fields and query parameters
+/// named like secrets are redacted.
+///
+/// In replay mode each request is answered with the first unused interaction that has
+/// the same method, endpoint, query and (scrubbed) body. The endpoint is the path from
+/// the API version on, so cassettes recorded against one base URL replay against
+/// another. Unmatched requests fail with [`CoinPaymentsError::Network`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CassetteTransport, CoinPaymentsClient, ReqwestTransport};
+/// # use std::sync::Arc;
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// // Record once against the real API...
+/// let transport = CassetteTransport::record(
+///     "tests/cassettes/create_invoice.json",
+///     Arc::new(ReqwestTransport::new(reqwest::Client::new())),
+/// );
+/// // ...then replay offline in CI
+/// let transport = CassetteTransport::replay("tests/cassettes/create_invoice.json")?;
+///
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_transport(Arc::new(transport));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CassetteTransport {
+    mode: CassetteMode,
+    path: PathBuf,
+    inner: Option<Arc<dyn Transport>>,
+    scrubbed_fields: Vec<String>,
+    state: Mutex<CassetteState>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    cassette: Cassette,
+    used: Vec<bool>,
+}
+
+impl CassetteTransport {
+    /// Record interactions sent through `inner` to `path`, replacing its contents
+    pub fn record(path: impl Into<PathBuf>, inner: Arc<dyn Transport>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path: path.into(),
+            inner: Some(inner),
+            scrubbed_fields: default_scrubbed_fields(),
+            state: Mutex::new(CassetteState::default()),
+        }
+    }
+
+    /// Replay interactions recorded in `path`
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let cassette = Cassette::load(&path)?;
+        let used = vec![false; cassette.interactions.len()];
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            path,
+            inner: None,
+            scrubbed_fields: default_scrubbed_fields(),
+            state: Mutex::new(CassetteState { cassette, used }),
+        })
+    }
+
+    /// Also scrub JSON fields and query parameters with this name
+    ///
+    /// Must be set the same way when recording and replaying, since bodies are matched
+    /// after scrubbing.
+    pub fn with_scrubbed_field(mut self, name: impl Into<String>) -> Self {
+        self.scrubbed_fields.push(field_key(&name.into()));
+        self
+    }
+
+    /// Whether the transport records or replays
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Interactions recorded or loaded so far
+    pub fn cassette(&self) -> Cassette {
+        self.state.lock().unwrap().cassette.clone()
+    }
+
+    /// Number of loaded interactions not replayed yet
+    pub fn remaining(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.used.iter().filter(|used| !**used).count()
+    }
+
+    fn record_request(&self, request: &HttpRequest) -> RecordedRequest {
+        let (_, query) = request.url.split_once('?').unwrap_or_default();
+        let mut query: Vec<(String, String)> = url::form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .map(|(name, value)| {
+                let value = if self.is_scrubbed(&name) {
+                    REDACTED.to_string()
+                } else {
+                    value
+                };
+                (name, value)
+            })
+            .collect();
+        query.sort();
+
+        RecordedRequest {
+            method: request.method.to_string(),
+            endpoint: endpoint(request.path()).to_string(),
+            query,
+            body: self.record_body(&request.body),
+            headers: record_headers(&request.headers),
+        }
+    }
+
+    fn record_response(&self, response: &HttpResponse) -> RecordedResponse {
+        RecordedResponse {
+            status: response.status.as_u16(),
+            headers: record_headers(&response.headers),
+            body: self.record_body(&response.body),
+        }
+    }
+
+    fn record_body(&self, body: &[u8]) -> Value {
+        if body.is_empty() {
+            return Value::Null;
+        }
+        match serde_json::from_slice::<Value>(body) {
+            Ok(mut json) => {
+                self.scrub(&mut json);
+                json
+            }
+            Err(_) => Value::String(String::from_utf8_lossy(body).into_owned()),
+        }
+    }
+
+    fn scrub(&self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (name, field) in fields.iter_mut() {
+                    if self.is_scrubbed(name) && !field.is_null() {
+                        *field = Value::String(REDACTED.to_string());
+                    } else {
+                        self.scrub(field);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.scrub(item)),
+            _ => {}
+        }
+    }
+
+    fn is_scrubbed(&self, name: &str) -> bool {
+        self.scrubbed_fields.contains(&field_key(name))
+    }
+
+    fn replay_request(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let recorded = self.record_request(request);
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let index = state
+            .cassette
+            .interactions
+            .iter()
+            .zip(&state.used)
+            .position(|(interaction, used)| !used && interaction.request.matches(&recorded))
+            .ok_or_else(|| {
+                CoinPaymentsError::Network(format!(
+                    "No interaction in cassette {} matches {} {}",
+                    self.path.display(),
+                    recorded.method,
+                    request.url
+                ))
+            })?;
+        state.used[index] = true;
+        Ok(state.cassette.interactions[index].response.to_response())
+    }
+}
+
+impl Transport for CassetteTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let Some(inner) = self.inner.as_ref() else {
+                return self.replay_request(&request);
+            };
+
+            let recorded = self.record_request(&request);
+            let response = inner.send(request).await?;
+            let mut state = self.state.lock().unwrap();
+            state.cassette.interactions.push(Interaction {
+                request: recorded,
+                response: self.record_response(&response),
+            });
+            state.used.push(true);
+            state.cassette.save(&self.path)?;
+            Ok(response)
+        })
+    }
+}
+
+// === Helper Functions ===
+
+fn default_scrubbed_fields() -> Vec<String> {
+    DEFAULT_SCRUBBED_FIELDS
+        .iter()
+        .map(|field| field_key(field))
+        .collect()
+}
+
+/// Field name normalized for matching (`client_secret`, `clientSecret` -> `clientsecret`)
+fn field_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Path from the API version segment on (`/api/v2/rates` -> `/v2/rates`)
+fn endpoint(path: &str) -> &str {
+    let mut offset: usize = 0;
+    for segment in path.split('/') {
+        let is_version = segment.len() > 1
+            && segment.starts_with('v')
+            && segment[1..].chars().all(|c| c.is_ascii_digit());
+        if is_version {
+            return &path[offset.saturating_sub(1)..];
+        }
+        offset += segment.len() + 1;
+    }
+    path
+}
+
+fn record_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| !VOLATILE_HEADERS.contains(&name.as_str()))
+        .map(|(name, value)| {
+            let value = if SCRUBBED_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CoinPaymentsClient, CreateClientWebhookRequest, MockTransport, RetryPolicy};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "coinpayments-cassette-{}-{}.json",
+            name,
+            crate::generate_random_string(12)
+        ))
+    }
+
+    fn client(transport: Arc<dyn Transport>) -> CoinPaymentsClient {
+        CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy::none())
+    }
+
+    #[test]
+    fn test_endpoint() {
+        assert_eq!(
+            endpoint("/api/v2/merchant/invoices"),
+            "/v2/merchant/invoices"
+        );
+        assert_eq!(endpoint("/v1/ping"), "/v1/ping");
+        assert_eq!(endpoint("/api/version/x"), "/api/version/x");
+    }
+
+    #[tokio::test]
+    async fn test_record_scrubs_and_replay_matches() {
+        let path = temp_path("roundtrip");
+        let mock = Arc::new(MockTransport::new());
+        mock.push_response(
+            HttpResponse::new(
+                StatusCode::OK,
+                r#"{"id":"wh_1","clientId":"test_client","url":"https://shop.example/hook","events":["invoicePaid"],"secret":"hook-secret","isActive":true,"createdAt":"2023-01-01T00:00:00Z","updatedAt":"2023-01-01T00:00:00Z"}"#,
+            )
+            .with_header("Date", "Sun, 01 Jan 2023 00:00:00 GMT"),
+        );
+
+        let recorder = Arc::new(CassetteTransport::record(&path, mock));
+        let request = || {
+            CreateClientWebhookRequest::new("https://shop.example/hook")
+                .with_secret("hook-secret".to_string())
+        };
+        client(recorder.clone())
+            .create_client_webhook("test_client", request())
+            .await
+            .unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(!written.contains("hook-secret"));
+        assert!(!written.contains("test_secret"));
+        let interaction = &Cassette::load(&path).unwrap().interactions[0];
+        assert_eq!(
+            interaction.request.endpoint,
+            "/v1/merchant/clients/test_client/webhooks"
+        );
+        assert_eq!(
+            interaction.request.headers["x-coinpayments-signature"],
+            REDACTED
+        );
+        assert!(!interaction.response.headers.contains_key("date"));
+
+        let replayer = Arc::new(CassetteTransport::replay(&path).unwrap());
+        let webhook = client(replayer.clone())
+            .with_base_url("https://staging.example/api")
+            .create_client_webhook("test_client", request())
+            .await
+            .unwrap();
+        assert_eq!(webhook.id, "wh_1");
+        assert_eq!(webhook.secret.as_deref(), Some(REDACTED));
+        assert_eq!(replayer.remaining(), 0);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_in_order_and_rejects_unmatched() {
+        let path = temp_path("order");
+        let recorded = |page: &str, body: &str| Interaction {
+            request: RecordedRequest {
+                method: "GET".to_string(),
+                endpoint: "/v2/currencies".to_string(),
+                query: vec![("page".to_string(), page.to_string())],
+                body: Value::Null,
+                headers: BTreeMap::new(),
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: BTreeMap::new(),
+                body: serde_json::from_str(body).unwrap(),
+            },
+        };
+        let empty = r#"{"currencies":[],"pagination":null}"#;
+        Cassette {
+            interactions: vec![
+                recorded("1", empty),
+                recorded("1", empty),
+                recorded("2", empty),
+            ],
+        }
+        .save(&path)
+        .unwrap();
+
+        let transport = Arc::new(CassetteTransport::replay(&path).unwrap());
+        let client = client(transport.clone());
+        client.get_currencies(Some(2), None).await.unwrap();
+        client.get_currencies(Some(1), None).await.unwrap();
+        client.get_currencies(Some(1), None).await.unwrap();
+        assert_eq!(transport.remaining(), 0);
+        assert!(matches!(
+            client.get_currencies(Some(1), None).await,
+            Err(CoinPaymentsError::Network(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,344 @@
+//! Circuit breaking for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Tracking the failure rate of recent requests
+//! - Failing fast with [`CoinPaymentsError::CircuitOpen`] while the API is failing
+//! - Probing the API (e.g. with `ping()`) before letting traffic through again
+
+use crate::{CoinPaymentsError, Result};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    static PROBE: ();
+}
+
+// === Circuit Breaker Types ===
+
+/// State of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent normally
+    Closed,
+    /// Requests fail fast until the open duration has passed
+    Open,
+    /// A single probe request is let through to test whether the API recovered
+    HalfOpen,
+}
+
+/// Circuit breaker shared by all clones of a client
+///
+/// Transport failures and `5xx` responses count as failures. Once the failure rate of
+/// the last `window_size` requests reaches the threshold the circuit opens, and calls
+/// fail with [`CoinPaymentsError::CircuitOpen`] without contacting the API. After the
+/// open duration one probe request is let through: success closes the circuit, failure
+/// opens it again. `ping()` is always allowed through as a probe.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CircuitBreaker, CoinPaymentsClient};
+/// # use std::time::Duration;
+/// # fn example() {
+/// let breaker = CircuitBreaker::new()
+///     .with_failure_rate_threshold(0.5)
+///     .with_open_duration(Duration::from_secs(15));
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_circuit_breaker(breaker.clone());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_rate_threshold: f64,
+    minimum_requests: usize,
+    window_size: usize,
+    open_duration: Duration,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    // Outcomes of the latest requests while closed (`true` = failure)
+    outcomes: VecDeque<bool>,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a breaker opening at a 50% failure rate over the last 20 requests (minimum 10)
+    /// and staying open for 30 seconds
+    pub fn new() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_requests: 10,
+            window_size: 20,
+            open_duration: Duration::from_secs(30),
+            state: Arc::new(Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                probe_in_flight: false,
+            })),
+        }
+    }
+
+    /// Set the failure rate (0.0 - 1.0) at which the circuit opens
+    pub fn with_failure_rate_threshold(mut self, threshold: f64) -> Self {
+        self.failure_rate_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the number of requests needed before the failure rate is evaluated
+    pub fn with_minimum_requests(mut self, minimum_requests: usize) -> Self {
+        self.minimum_requests = minimum_requests.max(1);
+        self.window_size = self.window_size.max(self.minimum_requests);
+        self
+    }
+
+    /// Set the number of latest requests the failure rate is computed over
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(self.minimum_requests);
+        self
+    }
+
+    /// Set how long the circuit stays open before a probe is let through
+    pub fn with_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+
+    /// Current state of the circuit
+    pub fn state(&self) -> CircuitState {
+        let mut state = self.state.lock().unwrap();
+        self.refresh(&mut state, Instant::now());
+        state.state
+    }
+
+    /// Close the circuit and forget recorded outcomes
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.close();
+    }
+
+    /// Check whether a request may be sent, failing fast while the circuit is open
+    pub(crate) fn acquire(&self) -> Result<CircuitPermit<'_>> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        self.refresh(&mut state, now);
+
+        let probe = PROBE.try_with(|_| ()).is_ok();
+        let is_probe = match state.state {
+            CircuitState::Closed => false,
+            CircuitState::HalfOpen if !state.probe_in_flight => {
+                state.probe_in_flight = true;
+                true
+            }
+            CircuitState::Open if probe => {
+                state.state = CircuitState::HalfOpen;
+                state.probe_in_flight = true;
+                true
+            }
+            _ => {
+                return Err(CoinPaymentsError::CircuitOpen {
+                    retry_after: state
+                        .opened_at
+                        .map(|opened_at| {
+                            (opened_at + self.open_duration).saturating_duration_since(now)
+                        })
+                        .unwrap_or_default(),
+                })
+            }
+        };
+        Ok(CircuitPermit {
+            breaker: self,
+            is_probe,
+            recorded: false,
+        })
+    }
+
+    /// Record the outcome of a request that was let through
+    ///
+    /// Only the probe decides a half-open circuit; requests started while the circuit
+    /// was still closed may finish after it moved on and are ignored then.
+    fn record(&self, is_probe: bool, failed: bool) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        match state.state {
+            CircuitState::HalfOpen if !is_probe => {}
+            CircuitState::HalfOpen if failed => state.open(now),
+            CircuitState::HalfOpen => state.close(),
+            CircuitState::Closed => {
+                state.outcomes.push_back(failed);
+                while state.outcomes.len() > self.window_size {
+                    state.outcomes.pop_front();
+                }
+
+                let total = state.outcomes.len();
+                let failures = state.outcomes.iter().filter(|failed| **failed).count();
+                if total >= self.minimum_requests
+                    && failures as f64 / total as f64 >= self.failure_rate_threshold
+                {
+                    state.open(now);
+                }
+            }
+            // Requests started before the circuit opened
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Move an open circuit to half-open once the open duration has passed
+    fn refresh(&self, state: &mut BreakerState, now: Instant) {
+        if state.state == CircuitState::Open
+            && state
+                .opened_at
+                .is_some_and(|opened_at| now >= opened_at + self.open_duration)
+        {
+            state.state = CircuitState::HalfOpen;
+            state.probe_in_flight = false;
+        }
+    }
+}
+
+/// Permission to send one request; its outcome must be recorded
+pub(crate) struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    is_probe: bool,
+    recorded: bool,
+}
+
+impl CircuitPermit<'_> {
+    /// Record whether the request failed
+    pub(crate) fn record(mut self, failed: bool) {
+        self.recorded = true;
+        self.breaker.record(self.is_probe, failed);
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        // A cancelled probe lets the next request probe instead
+        if self.is_probe && !self.recorded {
+            self.breaker.state.lock().unwrap().probe_in_flight = false;
+        }
+    }
+}
+
+impl BreakerState {
+    fn open(&mut self, now: Instant) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(now);
+        self.outcomes.clear();
+        self.probe_in_flight = false;
+    }
+
+    fn close(&mut self) {
+        self.state = CircuitState::Closed;
+        self.opened_at = None;
+        self.outcomes.clear();
+        self.probe_in_flight = false;
+    }
+}
+
+/// Run a call as a probe that is let through an open circuit
+pub(crate) async fn probe<T>(call: impl Future<Output = T>) -> T {
+    PROBE.scope((), call).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new()
+            .with_minimum_requests(4)
+            .with_window_size(4)
+            .with_failure_rate_threshold(0.5)
+            .with_open_duration(Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_opens_at_failure_rate() {
+        let breaker = breaker();
+        for failed in [false, true, false] {
+            breaker.acquire().unwrap().record(failed);
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.acquire().unwrap().record(true);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            breaker.acquire(),
+            Err(CoinPaymentsError::CircuitOpen { retry_after }) if retry_after > Duration::from_secs(50)
+        ));
+
+        breaker.reset();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let breaker = breaker().with_open_duration(Duration::ZERO);
+        for _ in 0..4 {
+            breaker.record(false, true);
+        }
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        let probe = breaker.acquire().unwrap();
+        assert!(breaker.acquire().is_err());
+        probe.record(false);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_probe_passes_open_circuit() {
+        let breaker = breaker();
+        for _ in 0..4 {
+            breaker.record(false, true);
+        }
+        assert!(breaker.acquire().is_err());
+
+        let permit = probe(async { breaker.acquire() }).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        permit.record(true);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A dropped probe frees the half-open slot
+        drop(probe(async { breaker.acquire() }).await.unwrap());
+        probe(async { breaker.acquire() })
+            .await
+            .unwrap()
+            .record(false);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_stale_completion_does_not_decide_half_open() {
+        let breaker = breaker().with_open_duration(Duration::ZERO);
+        let slow = breaker.acquire().unwrap();
+        let cancelled = breaker.acquire().unwrap();
+        for _ in 0..4 {
+            breaker.record(false, true);
+        }
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        let probe = breaker.acquire().unwrap();
+        slow.record(false);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // A dropped non-probe permit keeps the probe slot taken
+        drop(cancelled);
+        assert!(breaker.acquire().is_err());
+
+        probe.record(true);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.acquire().is_ok());
+    }
+}
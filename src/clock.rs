@@ -0,0 +1,192 @@
+//! Server clock tracking for CoinPayments request signing
+//!
+//! This module provides functionality for:
+//! - Estimating the offset between the local clock and server time
+//! - Learning the offset from `Date` response headers and `ping` timestamps
+//! - Correcting `X-CoinPayments-Timestamp` for hosts with a drifting clock
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, DATE};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Offset beyond which an authentication failure is reported as clock skew
+pub const DEFAULT_SKEW_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Smallest offset learned from a `Date` header that is applied to timestamps
+///
+/// `Date` headers only have one-second resolution, so smaller offsets are noise.
+const MIN_DATE_HEADER_OFFSET_MS: i64 = 2_000;
+
+// === Server Clock ===
+
+/// Estimated server time, shared by all clones of a client
+#[derive(Debug, Clone)]
+pub struct ServerClock {
+    offset_ms: Arc<Mutex<Option<i64>>>,
+    skew_threshold: Duration,
+}
+
+impl Default for ServerClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerClock {
+    /// Create a clock without a known offset (local time is used until a response arrives)
+    pub fn new() -> Self {
+        Self {
+            offset_ms: Arc::new(Mutex::new(None)),
+            skew_threshold: DEFAULT_SKEW_THRESHOLD,
+        }
+    }
+
+    /// Set the offset beyond which a rejected signature is reported as clock skew
+    pub fn with_skew_threshold(mut self, threshold: Duration) -> Self {
+        self.skew_threshold = threshold;
+        self
+    }
+
+    /// Estimated server time minus local time, if known
+    pub fn offset(&self) -> Option<chrono::Duration> {
+        (*self.offset_ms.lock().unwrap()).map(chrono::Duration::milliseconds)
+    }
+
+    /// Current time corrected by the estimated offset
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset().unwrap_or_else(chrono::Duration::zero)
+    }
+
+    /// Check whether the estimated offset exceeds the skew threshold
+    pub fn is_skewed(&self) -> bool {
+        self.offset()
+            .and_then(|offset| offset.abs().to_std().ok())
+            .is_some_and(|offset| offset > self.skew_threshold)
+    }
+
+    /// Learn the offset from the `Date` header of a response
+    ///
+    /// # Arguments
+    /// * `headers` - Response headers
+    /// * `sent_at` - Local time the request was sent
+    /// * `received_at` - Local time the response was received
+    pub fn update_from_headers(
+        &self,
+        headers: &HeaderMap,
+        sent_at: DateTime<Utc>,
+        received_at: DateTime<Utc>,
+    ) {
+        let Some(server_time) = headers
+            .get(DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        else {
+            return;
+        };
+
+        // The header truncates to whole seconds: compare against its midpoint
+        let server_time = server_time.with_timezone(&Utc) + chrono::Duration::milliseconds(500);
+        let offset_ms = estimate_offset_ms(server_time, sent_at, received_at);
+        let offset_ms = if offset_ms.abs() < MIN_DATE_HEADER_OFFSET_MS {
+            0
+        } else {
+            offset_ms
+        };
+        *self.offset_ms.lock().unwrap() = Some(offset_ms);
+    }
+
+    /// Learn the offset from a server timestamp (e.g. the one returned by `ping`)
+    ///
+    /// # Arguments
+    /// * `server_time` - Time reported by the server
+    /// * `sent_at` - Local time the request was sent
+    /// * `received_at` - Local time the response was received
+    pub fn update_from_server_time(
+        &self,
+        server_time: DateTime<Utc>,
+        sent_at: DateTime<Utc>,
+        received_at: DateTime<Utc>,
+    ) {
+        let offset_ms = estimate_offset_ms(server_time, sent_at, received_at);
+        *self.offset_ms.lock().unwrap() = Some(offset_ms);
+    }
+}
+
+/// Offset between server time and the local midpoint of the round trip
+fn estimate_offset_ms(
+    server_time: DateTime<Utc>,
+    sent_at: DateTime<Utc>,
+    received_at: DateTime<Utc>,
+) -> i64 {
+    let midpoint = sent_at + (received_at - sent_at) / 2;
+    (server_time - midpoint).num_milliseconds()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn date_headers(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(DATE, HeaderValue::from_static(value));
+        headers
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_offset_from_date_header() {
+        let clock = ServerClock::new();
+        assert_eq!(clock.offset(), None);
+
+        let sent = at("2024-01-01T12:00:00Z");
+        let received = at("2024-01-01T12:00:01Z");
+        clock.update_from_headers(
+            &date_headers("Mon, 01 Jan 2024 12:05:00 GMT"),
+            sent,
+            received,
+        );
+        assert_eq!(clock.offset(), Some(chrono::Duration::seconds(300)));
+        assert!(clock.is_skewed());
+
+        // Sub-second differences are within the header's resolution
+        clock.update_from_headers(
+            &date_headers("Mon, 01 Jan 2024 12:00:01 GMT"),
+            sent,
+            received,
+        );
+        assert_eq!(clock.offset(), Some(chrono::Duration::zero()));
+        assert!(!clock.is_skewed());
+    }
+
+    #[test]
+    fn test_offset_from_server_time() {
+        let clock = ServerClock::new().with_skew_threshold(Duration::from_secs(5));
+        clock.update_from_server_time(
+            at("2024-01-01T11:59:50.250Z"),
+            at("2024-01-01T12:00:00Z"),
+            at("2024-01-01T12:00:00.500Z"),
+        );
+
+        assert_eq!(
+            clock.offset(),
+            Some(chrono::Duration::milliseconds(-10_000))
+        );
+        assert!(clock.is_skewed());
+        assert!(clock.now() < Utc::now() - chrono::Duration::seconds(9));
+    }
+
+    #[test]
+    fn test_invalid_date_header_is_ignored() {
+        let clock = ServerClock::new();
+        clock.update_from_headers(&date_headers("yesterday"), Utc::now(), Utc::now());
+        assert_eq!(clock.offset(), None);
+        assert!(!clock.is_skewed());
+    }
+}
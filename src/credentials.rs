@@ -0,0 +1,419 @@
+//! Client credential providers for signed CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Loading client credentials from static values, environment variables or files
+//! - Re-reading rotated credentials periodically and after a rejected signature
+//! - Zeroizing secrets on drop and redacting them from `Debug` output
+
+use crate::builder::{read_env, ENV_CLIENT_ID, ENV_CLIENT_SECRET};
+use crate::{CoinPaymentsError, Result};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
+
+/// How often rotating providers are re-read by default
+pub const DEFAULT_CREDENTIAL_REFRESH: Duration = Duration::from_secs(60);
+
+// === Credentials ===
+
+/// Client ID and secret used to sign requests
+///
+/// The secret is zeroized when dropped, never shown by `Debug` and compared in constant time.
+#[derive(Clone)]
+pub struct Credentials {
+    client_id: String,
+    client_secret: Zeroizing<String>,
+}
+
+impl Credentials {
+    /// Create credentials from a client ID and secret
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: Zeroizing::new(client_secret.into()),
+        }
+    }
+
+    /// Create credentials from a secret that is already zeroized on drop
+    pub(crate) fn from_secret(
+        client_id: impl Into<String>,
+        client_secret: Zeroizing<String>,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret,
+        }
+    }
+
+    /// Client ID
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Client secret
+    pub fn client_secret(&self) -> &str {
+        &self.client_secret
+    }
+}
+
+impl PartialEq for Credentials {
+    fn eq(&self, other: &Self) -> bool {
+        let (secret, other_secret) = (
+            self.client_secret.as_bytes(),
+            other.client_secret.as_bytes(),
+        );
+        let difference = secret
+            .iter()
+            .zip(other_secret)
+            .fold(0u8, |difference, (a, b)| difference | (a ^ b));
+        (self.client_id == other.client_id)
+            & (secret.len() == other_secret.len())
+            & (std::hint::black_box(difference) == 0)
+    }
+}
+
+impl Eq for Credentials {}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"***")
+            .finish()
+    }
+}
+
+/// Source of client credentials
+///
+/// The client loads credentials on first use and again once the refresh interval has
+/// passed, or when the API rejects a signature, so rotated secrets are picked up without
+/// a restart. If a reload fails the previous credentials stay in use.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{AuthType, CoinPaymentsClient, FileCredentials};
+/// # use std::sync::Arc;
+/// # fn example() {
+/// let provider = FileCredentials::new("/var/run/secrets/coinpayments");
+/// let client = CoinPaymentsClient::with_auth(AuthType::Provider(Arc::new(provider)));
+/// # }
+/// ```
+pub trait CredentialProvider: Send + Sync + std::fmt::Debug {
+    /// Load the current credentials
+    fn load(&self) -> Result<Credentials>;
+
+    /// How long loaded credentials are used before loading them again (`None` = forever)
+    fn refresh_interval(&self) -> Option<Duration> {
+        Some(DEFAULT_CREDENTIAL_REFRESH)
+    }
+}
+
+/// Provider returning fixed credentials
+#[derive(Debug, Clone)]
+pub struct StaticCredentials {
+    credentials: Credentials,
+}
+
+impl StaticCredentials {
+    /// Create a provider for a fixed client ID and secret
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            credentials: Credentials::new(client_id, client_secret),
+        }
+    }
+}
+
+impl From<Credentials> for StaticCredentials {
+    fn from(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+}
+
+impl CredentialProvider for StaticCredentials {
+    fn load(&self) -> Result<Credentials> {
+        Ok(self.credentials.clone())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Provider reading credentials from environment variables
+///
+/// Defaults to `COINPAYMENTS_CLIENT_ID` and `COINPAYMENTS_CLIENT_SECRET`.
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    client_id_var: String,
+    client_secret_var: String,
+    refresh_interval: Option<Duration>,
+}
+
+impl Default for EnvCredentials {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvCredentials {
+    /// Create a provider reading the default environment variables
+    pub fn new() -> Self {
+        Self::with_variables(ENV_CLIENT_ID, ENV_CLIENT_SECRET)
+    }
+
+    /// Create a provider reading the given environment variables
+    pub fn with_variables(
+        client_id_var: impl Into<String>,
+        client_secret_var: impl Into<String>,
+    ) -> Self {
+        Self {
+            client_id_var: client_id_var.into(),
+            client_secret_var: client_secret_var.into(),
+            refresh_interval: Some(DEFAULT_CREDENTIAL_REFRESH),
+        }
+    }
+
+    /// Set how often the variables are read again (`None` = only once)
+    pub fn with_refresh_interval(mut self, refresh_interval: Option<Duration>) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn load(&self) -> Result<Credentials> {
+        Ok(Credentials::from_secret(
+            read_env(&self.client_id_var)?.as_str(),
+            read_env(&self.client_secret_var)?,
+        ))
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+}
+
+/// Provider reading credentials from the file system
+///
+/// The path is either a JSON file with `client_id` and `client_secret` fields, or a
+/// directory containing `client_id` and `client_secret` files (as mounted from a
+/// Kubernetes secret).
+#[derive(Debug, Clone)]
+pub struct FileCredentials {
+    path: PathBuf,
+    refresh_interval: Option<Duration>,
+}
+
+#[derive(Deserialize)]
+struct CredentialsFile {
+    client_id: String,
+    client_secret: Zeroizing<String>,
+}
+
+impl FileCredentials {
+    /// Create a provider reading `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            refresh_interval: Some(DEFAULT_CREDENTIAL_REFRESH),
+        }
+    }
+
+    /// Set how often the file is read again (`None` = only once)
+    pub fn with_refresh_interval(mut self, refresh_interval: Option<Duration>) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    fn read(path: &std::path::Path) -> Result<Zeroizing<String>> {
+        std::fs::read_to_string(path)
+            .map(Zeroizing::new)
+            .map_err(|error| {
+                CoinPaymentsError::InvalidParameters(format!(
+                    "Cannot read credentials from {}: {}",
+                    path.display(),
+                    error
+                ))
+            })
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn load(&self) -> Result<Credentials> {
+        if self.path.is_dir() {
+            let client_id = Self::read(&self.path.join("client_id"))?;
+            let client_secret = Self::read(&self.path.join("client_secret"))?;
+            return Ok(Credentials::new(client_id.trim(), client_secret.trim()));
+        }
+
+        let contents = Self::read(&self.path)?;
+        let file: CredentialsFile = serde_json::from_str(&contents).map_err(|error| {
+            CoinPaymentsError::InvalidParameters(format!(
+                "Invalid credentials file {}: {}",
+                self.path.display(),
+                error
+            ))
+        })?;
+        Ok(Credentials::from_secret(file.client_id, file.client_secret))
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+}
+
+// === Credential Cache ===
+
+/// Loaded credentials and when they were loaded
+type Loaded = Option<(Arc<Credentials>, Instant)>;
+
+/// Caches the current credentials of a provider, shared by all clones of a client
+#[derive(Clone)]
+pub(crate) struct CredentialCache {
+    provider: Arc<dyn CredentialProvider>,
+    current: Arc<Mutex<Loaded>>,
+}
+
+impl CredentialCache {
+    pub(crate) fn new(provider: Arc<dyn CredentialProvider>) -> Self {
+        Self {
+            provider,
+            current: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Get the current credentials, loading them if none are cached or they are due
+    pub(crate) fn get(&self) -> Result<Arc<Credentials>> {
+        let mut current = self.current.lock().unwrap();
+        if let Some((credentials, loaded_at)) = current.as_ref() {
+            let due = self
+                .provider
+                .refresh_interval()
+                .is_some_and(|interval| loaded_at.elapsed() >= interval);
+            if !due {
+                return Ok(credentials.clone());
+            }
+        }
+        self.load(&mut current)
+    }
+
+    /// Load the credentials again, e.g. after the API rejected a signature
+    pub(crate) fn reload(&self) -> Result<Arc<Credentials>> {
+        let mut current = self.current.lock().unwrap();
+        self.load(&mut current)
+    }
+
+    fn load(&self, current: &mut Loaded) -> Result<Arc<Credentials>> {
+        match self.provider.load() {
+            Ok(credentials) => {
+                let credentials = Arc::new(credentials);
+                *current = Some((credentials.clone(), Instant::now()));
+                Ok(credentials)
+            }
+            // Keep signing with the previous credentials until the provider recovers
+            Err(error) => match current.as_mut() {
+                Some((credentials, loaded_at)) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(%error, "reloading credentials failed");
+                    #[cfg(not(feature = "tracing"))]
+                    let _ = error;
+                    *loaded_at = Instant::now();
+                    Ok(credentials.clone())
+                }
+                None => Err(error),
+            },
+        }
+    }
+}
+
+impl std::fmt::Debug for CredentialCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialCache")
+            .field("provider", &self.provider)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "coinpayments-credentials-{}-{}",
+            name,
+            crate::generate_random_string(12)
+        ))
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let provider = StaticCredentials::new("client", "very-secret-value");
+        let debug = format!("{:?} {:?}", provider, provider.load().unwrap());
+        assert!(debug.contains("client"));
+        assert!(!debug.contains("very-secret-value"));
+    }
+
+    #[test]
+    fn test_credentials_equality() {
+        let credentials = Credentials::new("id", "secret");
+        assert_eq!(credentials, Credentials::new("id", "secret"));
+        assert_ne!(credentials, Credentials::new("id", "secreT"));
+        assert_ne!(credentials, Credentials::new("id", "secret-2"));
+        assert_ne!(credentials, Credentials::new("id-2", "secret"));
+    }
+
+    #[test]
+    fn test_file_credentials() {
+        let file = temp_path("file");
+        std::fs::write(&file, r#"{"client_id":"id-1","client_secret":"secret-1"}"#).unwrap();
+        let credentials = FileCredentials::new(&file).load().unwrap();
+        assert_eq!(credentials.client_id(), "id-1");
+        assert_eq!(credentials.client_secret(), "secret-1");
+        std::fs::remove_file(&file).unwrap();
+        assert!(FileCredentials::new(&file).load().is_err());
+
+        let directory = temp_path("dir");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("client_id"), "id-2\n").unwrap();
+        std::fs::write(directory.join("client_secret"), "secret-2\n").unwrap();
+        let credentials = FileCredentials::new(&directory).load().unwrap();
+        assert_eq!(credentials, Credentials::new("id-2", "secret-2"));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_env_credentials() {
+        let provider = EnvCredentials::with_variables(
+            "COINPAYMENTS_TEST_PROVIDER_ID",
+            "COINPAYMENTS_TEST_PROVIDER_SECRET",
+        );
+        assert!(provider.load().is_err());
+
+        std::env::set_var("COINPAYMENTS_TEST_PROVIDER_ID", "env-id");
+        std::env::set_var("COINPAYMENTS_TEST_PROVIDER_SECRET", "env-secret");
+        assert_eq!(
+            provider.load().unwrap(),
+            Credentials::new("env-id", "env-secret")
+        );
+    }
+
+    #[test]
+    fn test_cache_reloads_and_keeps_previous_on_failure() {
+        let file = temp_path("rotation");
+        std::fs::write(&file, r#"{"client_id":"id","client_secret":"old"}"#).unwrap();
+        let cache = CredentialCache::new(Arc::new(
+            FileCredentials::new(&file).with_refresh_interval(Some(Duration::from_secs(3600))),
+        ));
+        assert_eq!(cache.get().unwrap().client_secret(), "old");
+
+        std::fs::write(&file, r#"{"client_id":"id","client_secret":"new"}"#).unwrap();
+        assert_eq!(cache.get().unwrap().client_secret(), "old");
+        assert_eq!(cache.reload().unwrap().client_secret(), "new");
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(cache.reload().unwrap().client_secret(), "new");
+    }
+}
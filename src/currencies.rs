@@ -6,13 +6,17 @@
 //! - Getting blockchain information
 //! - Managing currency conversions and limits
 
-use crate::{CoinPaymentsClient, Result};
+use crate::endpoint::{page_query, Endpoint};
+use crate::{CachedEndpoint, CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // === Currency Types ===
 
 /// Currency information from the v2 API
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CurrencyV2 {
     pub id: String,
     pub name: String,
@@ -25,6 +29,9 @@ pub struct CurrencyV2 {
     pub capabilities: Vec<CurrencyCapability>,
     pub created_at: String,
     pub updated_at: String,
+    /// Fields not known to this version of the crate, kept verbatim
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Currency status
@@ -34,6 +41,9 @@ pub enum CurrencyStatus {
     Active,
     Inactive,
     Maintenance,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Currency capabilities
@@ -45,10 +55,14 @@ pub enum CurrencyCapability {
     Conversion,
     InvoicePayment,
     WalletCreation,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Response for getting currencies
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetCurrenciesResponse {
     pub currencies: Vec<CurrencyV2>,
     pub pagination: Option<PaginationInfo>,
@@ -56,6 +70,7 @@ pub struct GetCurrenciesResponse {
 
 /// Pagination information
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -65,6 +80,7 @@ pub struct PaginationInfo {
 
 /// Merchant currency information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct MerchantCurrency {
     pub currency_id: String,
     pub rank: Option<u32>,
@@ -75,12 +91,14 @@ pub struct MerchantCurrency {
 
 /// Response for getting merchant currencies
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetMerchantCurrenciesResponse {
     pub currencies: Vec<MerchantCurrency>,
 }
 
 /// Blockchain node information
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BlockchainNodeInfo {
     pub currency_id: String,
     pub latest_block_number: u64,
@@ -90,6 +108,7 @@ pub struct BlockchainNodeInfo {
 
 /// Required confirmations information
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RequiredConfirmations {
     pub currency_id: String,
     pub confirmations: u32,
@@ -98,12 +117,14 @@ pub struct RequiredConfirmations {
 
 /// Response for required confirmations
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetRequiredConfirmationsResponse {
     pub confirmations: Vec<RequiredConfirmations>,
 }
 
 /// Currency conversion information
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CurrencyConversion {
     pub from_currency_id: String,
     pub to_currency_id: String,
@@ -114,12 +135,14 @@ pub struct CurrencyConversion {
 
 /// Response for currency conversions
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetCurrencyConversionsResponse {
     pub conversions: Vec<CurrencyConversion>,
 }
 
 /// Currency conversion limits
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CurrencyLimits {
     pub from_currency_id: String,
     pub to_currency_id: String,
@@ -129,6 +152,178 @@ pub struct CurrencyLimits {
     pub monthly_limit: Option<String>,
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::get_currencies`]
+#[derive(Debug, Clone, Default)]
+pub struct GetCurrencies {
+    /// Page number
+    pub page: Option<u32>,
+    /// Number of results per page
+    pub per_page: Option<u32>,
+}
+
+impl Endpoint for GetCurrencies {
+    type Request = ();
+    type Response = GetCurrenciesResponse;
+
+    const NAME: &'static str = "get_currencies";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/currencies";
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        page_query(self.page, self.per_page)
+    }
+
+    fn cache(&self) -> Option<CachedEndpoint> {
+        Some(CachedEndpoint::Currencies)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_currency_by_id`]
+#[derive(Debug, Clone)]
+pub struct GetCurrencyById {
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetCurrencyById {
+    /// Get the currency with the given ID
+    pub fn new(currency_id: impl Into<String>) -> Self {
+        Self {
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetCurrencyById {
+    type Request = ();
+    type Response = CurrencyV2;
+
+    const NAME: &'static str = "get_currency_by_id";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/currencies/{currency_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("currency_id", self.currency_id.clone())]
+    }
+
+    fn cache(&self) -> Option<CachedEndpoint> {
+        Some(CachedEndpoint::Currency)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_merchant_currencies`]
+#[derive(Debug, Clone, Default)]
+pub struct GetMerchantCurrencies;
+
+impl Endpoint for GetMerchantCurrencies {
+    type Request = ();
+    type Response = GetMerchantCurrenciesResponse;
+
+    const NAME: &'static str = "get_merchant_currencies";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v1/merchant/currencies";
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_latest_block_number`]
+#[derive(Debug, Clone)]
+pub struct GetLatestBlockNumber {
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetLatestBlockNumber {
+    /// Get the latest block number of the currency's blockchain
+    pub fn new(currency_id: impl Into<String>) -> Self {
+        Self {
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetLatestBlockNumber {
+    type Request = ();
+    type Response = BlockchainNodeInfo;
+
+    const NAME: &'static str = "get_latest_block_number";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/currencies/blockchain-nodes/{currency_id}/latest-block-number";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("currency_id", self.currency_id.clone())]
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_required_confirmations`]
+#[derive(Debug, Clone, Default)]
+pub struct GetRequiredConfirmations;
+
+impl Endpoint for GetRequiredConfirmations {
+    type Request = ();
+    type Response = GetRequiredConfirmationsResponse;
+
+    const NAME: &'static str = "get_required_confirmations";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/currencies/required-confirmations";
+
+    fn cache(&self) -> Option<CachedEndpoint> {
+        Some(CachedEndpoint::RequiredConfirmations)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_currency_conversions`]
+#[derive(Debug, Clone, Default)]
+pub struct GetCurrencyConversions;
+
+impl Endpoint for GetCurrencyConversions {
+    type Request = ();
+    type Response = GetCurrencyConversionsResponse;
+
+    const NAME: &'static str = "get_currency_conversions";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/currencies/conversions";
+
+    fn cache(&self) -> Option<CachedEndpoint> {
+        Some(CachedEndpoint::CurrencyConversions)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_currency_limits`]
+#[derive(Debug, Clone)]
+pub struct GetCurrencyLimits {
+    /// Source currency ID
+    pub from_currency: String,
+    /// Target currency ID
+    pub to_currency: String,
+}
+
+impl GetCurrencyLimits {
+    /// Get the conversion limits between two currencies
+    pub fn new(from_currency: impl Into<String>, to_currency: impl Into<String>) -> Self {
+        Self {
+            from_currency: from_currency.into(),
+            to_currency: to_currency.into(),
+        }
+    }
+}
+
+impl Endpoint for GetCurrencyLimits {
+    type Request = ();
+    type Response = CurrencyLimits;
+
+    const NAME: &'static str = "get_currency_limits";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/currencies/limits/{from_currency}/{to_currency}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("from_currency", self.from_currency.clone()),
+            ("to_currency", self.to_currency.clone()),
+        ]
+    }
+}
+
 impl CoinPaymentsClient {
     /// Get list of supported currencies
     ///
@@ -137,25 +332,21 @@ impl CoinPaymentsClient {
     /// * `per_page` - Number of results per page (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let currencies = client.get_currencies(None, None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_currencies(
         &self,
         page: Option<u32>,
         per_page: Option<u32>,
     ) -> Result<GetCurrenciesResponse> {
-        let mut query_params = Vec::new();
-
-        if let Some(page) = page {
-            query_params.push(("page", page.to_string()));
-        }
-        if let Some(per_page) = per_page {
-            query_params.push(("per_page", per_page.to_string()));
-        }
-
-        self.get_request("v2/currencies", &query_params).await
+        self.execute(GetCurrencies { page, per_page }).await
     }
 
     /// Get currency by ID
@@ -164,24 +355,36 @@ impl CoinPaymentsClient {
     /// * `currency_id` - The currency ID to retrieve
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let currency = client.get_currency_by_id("4").await?; // Bitcoin
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn get_currency_by_id(&self, currency_id: &str) -> Result<CurrencyV2> {
-        let endpoint = format!("v2/currencies/{}", currency_id);
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetCurrencyById::new(currency_id)).await
     }
 
     /// Get merchant's currently accepted currencies
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let merchant_currencies = client.get_merchant_currencies().await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_merchant_currencies(&self) -> Result<GetMerchantCurrenciesResponse> {
-        self.get_request("v1/merchant/currencies", &[]).await
+        self.execute(GetMerchantCurrencies).await
     }
 
     /// Get latest blockchain block number by currency
@@ -190,39 +393,52 @@ impl CoinPaymentsClient {
     /// * `currency_id` - The currency ID to get block number for
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let block_info = client.get_latest_block_number("4").await?; // Bitcoin
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn get_latest_block_number(&self, currency_id: &str) -> Result<BlockchainNodeInfo> {
-        let endpoint = format!(
-            "v2/currencies/blockchain-nodes/{}/latest-block-number",
-            currency_id
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetLatestBlockNumber::new(currency_id)).await
     }
 
     /// Get required confirmations for each currency
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let confirmations = client.get_required_confirmations().await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_required_confirmations(&self) -> Result<GetRequiredConfirmationsResponse> {
-        self.get_request("v2/currencies/required-confirmations", &[])
-            .await
+        self.execute(GetRequiredConfirmations).await
     }
 
     /// Get list of all possible currency conversions
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let conversions = client.get_currency_conversions().await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_currency_conversions(&self) -> Result<GetCurrencyConversionsResponse> {
-        self.get_request("v2/currencies/conversions", &[]).await
+        self.execute(GetCurrencyConversions).await
     }
 
     /// Get conversion limits by currency pair
@@ -232,17 +448,31 @@ impl CoinPaymentsClient {
     /// * `to_currency` - Target currency ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let limits = client.get_currency_limits("4", "3").await?; // BTC to LTC
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                from_currency = %from_currency,
+                to_currency = %to_currency
+            )
+        )
+    )]
     pub async fn get_currency_limits(
         &self,
         from_currency: &str,
         to_currency: &str,
     ) -> Result<CurrencyLimits> {
-        let endpoint = format!("v2/currencies/limits/{}/{}", from_currency, to_currency);
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetCurrencyLimits::new(from_currency, to_currency))
+            .await
     }
 }
 
@@ -344,6 +574,7 @@ mod tests {
             capabilities: vec![CurrencyCapability::Deposit, CurrencyCapability::Withdrawal],
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            extra: HashMap::new(),
         };
 
         assert!(currency_supports_capability(
@@ -375,6 +606,7 @@ mod tests {
                 capabilities: vec![],
                 created_at: "2023-01-01T00:00:00Z".to_string(),
                 updated_at: "2023-01-01T00:00:00Z".to_string(),
+                extra: HashMap::new(),
             },
             CurrencyV2 {
                 id: "2".to_string(),
@@ -388,6 +620,7 @@ mod tests {
                 capabilities: vec![],
                 created_at: "2023-01-01T00:00:00Z".to_string(),
                 updated_at: "2023-01-01T00:00:00Z".to_string(),
+                extra: HashMap::new(),
             },
         ];
 
@@ -0,0 +1,438 @@
+//! Typed descriptions of CoinPayments API operations
+//!
+//! This module provides functionality for:
+//! - The [`Endpoint`] trait: method, path template, query and body of one API operation
+//! - Executing any endpoint, built-in or user-defined, with [`CoinPaymentsClient::execute`]
+//! - Executing batches of endpoints concurrently
+//! - Listing the built-in endpoints, e.g. to generate an API coverage report
+
+use crate::cache::CachedEndpoint;
+use crate::idempotency::{self, IdempotencyKey};
+use crate::{CoinPaymentsClient, Result};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+
+// === Endpoint Trait ===
+
+/// One API operation, described declaratively
+///
+/// Every typed method of the client is backed by an endpoint (e.g. `get_currency_by_id`
+/// executes [`crate::GetCurrencyById`]). Implement it for operations this crate does not
+/// wrap yet; executed endpoints are signed, retried, rate limited and mapped to errors
+/// like the built-in ones.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, Endpoint};
+/// # use reqwest::Method;
+/// # use serde::Deserialize;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+/// #[derive(Deserialize)]
+/// struct Balance { currency_id: String, amount: String }
+///
+/// struct GetWalletBalances { wallet_label: String }
+///
+/// impl Endpoint for GetWalletBalances {
+///     type Request = ();
+///     type Response = Vec<Balance>;
+///
+///     const NAME: &'static str = "get_wallet_balances";
+///     const METHOD: Method = Method::GET;
+///     const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/balances";
+///
+///     fn path_params(&self) -> Vec<(&'static str, String)> {
+///         vec![("wallet_label", self.wallet_label.clone())]
+///     }
+/// }
+///
+/// let balances = client
+///     .execute(GetWalletBalances { wallet_label: "hot".to_string() })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Endpoint {
+    /// Request body (`()` for operations without a body)
+    type Request: Serialize;
+    /// Decoded response
+    type Response: DeserializeOwned;
+
+    /// Operation name, matching the client method for built-in endpoints
+    const NAME: &'static str;
+    /// HTTP method
+    const METHOD: Method;
+    /// Path relative to the base URL, with `{name}` placeholders
+    const PATH: &'static str;
+    /// The operation moves funds and is always sent with an idempotency key (the caller's,
+    /// or a generated one); it is only retried automatically with a caller-supplied key
+    const REQUIRES_IDEMPOTENCY_KEY: bool = false;
+
+    /// Values of the placeholders in [`Endpoint::PATH`]
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Query parameters
+    fn query(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Request body, if any
+    fn body(&self) -> Option<&Self::Request> {
+        None
+    }
+
+    /// Response cache category of GET endpoints serving reference data
+    fn cache(&self) -> Option<CachedEndpoint> {
+        None
+    }
+
+    /// Path with its placeholders filled in, each value percent-encoded as one segment
+    fn path(&self) -> String {
+        self.path_params()
+            .iter()
+            .fold(Self::PATH.to_string(), |path, (name, value)| {
+                path.replace(&format!("{{{}}}", name), &encode_path_segment(value))
+            })
+    }
+}
+
+/// Static description of an endpoint type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointInfo {
+    /// Operation name
+    pub name: &'static str,
+    /// HTTP method
+    pub method: Method,
+    /// Path template
+    pub path: &'static str,
+    /// Always sent with an idempotency key
+    pub requires_idempotency_key: bool,
+}
+
+impl EndpointInfo {
+    /// Describe an endpoint type
+    pub fn of<E: Endpoint>() -> Self {
+        Self {
+            name: E::NAME,
+            method: E::METHOD,
+            path: E::PATH,
+            requires_idempotency_key: E::REQUIRES_IDEMPOTENCY_KEY,
+        }
+    }
+}
+
+/// Endpoints backing the typed client methods, in module order
+pub fn builtin_endpoints() -> Vec<EndpointInfo> {
+    use crate::*;
+
+    vec![
+        EndpointInfo::of::<Ping>(),
+        EndpointInfo::of::<GetClientInfo>(),
+        EndpointInfo::of::<GetCurrencies>(),
+        EndpointInfo::of::<GetCurrencyById>(),
+        EndpointInfo::of::<GetMerchantCurrencies>(),
+        EndpointInfo::of::<GetLatestBlockNumber>(),
+        EndpointInfo::of::<GetRequiredConfirmations>(),
+        EndpointInfo::of::<GetCurrencyConversions>(),
+        EndpointInfo::of::<GetCurrencyLimits>(),
+        EndpointInfo::of::<GetRates>(),
+        EndpointInfo::of::<CalculateBlockchainFee>(),
+        EndpointInfo::of::<GetGasFee>(),
+        EndpointInfo::of::<GetNetworkStatus>(),
+        EndpointInfo::of::<GetWallets>(),
+        EndpointInfo::of::<CreateWallet>(),
+        EndpointInfo::of::<GetWalletCount>(),
+        EndpointInfo::of::<GetWalletAddresses>(),
+        EndpointInfo::of::<GetWalletAddressCount>(),
+        EndpointInfo::of::<GetAddressByLabel>(),
+        EndpointInfo::of::<UpdateWalletWebhook>(),
+        EndpointInfo::of::<UpdateAddressWebhook>(),
+        EndpointInfo::of::<GetTransactionCount>(),
+        EndpointInfo::of::<GetTransactions>(),
+        EndpointInfo::of::<GetTransaction>(),
+        EndpointInfo::of::<CreateSpend>(),
+        EndpointInfo::of::<ConfirmSpend>(),
+        EndpointInfo::of::<GetWalletConsolidation>(),
+        EndpointInfo::of::<ExecuteWalletConsolidation>(),
+        EndpointInfo::of::<ExecuteMultiWalletConsolidation>(),
+        EndpointInfo::of::<PreviewConsolidation>(),
+        EndpointInfo::of::<GetConsolidationTransactions>(),
+        EndpointInfo::of::<CreateInvoice>(),
+        EndpointInfo::of::<CancelInvoice>(),
+        EndpointInfo::of::<GetInvoices>(),
+        EndpointInfo::of::<GetInvoicePaymentInfo>(),
+        EndpointInfo::of::<GetInvoicePaymentStatus>(),
+        EndpointInfo::of::<GetInvoice>(),
+        EndpointInfo::of::<GetInvoicePayouts>(),
+        EndpointInfo::of::<GetInvoiceHistory>(),
+        EndpointInfo::of::<CreateClientWebhook>(),
+        EndpointInfo::of::<UpdateWalletWebhookV2>(),
+        EndpointInfo::of::<UpdateAddressWebhookV2>(),
+        EndpointInfo::of::<UpdateWalletWebhookV3>(),
+        EndpointInfo::of::<UpdateAddressWebhookV3>(),
+    ]
+}
+
+// === Execution ===
+
+impl CoinPaymentsClient {
+    /// Execute an endpoint
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, GetCurrencyById};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+    /// let currency = client
+    ///     .execute(GetCurrencyById::new("4"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute<E: Endpoint>(&self, endpoint: E) -> Result<E::Response> {
+        let path = endpoint.path();
+        let query = endpoint.query();
+        if let Some(cached) = endpoint.cache().filter(|_| E::METHOD == Method::GET) {
+            return self.cached_get_request(cached, &path, &query).await;
+        }
+
+        let body = endpoint.body().map(serde_json::to_string).transpose()?;
+        let idempotency_key = match idempotency::caller_key() {
+            Some(key) if E::METHOD != Method::GET => Some(IdempotencyKey::Caller(key)),
+            None if E::REQUIRES_IDEMPOTENCY_KEY => Some(IdempotencyKey::Generated(
+                idempotency::generate_idempotency_key(),
+            )),
+            _ => None,
+        };
+        self.send_request(E::METHOD, &path, &query, body, idempotency_key)
+            .await
+    }
+
+    /// Execute endpoints concurrently, returning their results in order
+    ///
+    /// Requests still go through the client's rate limiter and circuit breaker.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, GetInvoice};
+    /// # async fn example() {
+    /// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+    /// # let ids = vec!["inv_1".to_string(), "inv_2".to_string()];
+    /// let invoices = client
+    ///     .execute_all(ids.iter().map(|id| GetInvoice::new(id)))
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn execute_all<E: Endpoint>(
+        &self,
+        endpoints: impl IntoIterator<Item = E>,
+    ) -> Vec<Result<E::Response>> {
+        join_all(endpoints.into_iter().map(|endpoint| self.execute(endpoint))).await
+    }
+}
+
+/// Query parameters of paginated endpoints
+pub(crate) fn page_query(page: Option<u32>, per_page: Option<u32>) -> Vec<(&'static str, String)> {
+    let mut query_params = Vec::new();
+
+    if let Some(page) = page {
+        query_params.push(("page", page.to_string()));
+    }
+    if let Some(per_page) = per_page {
+        query_params.push(("perPage", per_page.to_string()));
+    }
+    query_params
+}
+
+/// Wire value of an enum used as a query parameter (e.g. `timedOut` for
+/// `InvoiceStatus::TimedOut`), matching its JSON representation
+pub(crate) fn wire_value<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(value)) => value,
+        Ok(value) => value.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Path parameters of endpoints under `v3/merchant/wallets/{wallet_label}/{currency_id}`
+pub(crate) fn wallet_params(wallet_label: &str, currency_id: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("wallet_label", wallet_label.to_string()),
+        ("currency_id", currency_id.to_string()),
+    ]
+}
+
+/// Percent-encode a path parameter so it stays a single segment
+///
+/// Characters allowed in a segment by RFC 3986 (unreserved, sub-delims, `:` and `@`)
+/// are kept, so ids such as `4:0xdac1...` are sent unchanged; `/`, `?`, `#`, `%` and
+/// anything else is encoded.
+fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Poll futures concurrently on the current task until all are done
+async fn join_all<F: Future>(futures: impl Iterator<Item = F>) -> Vec<F::Output> {
+    let mut futures: Vec<Pin<Box<F>>> = futures.map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    std::future::poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => *output = Some(value),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GetCurrencyById, GetInvoice, MockTransport, RetryPolicy};
+    use std::sync::Arc;
+
+    struct GetBalances {
+        wallet_label: String,
+        currency: Option<String>,
+    }
+
+    impl Endpoint for GetBalances {
+        type Request = ();
+        type Response = serde_json::Value;
+
+        const NAME: &'static str = "get_balances";
+        const METHOD: Method = Method::GET;
+        const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/balances";
+
+        fn path_params(&self) -> Vec<(&'static str, String)> {
+            vec![("wallet_label", self.wallet_label.clone())]
+        }
+
+        fn query(&self) -> Vec<(&'static str, String)> {
+            self.currency
+                .iter()
+                .map(|currency| ("currency", currency.clone()))
+                .collect()
+        }
+    }
+
+    fn client(transport: Arc<MockTransport>) -> CoinPaymentsClient {
+        CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport)
+            .with_base_url("https://api.example.com")
+            .with_retry_policy(RetryPolicy::none())
+    }
+
+    #[tokio::test]
+    async fn test_execute_custom_endpoint() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, r#"{"btc":"1.5"}"#);
+        let balances = client(transport.clone())
+            .execute(GetBalances {
+                wallet_label: "hot".to_string(),
+                currency: Some("BTC".to_string()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(balances["btc"], "1.5");
+        assert_eq!(
+            transport.requests()[0].url,
+            "https://api.example.com/v3/merchant/wallets/hot/balances?currency=BTC"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_all_keeps_order() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, r#"{"a":1}"#);
+        transport.push_json(404, "");
+        let results = client(transport.clone())
+            .execute_all(["a", "b"].map(|label| GetBalances {
+                wallet_label: label.to_string(),
+                currency: None,
+            }))
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap()["a"], 1);
+        assert!(results[1].is_err());
+        assert_eq!(
+            transport.requests()[1].path(),
+            "/v3/merchant/wallets/b/balances"
+        );
+    }
+
+    #[test]
+    fn test_builtin_endpoints() {
+        let endpoints = builtin_endpoints();
+        let info = endpoints
+            .iter()
+            .find(|info| info.name == "get_currency_by_id")
+            .unwrap();
+        assert_eq!(*info, EndpointInfo::of::<GetCurrencyById>());
+        assert_eq!(info.path, "v2/currencies/{currency_id}");
+
+        let mut names: Vec<_> = endpoints.iter().map(|info| info.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), endpoints.len());
+        assert!(endpoints
+            .iter()
+            .any(|info| info.name == "create_spend_request" && info.requires_idempotency_key));
+        assert_eq!(
+            GetInvoice::new("inv_1").path(),
+            "v2/merchant/invoices/inv_1"
+        );
+        assert_eq!(
+            GetInvoice::new("../wallets?all=1#x").path(),
+            "v2/merchant/invoices/..%2Fwallets%3Fall=1%23x"
+        );
+        assert_eq!(
+            GetInvoice::new("50% off é").path(),
+            "v2/merchant/invoices/50%25%20off%20%C3%A9"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Structured errors returned by the CoinPayments API
+//!
+//! This module provides functionality for:
+//! - Parsing error bodies (`{"error": ...}`, `{"code", "message"}`, problem details)
+//! - Keeping the HTTP status, error code, details and request id of a failed call
+//! - Mapping server error codes to specific [`CoinPaymentsError`] variants
+
+use crate::meta;
+use crate::CoinPaymentsError;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+/// Error codes (matched case-insensitively, ignoring `_` and `-`) meaning the balance is too low
+pub const INSUFFICIENT_FUNDS_CODES: &[&str] = &[
+    "insufficientfunds",
+    "insufficientbalance",
+    "notenoughfunds",
+    "notenoughbalance",
+];
+
+// === Error Types ===
+
+/// Details of an error response returned by the API
+#[derive(Debug, Clone)]
+pub struct ApiErrorDetails {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// CoinPayments error code, if the API returned one
+    pub code: Option<String>,
+    /// Human readable error message
+    pub message: String,
+    /// Additional error details (e.g. per-field validation errors)
+    pub details: Option<Value>,
+    /// Server request/correlation id, if the API returned one
+    pub request_id: Option<String>,
+}
+
+impl std::fmt::Display for ApiErrorDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP {}", self.status.as_u16())?;
+        if let Some(code) = &self.code {
+            write!(f, " [{}]", code)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request id {})", request_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl ApiErrorDetails {
+    /// Parse an error response body
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, body: &str) -> Self {
+        let json = serde_json::from_str::<Value>(body).ok();
+        let error = json.as_ref().map(|json| json.get("error").unwrap_or(json));
+
+        let field = |name: &str| {
+            error
+                .and_then(|error| error.get(name))
+                .or_else(|| json.as_ref().and_then(|json| json.get(name)))
+                .filter(|value| !value.is_null())
+        };
+        let text = |name: &str| field(name).and_then(Value::as_str).map(str::to_string);
+
+        let code = text("code").or_else(|| text("errorCode")).or_else(|| {
+            field("code")
+                .filter(|code| code.is_number())
+                .map(ToString::to_string)
+        });
+        let message = error
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| text("message"))
+            .or_else(|| text("detail"))
+            .or_else(|| text("title"))
+            .unwrap_or_else(|| {
+                if body.trim().is_empty() {
+                    status
+                        .canonical_reason()
+                        .unwrap_or("Unknown error")
+                        .to_string()
+                } else {
+                    body.to_string()
+                }
+            });
+        let details = field("details").or_else(|| field("errors")).cloned();
+        let request_id = meta::extract_request_id(headers)
+            .or_else(|| text("requestId"))
+            .or_else(|| text("traceId"));
+
+        Self {
+            status,
+            code,
+            message,
+            details,
+            request_id,
+        }
+    }
+
+    /// Check whether the error code reports insufficient funds
+    ///
+    /// The message is only matched when the response carries no error code.
+    pub fn is_insufficient_funds(&self) -> bool {
+        let normalize = |value: &str| {
+            value
+                .chars()
+                .filter(|c| *c != '_' && *c != '-' && !c.is_whitespace())
+                .collect::<String>()
+                .to_lowercase()
+        };
+
+        let text = normalize(self.code.as_deref().unwrap_or(&self.message));
+        INSUFFICIENT_FUNDS_CODES
+            .iter()
+            .any(|known| text.contains(known))
+    }
+
+    /// Check whether the error reports invalid request data
+    pub fn is_validation_error(&self) -> bool {
+        let code = self.code.as_deref().unwrap_or_default().to_lowercase();
+        self.status == StatusCode::UNPROCESSABLE_ENTITY
+            || (self.status == StatusCode::BAD_REQUEST
+                && (code.contains("validation")
+                    || code.contains("invalid")
+                    || self.details.as_ref().is_some_and(Value::is_object)))
+    }
+
+    /// Convert into the most specific error variant
+    ///
+    /// Only `5xx` responses become [`CoinPaymentsError::Server`]; other rejections
+    /// without a more specific variant become [`CoinPaymentsError::Client`].
+    pub fn into_error(self) -> CoinPaymentsError {
+        match self.status.as_u16() {
+            401 => CoinPaymentsError::Authentication(Box::new(self)),
+            404 => CoinPaymentsError::NotFound(Box::new(self)),
+            429 => CoinPaymentsError::RateLimit(Box::new(self)),
+            _ if self.status.is_client_error() && self.is_insufficient_funds() => {
+                CoinPaymentsError::InsufficientFunds(Box::new(self))
+            }
+            _ if self.is_validation_error() => CoinPaymentsError::Validation(Box::new(self)),
+            _ if self.status.is_server_error() => CoinPaymentsError::Server(Box::new(self)),
+            _ => CoinPaymentsError::Client(Box::new(self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn parse(status: u16, body: &str) -> ApiErrorDetails {
+        ApiErrorDetails::from_response(
+            StatusCode::from_u16(status).unwrap(),
+            &HeaderMap::new(),
+            body,
+        )
+    }
+
+    #[test]
+    fn test_parse_error_formats() {
+        let simple = parse(400, r#"{"error":"Invalid amount"}"#);
+        assert_eq!(simple.message, "Invalid amount");
+        assert_eq!(simple.code, None);
+
+        let wrapped = parse(
+            400,
+            r#"{"error":{"code":"INVALID_CURRENCY","message":"Unknown currency","details":{"field":"currency"}}}"#,
+        );
+        assert_eq!(wrapped.code.as_deref(), Some("INVALID_CURRENCY"));
+        assert_eq!(wrapped.message, "Unknown currency");
+        assert_eq!(wrapped.details.unwrap()["field"], "currency");
+
+        let problem = parse(
+            400,
+            r#"{"title":"One or more validation errors occurred.","status":400,"errors":{"amount":["Required"]},"traceId":"00-abc-01"}"#,
+        );
+        assert_eq!(problem.message, "One or more validation errors occurred.");
+        assert_eq!(problem.request_id.as_deref(), Some("00-abc-01"));
+        assert!(problem.details.is_some());
+
+        let raw = parse(502, "Bad gateway from upstream");
+        assert_eq!(raw.message, "Bad gateway from upstream");
+        assert_eq!(parse(503, "").message, "Service Unavailable");
+    }
+
+    #[test]
+    fn test_request_id_prefers_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Request-Id", HeaderValue::from_static("req-1"));
+        let details = ApiErrorDetails::from_response(
+            StatusCode::BAD_REQUEST,
+            &headers,
+            r#"{"message":"Bad","traceId":"trace-1"}"#,
+        );
+        assert_eq!(details.request_id.as_deref(), Some("req-1"));
+    }
+
+    #[test]
+    fn test_classification() {
+        assert!(matches!(
+            parse(
+                400,
+                r#"{"code":"INSUFFICIENT_FUNDS","message":"Balance too low"}"#
+            )
+            .into_error(),
+            CoinPaymentsError::InsufficientFunds(_)
+        ));
+        assert!(matches!(
+            parse(400, r#"{"message":"Insufficient balance for spend"}"#).into_error(),
+            CoinPaymentsError::InsufficientFunds(_)
+        ));
+        assert!(matches!(
+            parse(
+                400,
+                r#"{"title":"Invalid","errors":{"amount":["Required"]}}"#
+            )
+            .into_error(),
+            CoinPaymentsError::Validation(_)
+        ));
+        assert!(matches!(
+            parse(422, r#"{"message":"Unprocessable"}"#).into_error(),
+            CoinPaymentsError::Validation(_)
+        ));
+        assert!(matches!(
+            parse(409, r#"{"message":"Conflict"}"#).into_error(),
+            CoinPaymentsError::Client(_)
+        ));
+        assert!(matches!(
+            parse(400, r#"{"error":"Invalid amount"}"#).into_error(),
+            CoinPaymentsError::Client(_)
+        ));
+        assert!(matches!(
+            parse(500, r#"{"message":"Internal error"}"#).into_error(),
+            CoinPaymentsError::Server(_)
+        ));
+    }
+
+    #[test]
+    fn test_insufficient_funds_needs_client_error_and_code() {
+        assert!(matches!(
+            parse(503, r#"{"message":"Insufficient balance in hot wallet"}"#).into_error(),
+            CoinPaymentsError::Server(_)
+        ));
+        assert!(matches!(
+            parse(
+                400,
+                r#"{"code":"AMOUNT_TOO_SMALL","message":"Insufficient balance after fees"}"#
+            )
+            .into_error(),
+            CoinPaymentsError::Client(_)
+        ));
+    }
+
+    #[test]
+    fn test_retryable_matches_retry_policy() {
+        for status in [400, 404, 408, 409, 422, 429, 500, 501, 502, 503, 504] {
+            let error = parse(status, r#"{"message":"Failed"}"#).into_error();
+            assert_eq!(
+                error.is_retryable(),
+                crate::retry::is_retryable_status(StatusCode::from_u16(status).unwrap()),
+                "status {}",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn test_common_errors_keep_details() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Request-Id", HeaderValue::from_static("req-5"));
+        for (status, kind) in [
+            (401, "authentication"),
+            (404, "not_found"),
+            (429, "rate_limit"),
+        ] {
+            let error = ApiErrorDetails::from_response(
+                StatusCode::from_u16(status).unwrap(),
+                &headers,
+                r#"{"code":"E1","message":"Denied","details":{"scope":"wallets"}}"#,
+            )
+            .into_error();
+            assert_eq!(error.kind(), kind);
+            assert_eq!(error.status().map(|status| status.as_u16()), Some(status));
+            assert_eq!(error.code(), Some("E1"));
+            assert_eq!(error.request_id(), Some("req-5"));
+            assert_eq!(
+                error.details().unwrap().details.as_ref().unwrap()["scope"],
+                "wallets"
+            );
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let mut details = parse(400, r#"{"code":"E42","message":"Nope"}"#);
+        details.request_id = Some("req-9".to_string());
+        assert_eq!(
+            details.to_string(),
+            "HTTP 400 [E42]: Nope (request id req-9)"
+        );
+    }
+}
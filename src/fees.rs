@@ -5,13 +5,16 @@
 //! - Getting fee estimates for different transaction types
 //! - Fee optimization and recommendations
 
+use crate::endpoint::Endpoint;
 use crate::{CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
 // === Fee Types ===
 
 /// Blockchain fee information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct BlockchainFee {
     pub currency_id: String,
     pub fee_type: FeeType,
@@ -34,6 +37,9 @@ pub enum FeeType {
     Dynamic,
     /// Gas-based fee (for EVM chains)
     Gas,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Fee priority levels
@@ -48,10 +54,14 @@ pub enum FeePriority {
     Fast,
     /// Highest fee, fastest confirmation
     Priority,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Fee calculation request
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct FeeCalculationRequest {
     pub currency_id: String,
     pub transaction_type: TransactionType,
@@ -74,10 +84,14 @@ pub enum TransactionType {
     Consolidation,
     /// Smart contract interaction
     Contract,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Fee calculation response
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FeeCalculationResponse {
     pub currency_id: String,
     pub transaction_type: TransactionType,
@@ -88,6 +102,7 @@ pub struct FeeCalculationResponse {
 
 /// Network status information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct NetworkStatus {
     pub currency_id: String,
     pub congestion_level: CongestionLevel,
@@ -104,10 +119,14 @@ pub enum CongestionLevel {
     Medium,
     High,
     Critical,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Gas fee information for EVM chains
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct GasFee {
     pub currency_id: String,
     pub gas_price: String,
@@ -159,6 +178,112 @@ impl FeeCalculationRequest {
     }
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::calculate_blockchain_fee`]
+#[derive(Debug, Clone)]
+pub struct CalculateBlockchainFee {
+    /// Currency ID
+    pub currency_id: String,
+    /// Fee calculation parameters
+    pub request: FeeCalculationRequest,
+}
+
+impl CalculateBlockchainFee {
+    /// Calculate the blockchain fee of a transaction
+    pub fn new(currency_id: impl Into<String>, request: FeeCalculationRequest) -> Self {
+        Self {
+            currency_id: currency_id.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for CalculateBlockchainFee {
+    type Request = FeeCalculationRequest;
+    type Response = FeeCalculationResponse;
+
+    const NAME: &'static str = "calculate_blockchain_fee";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v2/fees/blockchain/{currency_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("currency_id", self.currency_id.clone())]
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_gas_fee`]
+#[derive(Debug, Clone)]
+pub struct GetGasFee {
+    /// EVM currency ID
+    pub currency_id: String,
+    /// Gas limit override
+    pub gas_limit: Option<u64>,
+}
+
+impl GetGasFee {
+    /// Get gas fee information for an EVM currency
+    pub fn new(currency_id: impl Into<String>) -> Self {
+        Self {
+            currency_id: currency_id.into(),
+            gas_limit: None,
+        }
+    }
+}
+
+impl Endpoint for GetGasFee {
+    type Request = ();
+    type Response = GasFee;
+
+    const NAME: &'static str = "get_gas_fee";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/fees/gas/{currency_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("currency_id", self.currency_id.clone())]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        self.gas_limit
+            .iter()
+            .map(|limit| ("gasLimit", limit.to_string()))
+            .collect()
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_network_status`]
+#[derive(Debug, Clone)]
+pub struct GetNetworkStatus {
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetNetworkStatus {
+    /// Get the network status of a currency
+    pub fn new(currency_id: impl Into<String>) -> Self {
+        Self {
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetNetworkStatus {
+    type Request = ();
+    type Response = NetworkStatus;
+
+    const NAME: &'static str = "get_network_status";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/fees/network-status/{currency_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("currency_id", self.currency_id.clone())]
+    }
+}
+
 impl CoinPaymentsClient {
     /// Calculate blockchain fee for a transaction
     ///
@@ -167,7 +292,10 @@ impl CoinPaymentsClient {
     /// * `request` - Optional fee calculation parameters
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, FeeCalculationRequest, FeePriority};
+    /// # use coinpayments::fees::TransactionType;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     ///
     /// // Calculate basic send fee for Bitcoin
@@ -178,20 +306,22 @@ impl CoinPaymentsClient {
     ///     .with_amount("0.001")
     ///     .with_priority(FeePriority::Fast);
     /// let fee = client.calculate_blockchain_fee("4", Some(request)).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn calculate_blockchain_fee(
         &self,
         currency_id: &str,
         request: Option<FeeCalculationRequest>,
     ) -> Result<FeeCalculationResponse> {
-        let endpoint = format!("v2/fees/blockchain/{}", currency_id);
-
-        if let Some(request) = request {
-            self.post_request(&endpoint, &request).await
-        } else {
-            let default_request = FeeCalculationRequest::new(currency_id, TransactionType::Send);
-            self.post_request(&endpoint, &default_request).await
-        }
+        let request = request
+            .unwrap_or_else(|| FeeCalculationRequest::new(currency_id, TransactionType::Send));
+        self.execute(CalculateBlockchainFee::new(currency_id, request))
+            .await
     }
 
     /// Get gas fee information for EVM-based currencies
@@ -201,19 +331,24 @@ impl CoinPaymentsClient {
     /// * `gas_limit` - Optional gas limit override
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let gas_fee = client.get_gas_fee("61", Some(21000)).await?; // Ethereum
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn get_gas_fee(&self, currency_id: &str, gas_limit: Option<u64>) -> Result<GasFee> {
-        let endpoint = format!("v2/fees/gas/{}", currency_id);
-        let mut query_params = Vec::new();
-
-        if let Some(limit) = gas_limit {
-            query_params.push(("gas_limit", limit.to_string()));
-        }
-
-        self.get_request(&endpoint, &query_params).await
+        self.execute(GetGasFee {
+            currency_id: currency_id.to_string(),
+            gas_limit,
+        })
+        .await
     }
 
     /// Get network status for fee estimation
@@ -222,13 +357,20 @@ impl CoinPaymentsClient {
     /// * `currency_id` - The currency ID to get network status for
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let status = client.get_network_status("4").await?; // Bitcoin
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn get_network_status(&self, currency_id: &str) -> Result<NetworkStatus> {
-        let endpoint = format!("v2/fees/network-status/{}", currency_id);
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetNetworkStatus::new(currency_id)).await
     }
 
     /// Get recommended fee for optimal confirmation time
@@ -238,11 +380,19 @@ impl CoinPaymentsClient {
     /// * `target_confirmation_time` - Desired confirmation time in minutes
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// // Get fee for 30-minute confirmation
     /// let fee = client.get_recommended_fee("4", 30).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn get_recommended_fee(
         &self,
         currency_id: &str,
@@ -282,6 +432,7 @@ pub fn compare_fee_priority(a: &BlockchainFee, b: &BlockchainFee) -> std::cmp::O
         Standard => 2,
         Fast => 3,
         Priority => 4,
+        Unknown(_) => 0,
     };
 
     priority_order(&a.priority_level).cmp(&priority_order(&b.priority_level))
@@ -315,7 +466,7 @@ pub fn estimate_multi_recipient_fee(base_fee: &BlockchainFee, recipient_count: u
     match base_fee.fee_type {
         FeeType::Fixed => base_fee.amount_f * recipient_count as f64,
         FeeType::Percentage => base_fee.amount_f, // Percentage doesn't scale with recipients
-        FeeType::Dynamic | FeeType::Gas => {
+        FeeType::Dynamic | FeeType::Gas | FeeType::Unknown(_) => {
             // Approximate scaling for dynamic/gas fees
             base_fee.amount_f * (1.0 + (recipient_count - 1) as f64 * 0.3)
         }
@@ -427,7 +578,7 @@ mod tests {
         };
 
         let multi_fee = estimate_multi_recipient_fee(&fixed_fee, 3);
-        assert_eq!(multi_fee, 0.0003); // 3 recipients × 0.0001
+        assert!((multi_fee - 0.0003).abs() < 1e-12); // 3 recipients × 0.0001
     }
 
     #[test]
@@ -0,0 +1,276 @@
+//! Startup and readiness checks for CoinPayments clients
+//!
+//! This module provides functionality for:
+//! - Confirming connectivity and authentication
+//! - Measuring latency and server clock skew
+//! - Listing permissions missing for the operations an application uses
+
+use crate::{
+    CoinPaymentsClient, CoinPaymentsError, RequestOptions, RequestOptionsExt, RetryPolicy,
+};
+use std::time::{Duration, Instant};
+
+/// Timeout of each request made by a self-check
+pub const SELF_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// === Permission Types ===
+
+/// API permission of a client, as listed in `ClientInfo::permissions`
+///
+/// Granted permissions are matched case-insensitively, ignoring separators, so
+/// `wallet:spend`, `wallet_spend` and `WalletSpend` are the same permission.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Create invoices
+    InvoiceCreate,
+    /// Read and list invoices
+    InvoiceRead,
+    /// Create wallets and addresses
+    WalletCreate,
+    /// Read wallets, balances and transactions
+    WalletRead,
+    /// Spend from wallets (withdrawals and consolidations)
+    WalletSpend,
+    /// Create, update and delete webhooks
+    WebhookManage,
+    /// Any other permission, by name
+    Other(String),
+}
+
+impl Permission {
+    /// Canonical name of the permission
+    pub fn as_str(&self) -> &str {
+        match self {
+            Permission::InvoiceCreate => "invoice:create",
+            Permission::InvoiceRead => "invoice:read",
+            Permission::WalletCreate => "wallet:create",
+            Permission::WalletRead => "wallet:read",
+            Permission::WalletSpend => "wallet:spend",
+            Permission::WebhookManage => "webhook:manage",
+            Permission::Other(name) => name,
+        }
+    }
+
+    /// Check whether a granted permission name is this permission
+    pub fn matches(&self, granted: &str) -> bool {
+        normalize(self.as_str()) == normalize(granted)
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// === Self-Check Report ===
+
+/// Result of [`CoinPaymentsClient::self_check`]
+#[derive(Debug, Clone, Default)]
+pub struct SelfCheckReport {
+    /// The API answered the ping
+    pub reachable: bool,
+    /// The credentials were accepted
+    pub authenticated: bool,
+    /// Round-trip time of the ping
+    pub latency: Option<Duration>,
+    /// Estimated server clock minus local clock
+    pub clock_offset: Option<chrono::Duration>,
+    /// The clock offset exceeds the skew threshold of the client
+    pub clock_skewed: bool,
+    /// Permissions granted to the client
+    pub granted_permissions: Vec<String>,
+    /// Required permissions that are not granted
+    pub missing_permissions: Vec<Permission>,
+    /// Failures encountered during the check, by step
+    pub errors: Vec<String>,
+}
+
+impl SelfCheckReport {
+    /// Check whether the client is ready to serve the required operations
+    pub fn is_ready(&self) -> bool {
+        self.reachable
+            && self.authenticated
+            && !self.clock_skewed
+            && self.missing_permissions.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+impl CoinPaymentsClient {
+    /// Check connectivity, credentials, clock skew and permissions
+    ///
+    /// Never fails: problems are reported in the returned report, which makes it
+    /// suitable for readiness probes. Requests are not retried and time out after
+    /// [`SELF_CHECK_TIMEOUT`], so an unreachable API is reported quickly.
+    ///
+    /// # Arguments
+    /// * `required` - Permissions needed by the operations the application uses
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, Permission};
+    /// # async fn example() {
+    /// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+    /// let report = client
+    ///     .self_check(&[Permission::InvoiceCreate, Permission::WalletSpend])
+    ///     .await;
+    /// if !report.is_ready() {
+    ///     eprintln!("CoinPayments not ready: {:?}", report);
+    /// }
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn self_check(&self, required: &[Permission]) -> SelfCheckReport {
+        let mut report = SelfCheckReport::default();
+        let options = RequestOptions::new()
+            .with_timeout(SELF_CHECK_TIMEOUT)
+            .with_retry_policy(RetryPolicy::none());
+
+        let started = Instant::now();
+        match self.ping().with_options(options.clone()).await {
+            Ok(_) => {
+                report.reachable = true;
+                report.latency = Some(started.elapsed());
+            }
+            Err(error) => {
+                report.reachable = answered(&error);
+                report.errors.push(format!("ping: {}", error));
+            }
+        }
+
+        if report.reachable {
+            match self.get_client_info().with_options(options).await {
+                Ok(info) => {
+                    report.authenticated = true;
+                    report.granted_permissions = info.permissions;
+                }
+                Err(error) => report.errors.push(format!("client info: {}", error)),
+            }
+        }
+
+        report.missing_permissions = required
+            .iter()
+            .filter(|permission| {
+                !report
+                    .granted_permissions
+                    .iter()
+                    .any(|granted| permission.matches(granted))
+            })
+            .cloned()
+            .collect();
+        report.clock_offset = self.clock_offset();
+        report.clock_skewed = self.server_clock.is_skewed();
+        report
+    }
+}
+
+/// Check whether an error means the API answered (as opposed to being unreachable)
+fn answered(error: &CoinPaymentsError) -> bool {
+    !matches!(
+        error,
+        CoinPaymentsError::Http(_)
+            | CoinPaymentsError::Network(_)
+            | CoinPaymentsError::Timeout
+            | CoinPaymentsError::Cancelled
+            | CoinPaymentsError::CircuitOpen { .. }
+    ) && !error
+        .status()
+        .is_some_and(|status| status.is_server_error())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockTransport, RetryPolicy};
+    use std::sync::Arc;
+
+    const CLIENT_INFO: &str = r#"{
+        "clientId": "test_client",
+        "name": "Shop",
+        "permissions": ["Invoice_Create", "wallet:read"],
+        "rateLimits": {"requestsPerMinute": 0, "requestsPerHour": 0, "requestsPerDay": 0},
+        "createdAt": "2023-01-01T00:00:00Z",
+        "updatedAt": "2023-01-01T00:00:00Z"
+    }"#;
+
+    fn client(transport: Arc<MockTransport>) -> CoinPaymentsClient {
+        CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy::none())
+    }
+
+    #[test]
+    fn test_permission_matching() {
+        assert!(Permission::WalletSpend.matches("wallet_spend"));
+        assert!(Permission::WalletSpend.matches("WalletSpend"));
+        assert!(!Permission::WalletSpend.matches("wallet:read"));
+        assert!(Permission::Other("rates:read".to_string()).matches("Rates.Read"));
+    }
+
+    #[tokio::test]
+    async fn test_self_check_reports_missing_permissions() {
+        let transport = Arc::new(MockTransport::new());
+        let now = chrono::Utc::now().to_rfc3339();
+        transport.push_json(
+            200,
+            format!(
+                r#"{{"message":"pong","timestamp":"{}","version":"1"}}"#,
+                now
+            ),
+        );
+        transport.push_json(200, CLIENT_INFO);
+
+        let report = client(transport)
+            .self_check(&[Permission::InvoiceCreate, Permission::WalletSpend])
+            .await;
+
+        assert!(report.reachable && report.authenticated);
+        assert!(report.latency.is_some());
+        assert!(!report.clock_skewed);
+        assert_eq!(report.missing_permissions, vec![Permission::WalletSpend]);
+        assert!(!report.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_self_check_unreachable_and_unauthenticated() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error("connection refused");
+        let report = client(transport).self_check(&[]).await;
+        assert!(!report.reachable);
+        assert!(!report.authenticated);
+        assert_eq!(report.errors.len(), 1);
+
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(401, "");
+        let report = client(transport).self_check(&[]).await;
+        assert!(report.reachable);
+        assert!(!report.authenticated);
+        assert!(!report.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_self_check_does_not_retry() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error("connection refused");
+        transport.push_json(503, "{}");
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(
+                RetryPolicy::new().with_backoff(Duration::from_secs(30), Duration::from_secs(60)),
+            );
+
+        let started = Instant::now();
+        let report = client.self_check(&[]).await;
+        assert!(!report.reachable);
+        assert_eq!(transport.requests().len(), 1);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,324 @@
+//! Idempotency keys for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Generating an idempotency key for every money-moving call
+//! - Letting callers supply their own key via [`IdempotencyKeyExt::with_idempotency_key`]
+//! - Replaying the original response for duplicate keys within a time window
+
+use crate::options::{self, RequestOptionsExt};
+use crate::transport::HttpResponse;
+use crate::{CoinPaymentsError, Result};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Extension for endpoint futures that sets the idempotency key of the call
+///
+/// Shorthand for [`crate::RequestOptions::with_idempotency_key`] that keeps any other
+/// options of an enclosing [`RequestOptionsExt::with_options`] call. When both set a
+/// key, the one applied closest to the endpoint future wins.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, CreateSpendRequest};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # struct Payout { id: u64 }
+/// # let payout = Payout { id: 42 };
+/// # let request = CreateSpendRequest::new("0.5").to_address("bc1q...");
+/// use coinpayments::IdempotencyKeyExt;
+///
+/// let client = CoinPaymentsClient::new("client_id", "client_secret");
+/// let spend = client
+///     .create_spend_request("wallet", "4", request)
+///     .with_idempotency_key(format!("payout-{}", payout.id))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait IdempotencyKeyExt<T>: Future<Output = Result<T>> + Sized {
+    /// Send the call with the given idempotency key instead of a generated one
+    fn with_idempotency_key(self, key: impl Into<String>) -> impl Future<Output = Result<T>>;
+}
+
+impl<F, T> IdempotencyKeyExt<T> for F
+where
+    F: Future<Output = Result<T>>,
+{
+    async fn with_idempotency_key(self, key: impl Into<String>) -> Result<T> {
+        let options = options::current()
+            .unwrap_or_default()
+            .with_idempotency_key(key);
+        self.with_options(options).await
+    }
+}
+
+/// Idempotency key supplied by the caller for the current call, if any
+pub(crate) fn caller_key() -> Option<String> {
+    options::current().and_then(|options| options.idempotency_key)
+}
+
+/// Idempotency key sent with a request
+#[derive(Debug, Clone)]
+pub(crate) enum IdempotencyKey {
+    /// Supplied by the caller, who can reuse it across attempts and processes
+    Caller(String),
+    /// Generated by the client; only a local [`IdempotencyCache`] deduplicates it
+    Generated(String),
+}
+
+impl IdempotencyKey {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            IdempotencyKey::Caller(key) | IdempotencyKey::Generated(key) => key,
+        }
+    }
+
+    pub(crate) fn into_string(self) -> String {
+        match self {
+            IdempotencyKey::Caller(key) | IdempotencyKey::Generated(key) => key,
+        }
+    }
+}
+
+/// Generate a random (UUID v4 formatted) idempotency key
+pub fn generate_idempotency_key() -> String {
+    let mut bytes: [u8; 16] = rand::thread_rng().gen();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+// === Idempotency Cache ===
+
+/// Client-side cache replaying successful responses for repeated idempotency keys
+///
+/// A call repeated with the same key within the window returns the original
+/// response without contacting the API. A call whose key is still in flight waits
+/// for the first call and replays its response. Reusing a key for a different
+/// request is rejected. Only successful responses are cached. Clones share the same
+/// entries.
+#[derive(Debug, Clone)]
+pub struct IdempotencyCache {
+    window: Duration,
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+    in_flight: InFlight,
+}
+
+type InFlight = Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// Exclusive use of an idempotency key, released (and pruned) on drop
+pub(crate) struct Reservation {
+    key: String,
+    in_flight: InFlight,
+    guard: Option<tokio::sync::OwnedMutexGuard<()>>,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        let mut in_flight = self.in_flight.lock().unwrap();
+        // Only the map holds the lock once no call waits for it
+        if in_flight
+            .get(&self.key)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    fingerprint: String,
+    stored_at: Instant,
+    response: HttpResponse,
+}
+
+impl IdempotencyCache {
+    /// Create a cache that remembers responses for `window`
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Number of (unexpired) cached responses
+    pub fn len(&self) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        self.evict_expired(&mut entries, Instant::now());
+        entries.len()
+    }
+
+    /// Check whether the cache holds no responses
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait until no other call uses `key`, then hold it until the reservation is dropped
+    pub(crate) async fn reserve(&self, key: &str) -> Reservation {
+        let lock = self
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        Reservation {
+            key: key.to_string(),
+            in_flight: self.in_flight.clone(),
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+
+    /// Look up the response for a key, failing if the key was used for another request
+    pub(crate) fn lookup(&self, key: &str, fingerprint: &str) -> Result<Option<HttpResponse>> {
+        let mut entries = self.entries.lock().unwrap();
+        self.evict_expired(&mut entries, Instant::now());
+
+        match entries.get(key) {
+            Some(entry) if entry.fingerprint != fingerprint => {
+                Err(CoinPaymentsError::InvalidParameters(format!(
+                    "Idempotency key {} was already used for a different request",
+                    key
+                )))
+            }
+            Some(entry) => Ok(Some(entry.response.clone())),
+            None => Ok(None),
+        }
+    }
+
+    /// Remember a successful response
+    pub(crate) fn store(&self, key: &str, fingerprint: &str, response: &HttpResponse) {
+        if !response.status.is_success() {
+            return;
+        }
+
+        self.entries.lock().unwrap().insert(
+            key.to_string(),
+            CacheEntry {
+                fingerprint: fingerprint.to_string(),
+                stored_at: Instant::now(),
+                response: response.clone(),
+            },
+        );
+    }
+
+    fn evict_expired(&self, entries: &mut HashMap<String, CacheEntry>, now: Instant) {
+        entries.retain(|_, entry| now.duration_since(entry.stored_at) < self.window);
+    }
+}
+
+/// Fingerprint of a request, used to detect keys reused for different requests
+pub(crate) fn fingerprint(method: &str, endpoint: &str, body: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(endpoint.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_generated_keys_are_unique_uuids() {
+        let first = generate_idempotency_key();
+        let second = generate_idempotency_key();
+
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 36);
+        assert_eq!(first.matches('-').count(), 4);
+        assert_eq!(&first[14..15], "4");
+    }
+
+    #[tokio::test]
+    async fn test_caller_key_is_scoped_to_call() {
+        assert_eq!(caller_key(), None);
+
+        let key = async { Ok(caller_key()) }
+            .with_idempotency_key("order-42")
+            .await
+            .unwrap();
+        assert_eq!(key.as_deref(), Some("order-42"));
+        assert_eq!(caller_key(), None);
+    }
+
+    #[tokio::test]
+    async fn test_key_keeps_enclosing_options() {
+        let outer = crate::RequestOptions::new()
+            .with_idempotency_key("outer")
+            .with_timeout(Duration::from_secs(3));
+        let seen = async { Ok(options::current()) }
+            .with_idempotency_key("inner")
+            .with_options(outer)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(seen.idempotency_key.as_deref(), Some("inner"));
+        assert_eq!(seen.timeout, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_cache_replays_and_detects_conflicts() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let request = fingerprint("POST", "v2/merchant/invoices", r#"{"amount":"10"}"#);
+        let other = fingerprint("POST", "v2/merchant/invoices", r#"{"amount":"20"}"#);
+
+        assert!(cache.lookup("key-1", &request).unwrap().is_none());
+
+        cache.store("key-1", &request, &HttpResponse::new(StatusCode::OK, "{}"));
+        cache.store(
+            "key-2",
+            &request,
+            &HttpResponse::new(StatusCode::BAD_REQUEST, "{}"),
+        );
+        assert_eq!(cache.len(), 1);
+
+        let replayed = cache.lookup("key-1", &request).unwrap().unwrap();
+        assert_eq!(replayed.status, StatusCode::OK);
+        assert!(cache.lookup("key-1", &other).is_err());
+    }
+
+    #[test]
+    fn test_cache_entries_expire() {
+        let cache = IdempotencyCache::new(Duration::ZERO);
+        let request = fingerprint("POST", "endpoint", "");
+        cache.store("key", &request, &HttpResponse::new(StatusCode::OK, "{}"));
+
+        assert!(cache.lookup("key", &request).unwrap().is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reservations_are_exclusive_and_pruned() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let first = cache.reserve("key").await;
+
+        let waiting = cache.clone();
+        let second = tokio::spawn(async move { waiting.reserve("key").await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!second.is_finished());
+
+        drop(first);
+        drop(second.await.unwrap());
+        assert!(cache.in_flight.lock().unwrap().is_empty());
+    }
+}
@@ -6,13 +6,17 @@
 //! - Managing invoice status and history
 //! - Invoice payouts and completion tracking
 
+use crate::endpoint::{page_query, wire_value, Endpoint};
 use crate::{CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // === Invoice Types ===
 
 /// Invoice information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Invoice {
     pub id: String,
     pub merchant_id: String,
@@ -33,11 +37,14 @@ pub struct Invoice {
     pub completed_at: Option<String>,
     pub invoice_url: String,
     pub payment_urls: Option<Vec<PaymentUrl>>,
+    /// Fields not known to this version of the crate, kept verbatim
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Invoice statuses
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum InvoiceStatus {
     /// Invoice saved as draft
     Draft,
@@ -57,10 +64,14 @@ pub enum InvoiceStatus {
     TimedOut,
     /// Invoice deleted
     Deleted,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Payment URL for different currencies
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct PaymentUrl {
     pub currency_id: String,
     pub currency_symbol: String,
@@ -69,6 +80,7 @@ pub struct PaymentUrl {
 
 /// Request to create an invoice
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateInvoiceRequest {
     pub amount: String,
     pub currency: String,
@@ -88,6 +100,7 @@ pub struct CreateInvoiceRequest {
 
 /// Response for creating an invoice
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateInvoiceResponse {
     pub invoice: Invoice,
     pub payment_info: Option<Vec<PaymentInfo>>,
@@ -95,6 +108,7 @@ pub struct CreateInvoiceResponse {
 
 /// Payment information for an invoice
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct PaymentInfo {
     pub currency_id: String,
     pub currency_symbol: String,
@@ -109,6 +123,7 @@ pub struct PaymentInfo {
 
 /// Payment status information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct PaymentStatus {
     pub currency_id: String,
     pub amount_paid: String,
@@ -133,10 +148,14 @@ pub enum PaymentStatusType {
     Completed,
     Failed,
     Expired,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Response for getting invoices
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetInvoicesResponse {
     pub invoices: Vec<Invoice>,
     pub pagination: Option<InvoicePaginationInfo>,
@@ -144,6 +163,7 @@ pub struct GetInvoicesResponse {
 
 /// Pagination information for invoices
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InvoicePaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -153,6 +173,7 @@ pub struct InvoicePaginationInfo {
 
 /// Invoice payout information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct InvoicePayout {
     pub id: String,
     pub invoice_id: String,
@@ -177,16 +198,21 @@ pub enum PayoutStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Response for getting invoice payouts
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetInvoicePayoutsResponse {
     pub payouts: Vec<InvoicePayout>,
 }
 
 /// Invoice history entry
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct InvoiceHistoryEntry {
     pub id: String,
     pub invoice_id: String,
@@ -211,10 +237,14 @@ pub enum InvoiceEventType {
     Expired,
     PayoutCreated,
     PayoutCompleted,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Response for getting invoice history
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetInvoiceHistoryResponse {
     pub history: Vec<InvoiceHistoryEntry>,
 }
@@ -318,6 +348,264 @@ impl CreateInvoiceRequest {
     }
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::create_invoice`]
+#[derive(Debug, Clone)]
+pub struct CreateInvoice {
+    /// Invoice to create
+    pub request: CreateInvoiceRequest,
+}
+
+impl Endpoint for CreateInvoice {
+    type Request = CreateInvoiceRequest;
+    type Response = CreateInvoiceResponse;
+
+    const NAME: &'static str = "create_invoice";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v2/merchant/invoices";
+    const REQUIRES_IDEMPOTENCY_KEY: bool = true;
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::cancel_invoice`]
+#[derive(Debug, Clone)]
+pub struct CancelInvoice {
+    /// Invoice ID
+    pub invoice_id: String,
+}
+
+impl CancelInvoice {
+    /// Cancel an invoice
+    pub fn new(invoice_id: impl Into<String>) -> Self {
+        Self {
+            invoice_id: invoice_id.into(),
+        }
+    }
+}
+
+impl Endpoint for CancelInvoice {
+    type Request = ();
+    type Response = ();
+
+    const NAME: &'static str = "cancel_invoice";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v1/merchant/invoices/{invoice_id}/cancel";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("invoice_id", self.invoice_id.clone())]
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        // The API expects a JSON `null` body
+        Some(&())
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_invoices`]
+#[derive(Debug, Clone, Default)]
+pub struct GetInvoices {
+    /// Page number
+    pub page: Option<u32>,
+    /// Number of results per page
+    pub per_page: Option<u32>,
+    /// Only invoices with this status
+    pub status: Option<InvoiceStatus>,
+    /// Only invoices in this currency
+    pub currency: Option<String>,
+}
+
+impl Endpoint for GetInvoices {
+    type Request = ();
+    type Response = GetInvoicesResponse;
+
+    const NAME: &'static str = "get_invoices";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/merchant/invoices";
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query_params = page_query(self.page, self.per_page);
+
+        if let Some(status) = &self.status {
+            query_params.push(("status", wire_value(status)));
+        }
+        if let Some(currency) = &self.currency {
+            query_params.push(("currency", currency.clone()));
+        }
+        query_params
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_invoice_payment_info`]
+#[derive(Debug, Clone)]
+pub struct GetInvoicePaymentInfo {
+    /// Invoice ID
+    pub invoice_id: String,
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetInvoicePaymentInfo {
+    /// Get the payment information of an invoice in a currency
+    pub fn new(invoice_id: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            invoice_id: invoice_id.into(),
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetInvoicePaymentInfo {
+    type Request = ();
+    type Response = PaymentInfo;
+
+    const NAME: &'static str = "get_invoice_payment_info";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v1/invoices/{invoice_id}/payment-currencies/{currency_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("invoice_id", self.invoice_id.clone()),
+            ("currency_id", self.currency_id.clone()),
+        ]
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_invoice_payment_status`]
+#[derive(Debug, Clone)]
+pub struct GetInvoicePaymentStatus {
+    /// Invoice ID
+    pub invoice_id: String,
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetInvoicePaymentStatus {
+    /// Get the payment status of an invoice in a currency
+    pub fn new(invoice_id: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            invoice_id: invoice_id.into(),
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetInvoicePaymentStatus {
+    type Request = ();
+    type Response = PaymentStatus;
+
+    const NAME: &'static str = "get_invoice_payment_status";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v1/invoices/{invoice_id}/payment-currencies/{currency_id}/status";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("invoice_id", self.invoice_id.clone()),
+            ("currency_id", self.currency_id.clone()),
+        ]
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_invoice`]
+#[derive(Debug, Clone)]
+pub struct GetInvoice {
+    /// Invoice ID
+    pub invoice_id: String,
+    /// Include payment information
+    pub include_payments: Option<bool>,
+}
+
+impl GetInvoice {
+    /// Get an invoice by ID
+    pub fn new(invoice_id: impl Into<String>) -> Self {
+        Self {
+            invoice_id: invoice_id.into(),
+            include_payments: None,
+        }
+    }
+}
+
+impl Endpoint for GetInvoice {
+    type Request = ();
+    type Response = Invoice;
+
+    const NAME: &'static str = "get_invoice";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/merchant/invoices/{invoice_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("invoice_id", self.invoice_id.clone())]
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        self.include_payments
+            .map(|include_payments| ("includePayments", include_payments.to_string()))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_invoice_payouts`]
+#[derive(Debug, Clone)]
+pub struct GetInvoicePayouts {
+    /// Invoice ID
+    pub invoice_id: String,
+}
+
+impl GetInvoicePayouts {
+    /// Get the payouts of an invoice
+    pub fn new(invoice_id: impl Into<String>) -> Self {
+        Self {
+            invoice_id: invoice_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetInvoicePayouts {
+    type Request = ();
+    type Response = GetInvoicePayoutsResponse;
+
+    const NAME: &'static str = "get_invoice_payouts";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/merchant/invoices/{invoice_id}/payouts";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("invoice_id", self.invoice_id.clone())]
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_invoice_history`]
+#[derive(Debug, Clone)]
+pub struct GetInvoiceHistory {
+    /// Invoice ID
+    pub invoice_id: String,
+}
+
+impl GetInvoiceHistory {
+    /// Get the history of an invoice
+    pub fn new(invoice_id: impl Into<String>) -> Self {
+        Self {
+            invoice_id: invoice_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetInvoiceHistory {
+    type Request = ();
+    type Response = GetInvoiceHistoryResponse;
+
+    const NAME: &'static str = "get_invoice_history";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/merchant/invoices/{invoice_id}/history";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("invoice_id", self.invoice_id.clone())]
+    }
+}
+
 impl CoinPaymentsClient {
     /// Create a new invoice
     ///
@@ -325,19 +613,34 @@ impl CoinPaymentsClient {
     /// * `request` - Invoice creation request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, CreateInvoiceRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = CreateInvoiceRequest::new("10.00", "USD", "Payment for services")
     ///     .with_buyer("customer@example.com", Some("John Doe".to_string()))
     ///     .with_payment_currencies(vec!["4".to_string(), "61".to_string()]) // BTC, ETH
     ///     .expires_in_minutes(60);
     /// let invoice = client.create_invoice(request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                amount = %request.amount,
+                currency = %request.currency,
+                buyer_email = ?request.buyer_email.as_deref().map(crate::telemetry::redact_email)
+            )
+        )
+    )]
     pub async fn create_invoice(
         &self,
         request: CreateInvoiceRequest,
     ) -> Result<CreateInvoiceResponse> {
-        self.post_request("v2/merchant/invoices", &request).await
+        self.execute(CreateInvoice { request }).await
     }
 
     /// Cancel an invoice
@@ -346,13 +649,20 @@ impl CoinPaymentsClient {
     /// * `invoice_id` - ID of the invoice to cancel
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// client.cancel_invoice("inv_123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(invoice_id = %invoice_id))
+    )]
     pub async fn cancel_invoice(&self, invoice_id: &str) -> Result<()> {
-        let endpoint = format!("v1/merchant/invoices/{}/cancel", invoice_id);
-        self.post_request(&endpoint, &serde_json::Value::Null).await
+        self.execute(CancelInvoice::new(invoice_id)).await
     }
 
     /// Get list of invoices
@@ -364,10 +674,18 @@ impl CoinPaymentsClient {
     /// * `currency` - Filter by currency (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, InvoiceStatus};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let invoices = client.get_invoices(None, None, Some(InvoiceStatus::Unpaid), None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency = ?currency))
+    )]
     pub async fn get_invoices(
         &self,
         page: Option<u32>,
@@ -375,34 +693,13 @@ impl CoinPaymentsClient {
         status: Option<InvoiceStatus>,
         currency: Option<&str>,
     ) -> Result<GetInvoicesResponse> {
-        let mut query_params = Vec::new();
-
-        if let Some(page) = page {
-            query_params.push(("page", page.to_string()));
-        }
-        if let Some(per_page) = per_page {
-            query_params.push(("per_page", per_page.to_string()));
-        }
-        if let Some(status) = status {
-            let status_str = match status {
-                InvoiceStatus::Draft => "draft",
-                InvoiceStatus::Scheduled => "scheduled",
-                InvoiceStatus::Unpaid => "unpaid",
-                InvoiceStatus::Pending => "pending",
-                InvoiceStatus::Paid => "paid",
-                InvoiceStatus::Completed => "completed",
-                InvoiceStatus::Cancelled => "cancelled",
-                InvoiceStatus::TimedOut => "timedOut",
-                InvoiceStatus::Deleted => "deleted",
-            };
-            query_params.push(("status", status_str.to_string()));
-        }
-        if let Some(currency) = currency {
-            query_params.push(("currency", currency.to_string()));
-        }
-
-        self.get_request("v2/merchant/invoices", &query_params)
-            .await
+        self.execute(GetInvoices {
+            page,
+            per_page,
+            status,
+            currency: currency.map(str::to_string),
+        })
+        .await
     }
 
     /// Get invoice payment information for a specific currency
@@ -412,20 +709,25 @@ impl CoinPaymentsClient {
     /// * `currency_id` - Currency ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let payment_info = client.get_invoice_payment_info("inv_123", "4").await?; // Bitcoin
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(invoice_id = %invoice_id, currency_id = %currency_id))
+    )]
     pub async fn get_invoice_payment_info(
         &self,
         invoice_id: &str,
         currency_id: &str,
     ) -> Result<PaymentInfo> {
-        let endpoint = format!(
-            "v1/invoices/{}/payment-currencies/{}",
-            invoice_id, currency_id
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetInvoicePaymentInfo::new(invoice_id, currency_id))
+            .await
     }
 
     /// Get invoice payment status for a specific currency
@@ -435,20 +737,25 @@ impl CoinPaymentsClient {
     /// * `currency_id` - Currency ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let status = client.get_invoice_payment_status("inv_123", "4").await?; // Bitcoin
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(invoice_id = %invoice_id, currency_id = %currency_id))
+    )]
     pub async fn get_invoice_payment_status(
         &self,
         invoice_id: &str,
         currency_id: &str,
     ) -> Result<PaymentStatus> {
-        let endpoint = format!(
-            "v1/invoices/{}/payment-currencies/{}/status",
-            invoice_id, currency_id
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetInvoicePaymentStatus::new(invoice_id, currency_id))
+            .await
     }
 
     /// Get invoice by ID
@@ -458,23 +765,28 @@ impl CoinPaymentsClient {
     /// * `include_payments` - Include payment information (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let invoice = client.get_invoice("inv_123", Some(true)).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(invoice_id = %invoice_id))
+    )]
     pub async fn get_invoice(
         &self,
         invoice_id: &str,
         include_payments: Option<bool>,
     ) -> Result<Invoice> {
-        let mut query_params = Vec::new();
-
-        if let Some(include_payments) = include_payments {
-            query_params.push(("include_payments", include_payments.to_string()));
-        }
-
-        let endpoint = format!("v2/merchant/invoices/{}", invoice_id);
-        self.get_request(&endpoint, &query_params).await
+        self.execute(GetInvoice {
+            include_payments,
+            ..GetInvoice::new(invoice_id)
+        })
+        .await
     }
 
     /// Get invoice payouts
@@ -483,13 +795,20 @@ impl CoinPaymentsClient {
     /// * `invoice_id` - Invoice ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let payouts = client.get_invoice_payouts("inv_123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(invoice_id = %invoice_id))
+    )]
     pub async fn get_invoice_payouts(&self, invoice_id: &str) -> Result<GetInvoicePayoutsResponse> {
-        let endpoint = format!("v2/merchant/invoices/{}/payouts", invoice_id);
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetInvoicePayouts::new(invoice_id)).await
     }
 
     /// Get invoice history
@@ -498,13 +817,20 @@ impl CoinPaymentsClient {
     /// * `invoice_id` - Invoice ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let history = client.get_invoice_history("inv_123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(invoice_id = %invoice_id))
+    )]
     pub async fn get_invoice_history(&self, invoice_id: &str) -> Result<GetInvoiceHistoryResponse> {
-        let endpoint = format!("v2/merchant/invoices/{}/history", invoice_id);
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetInvoiceHistory::new(invoice_id)).await
     }
 }
 
@@ -572,7 +898,7 @@ pub fn find_invoice_by_number<'a>(
         invoice
             .invoice_number
             .as_ref()
-            .map_or(false, |num| num == invoice_number)
+            .is_some_and(|num| num == invoice_number)
     })
 }
 
@@ -601,6 +927,7 @@ mod tests {
             completed_at: None,
             invoice_url: "https://checkout.coinpayments.net/inv_123".to_string(),
             payment_urls: None,
+            extra: HashMap::new(),
         }
     }
 
@@ -684,4 +1011,27 @@ mod tests {
         assert_eq!(request.expires_in, Some(1800)); // 30 minutes
         assert_eq!(request.auto_accept_payments, Some(false));
     }
+
+    #[test]
+    fn test_invoice_with_unknown_status_and_fields() {
+        let mut json =
+            serde_json::to_value(create_test_invoice("inv_1", InvoiceStatus::Paid, 10.0)).unwrap();
+        json["status"] = "refunded".into();
+        json["refundId"] = "ref_1".into();
+
+        let invoice: Invoice = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            invoice.status,
+            InvoiceStatus::Unknown("refunded".to_string())
+        );
+        assert_eq!(invoice.extra["refundId"], "ref_1");
+        assert!(!invoice.extra.contains_key("status"));
+
+        let query = GetInvoices {
+            status: Some(invoice.status),
+            ..Default::default()
+        }
+        .query();
+        assert_eq!(query, vec![("status", "refunded".to_string())]);
+    }
 }
@@ -15,7 +15,7 @@
 //!
 //! # Quick Start
 //!
-//! ```rust
+//! ```rust,no_run
 //! use coinpayments::{CoinPaymentsClient, CreateInvoiceRequest};
 //!
 //! #[tokio::main]
@@ -33,17 +33,51 @@
 //! }
 //! ```
 
-use reqwest::Client;
+use auth::TokenCache;
+use credentials::CredentialCache;
+use idempotency::IdempotencyKey;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Client, Method, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 // Re-export all module types for easier access
+pub use auth::{AccessToken, StaticTokenSource, TokenSource};
+pub use builder::CoinPaymentsClientBuilder;
+pub use cache::{
+    CacheEntry, CachePolicy, CacheStore, CachedEndpoint, FileStore, MemoryStore, ResponseCache,
+};
+pub use cassette::{
+    Cassette, CassetteMode, CassetteTransport, Interaction, RecordedRequest, RecordedResponse,
+};
+pub use circuit::{CircuitBreaker, CircuitState};
+pub use clock::ServerClock;
+pub use credentials::{
+    CredentialProvider, Credentials, EnvCredentials, FileCredentials, StaticCredentials,
+};
 pub use currencies::*;
+pub use endpoint::{builtin_endpoints, Endpoint, EndpointInfo};
+pub use errors::ApiErrorDetails;
 pub use fees::*;
+pub use health::{Permission, SelfCheckReport};
+pub use idempotency::{IdempotencyCache, IdempotencyKeyExt};
 pub use invoices::*;
+pub use meta::{ResponseMeta, ResponseMetaExt};
 pub use rates::*;
+pub use raw::{RawClient, RawRequest};
+pub use registry::{ClientRegistry, TenantCredentials, TenantHealth};
 pub use transactions::*;
+// `fees` also defines a `TransactionType`; the wallet transaction one wins at the root
+pub use middleware::Middleware;
+pub use options::{CancellationToken, RequestOptions, RequestOptionsExt};
+pub use rate_limit::RateLimiter;
+pub use retry::{RetryBudget, RetryPolicy};
+pub use signing::{LegacySigner, Signer, SigningInput, V2Signer};
+pub use transactions::TransactionType;
+pub use transport::{HttpRequest, HttpResponse, MockTransport, ReqwestTransport, Transport};
 pub use utils::{
     build_query_string, calculate_pagination, convert_reqwest_error, create_auth_headers,
     create_http_client, extract_api_error_message, extract_rate_limit_info, format_amount,
@@ -57,11 +91,36 @@ pub use wallets::*;
 pub use webhooks::*;
 
 // Module declarations
+pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builder;
+pub mod cache;
+pub mod cassette;
+pub mod circuit;
+pub mod clock;
+pub mod credentials;
 pub mod currencies;
+pub mod endpoint;
+pub mod errors;
 pub mod fees;
+pub mod health;
+pub mod idempotency;
 pub mod invoices;
+pub mod meta;
+pub mod middleware;
+pub mod options;
+pub mod rate_limit;
 pub mod rates;
+pub mod raw;
+pub mod registry;
+pub mod retry;
+pub mod signing;
+pub mod telemetry;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod transactions;
+pub mod transport;
 pub mod utils;
 pub mod wallets;
 pub mod webhooks;
@@ -72,10 +131,19 @@ const API_BASE_URL: &str = "https://a-api.coinpayments.net/api";
 /// CoinPayments API Client
 #[derive(Debug, Clone)]
 pub struct CoinPaymentsClient {
-    client: Client,
-    client_id: String,
-    client_secret: String,
+    transport: Arc<dyn Transport>,
+    credentials: CredentialCache,
     base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    server_clock: ServerClock,
+    signer: Arc<dyn Signer>,
+    // Set for OAuth clients, which send bearer tokens instead of HMAC signatures
+    token_cache: Option<TokenCache>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    idempotency_cache: Option<IdempotencyCache>,
+    response_cache: Option<ResponseCache>,
+    circuit_breaker: Option<CircuitBreaker>,
 }
 
 /// API Error types
@@ -90,8 +158,8 @@ pub enum CoinPaymentsError {
     #[error("API error: {message}")]
     Api { message: String },
 
-    #[error("Authentication failed")]
-    Authentication,
+    #[error("Authentication failed: {0}")]
+    Authentication(Box<ApiErrorDetails>),
 
     #[error("Invalid parameters: {0}")]
     InvalidParameters(String),
@@ -99,14 +167,118 @@ pub enum CoinPaymentsError {
     #[error("Network error: {0}")]
     Network(String),
 
-    #[error("Rate limit exceeded")]
-    RateLimit,
+    #[error("Rate limit exceeded: {0}")]
+    RateLimit(Box<ApiErrorDetails>),
+
+    #[error("Resource not found: {0}")]
+    NotFound(Box<ApiErrorDetails>),
+
+    #[error("Request timed out")]
+    Timeout,
+
+    #[error("Request cancelled")]
+    Cancelled,
+
+    #[error("Circuit breaker open: API calls are failing, retry in {retry_after:?}")]
+    CircuitOpen { retry_after: std::time::Duration },
+
+    #[error("Authentication failed: local clock differs from server time by {offset_ms} ms")]
+    ClockSkew { offset_ms: i64 },
 
-    #[error("Resource not found")]
-    NotFound,
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(Box<ApiErrorDetails>),
 
-    #[error("Insufficient funds")]
-    InsufficientFunds,
+    #[error("Validation failed: {0}")]
+    Validation(Box<ApiErrorDetails>),
+
+    #[error("Request rejected: {0}")]
+    Client(Box<ApiErrorDetails>),
+
+    #[error("API error: {0}")]
+    Server(Box<ApiErrorDetails>),
+}
+
+impl CoinPaymentsError {
+    /// Short, stable name of the error variant (safe to log; never contains response data)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CoinPaymentsError::Http(_) => "http",
+            CoinPaymentsError::Json(_) => "json",
+            CoinPaymentsError::Api { .. } => "api",
+            CoinPaymentsError::Authentication(_) => "authentication",
+            CoinPaymentsError::InvalidParameters(_) => "invalid_parameters",
+            CoinPaymentsError::Network(_) => "network",
+            CoinPaymentsError::RateLimit(_) => "rate_limit",
+            CoinPaymentsError::NotFound(_) => "not_found",
+            CoinPaymentsError::Timeout => "timeout",
+            CoinPaymentsError::Cancelled => "cancelled",
+            CoinPaymentsError::CircuitOpen { .. } => "circuit_open",
+            CoinPaymentsError::ClockSkew { .. } => "clock_skew",
+            CoinPaymentsError::InsufficientFunds(_) => "insufficient_funds",
+            CoinPaymentsError::Validation(_) => "validation",
+            CoinPaymentsError::Client(_) => "client",
+            CoinPaymentsError::Server(_) => "server",
+        }
+    }
+
+    /// Details of the API error response, if the error came from one
+    pub fn details(&self) -> Option<&ApiErrorDetails> {
+        match self {
+            CoinPaymentsError::Authentication(details)
+            | CoinPaymentsError::RateLimit(details)
+            | CoinPaymentsError::NotFound(details)
+            | CoinPaymentsError::InsufficientFunds(details)
+            | CoinPaymentsError::Validation(details)
+            | CoinPaymentsError::Client(details)
+            | CoinPaymentsError::Server(details) => Some(details),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code of the failed response, if any
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            CoinPaymentsError::Http(error) => error.status(),
+            CoinPaymentsError::ClockSkew { .. } => Some(StatusCode::UNAUTHORIZED),
+            _ => self.details().map(|details| details.status),
+        }
+    }
+
+    /// CoinPayments error code, if the API returned one
+    pub fn code(&self) -> Option<&str> {
+        self.details().and_then(|details| details.code.as_deref())
+    }
+
+    /// Server request/correlation id, if the API returned one
+    pub fn request_id(&self) -> Option<&str> {
+        self.details()
+            .and_then(|details| details.request_id.as_deref())
+    }
+
+    /// Check whether repeating the call later may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CoinPaymentsError::Http(_) | CoinPaymentsError::Network(_) => {
+                retry::is_retryable_transport_error(self)
+            }
+            CoinPaymentsError::RateLimit(_)
+            | CoinPaymentsError::Timeout
+            | CoinPaymentsError::CircuitOpen { .. } => true,
+            // Matches the statuses the built-in retry policy repeats (e.g. 408)
+            CoinPaymentsError::Client(details) | CoinPaymentsError::Server(details) => {
+                retry::is_retryable_status(details.status)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check whether the request itself was at fault (HTTP 4xx or rejected locally)
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            CoinPaymentsError::InvalidParameters(_) => true,
+            _ => self.status().is_some_and(|status| status.is_client_error()),
+        }
+    }
 }
 
 /// Result type alias for CoinPayments operations
@@ -114,6 +286,7 @@ pub type Result<T> = std::result::Result<T, CoinPaymentsError>;
 
 /// Generic API response wrapper
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ApiResponse<T> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
@@ -125,6 +298,7 @@ pub struct ApiResponse<T> {
 
 /// API error information
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ApiError {
     pub code: String,
     pub message: String,
@@ -134,6 +308,7 @@ pub struct ApiError {
 
 /// Pagination metadata
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PaginationMetadata {
     pub page: u32,
     pub per_page: u32,
@@ -142,15 +317,30 @@ pub struct PaginationMetadata {
 }
 
 /// Authentication type
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum AuthType {
-    /// OAuth 2.0 authentication
-    OAuth(String),
+    /// OAuth 2.0 authentication with a fixed bearer token
+    OAuth(Zeroizing<String>),
     /// Client ID and Secret authentication
-    ClientCredentials {
-        client_id: String,
-        client_secret: String,
-    },
+    ClientCredentials(Credentials),
+    /// OAuth 2.0 authentication with tokens fetched (and refreshed) from a token source
+    TokenSource(Arc<dyn TokenSource>),
+    /// Client ID and Secret authentication with credentials loaded (and rotated) by a provider
+    Provider(Arc<dyn CredentialProvider>),
+}
+
+impl std::fmt::Debug for AuthType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthType::OAuth(_) => f.debug_tuple("OAuth").field(&"***").finish(),
+            AuthType::ClientCredentials(credentials) => f
+                .debug_tuple("ClientCredentials")
+                .field(credentials)
+                .finish(),
+            AuthType::TokenSource(_) => f.debug_tuple("TokenSource").finish_non_exhaustive(),
+            AuthType::Provider(provider) => f.debug_tuple("Provider").field(provider).finish(),
+        }
+    }
 }
 
 impl CoinPaymentsClient {
@@ -161,18 +351,104 @@ impl CoinPaymentsClient {
     /// * `client_secret` - Your CoinPayments client secret
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # fn example() {
     /// let client = CoinPaymentsClient::new("your_client_id", "your_client_secret");
+    /// # }
     /// ```
     pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
         Self {
-            client: Client::new(),
-            client_id: client_id.into(),
-            client_secret: client_secret.into(),
+            transport: Arc::new(ReqwestTransport::new(
+                utils::create_http_client().unwrap_or_else(|_| Client::new()),
+            )),
+            credentials: CredentialCache::new(Arc::new(StaticCredentials::new(
+                client_id,
+                client_secret,
+            ))),
             base_url: API_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: RateLimiter::new(),
+            server_clock: ServerClock::new(),
+            signer: Arc::new(V2Signer),
+            token_cache: None,
+            middleware: Vec::new(),
+            idempotency_cache: None,
+            response_cache: None,
+            circuit_breaker: None,
         }
     }
 
+    /// Create a new client using the given authentication type
+    ///
+    /// # Arguments
+    /// * `auth` - Client credentials, a fixed OAuth token or an OAuth token source
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{AuthType, CoinPaymentsClient};
+    /// # fn example() {
+    /// let client = CoinPaymentsClient::with_auth(AuthType::OAuth("access_token".to_string().into()));
+    /// # }
+    /// ```
+    pub fn with_auth(auth: AuthType) -> Self {
+        Self::with_client_and_auth(
+            utils::create_http_client().unwrap_or_else(|_| Client::new()),
+            auth,
+        )
+    }
+
+    /// Create a new client with custom HTTP client and authentication type
+    ///
+    /// # Arguments
+    /// * `client` - Custom reqwest client
+    /// * `auth` - Client credentials, a fixed OAuth token or an OAuth token source
+    pub fn with_client_and_auth(client: Client, auth: AuthType) -> Self {
+        let source: Arc<dyn TokenSource> = match auth {
+            AuthType::ClientCredentials(credentials) => {
+                let provider = Arc::new(StaticCredentials::from(credentials));
+                return Self::with_client_and_auth(client, AuthType::Provider(provider));
+            }
+            AuthType::Provider(provider) => {
+                return Self {
+                    credentials: CredentialCache::new(provider),
+                    ..Self::with_client(client, String::new(), String::new())
+                }
+            }
+            AuthType::OAuth(token) => Arc::new(StaticTokenSource::from_secret(token)),
+            AuthType::TokenSource(source) => source,
+        };
+
+        Self {
+            token_cache: Some(TokenCache::new(source)),
+            ..Self::with_client(client, String::new(), String::new())
+        }
+    }
+
+    /// Create a builder for configuring timeouts, proxy, user agent, TLS and headers
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # use std::time::Duration;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = CoinPaymentsClient::builder()
+    ///     .with_credentials("your_client_id", "your_client_secret")
+    ///     .with_timeout(Duration::from_secs(15))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> CoinPaymentsClientBuilder {
+        CoinPaymentsClientBuilder::new()
+    }
+
+    /// Create a client from the `COINPAYMENTS_CLIENT_ID`, `COINPAYMENTS_CLIENT_SECRET`
+    /// and optional `COINPAYMENTS_BASE_URL` environment variables
+    pub fn from_env() -> Result<Self> {
+        CoinPaymentsClientBuilder::from_env()?.build()
+    }
+
     /// Create a new client with custom HTTP client
     ///
     /// # Arguments
@@ -185,64 +461,254 @@ impl CoinPaymentsClient {
         client_secret: impl Into<String>,
     ) -> Self {
         Self {
-            client,
-            client_id: client_id.into(),
-            client_secret: client_secret.into(),
+            transport: Arc::new(ReqwestTransport::new(client)),
+            credentials: CredentialCache::new(Arc::new(StaticCredentials::new(
+                client_id,
+                client_secret,
+            ))),
             base_url: API_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: RateLimiter::new(),
+            server_clock: ServerClock::new(),
+            signer: Arc::new(V2Signer),
+            token_cache: None,
+            middleware: Vec::new(),
+            idempotency_cache: None,
+            response_cache: None,
+            circuit_breaker: None,
         }
     }
 
     /// Set a custom base URL (useful for testing)
     ///
+    /// A trailing `/` is removed, as request paths are appended after one.
+    ///
     /// # Arguments
     /// * `base_url` - Custom API base URL
     pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
-        self.base_url = base_url.into();
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Send requests through a custom transport instead of reqwest
+    ///
+    /// # Arguments
+    /// * `transport` - Transport used for all requests (e.g. a `MockTransport` in tests)
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, MockTransport};
+    /// # use std::sync::Arc;
+    /// # fn example() {
+    /// let transport = Arc::new(MockTransport::new());
+    /// let client = CoinPaymentsClient::new("client_id", "client_secret")
+    ///     .with_transport(transport.clone());
+    /// # }
+    /// ```
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Add a middleware to the end of the chain
+    ///
+    /// # Arguments
+    /// * `middleware` - Hooks run before and after every HTTP attempt
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Replay original responses for repeated idempotency keys
+    ///
+    /// # Arguments
+    /// * `cache` - Cache of successful responses (clones share entries)
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, IdempotencyCache};
+    /// # use std::time::Duration;
+    /// # fn example() {
+    /// let client = CoinPaymentsClient::new("client_id", "client_secret")
+    ///     .with_idempotency_cache(IdempotencyCache::new(Duration::from_secs(24 * 60 * 60)));
+    /// # }
+    /// ```
+    pub fn with_idempotency_cache(mut self, cache: IdempotencyCache) -> Self {
+        self.idempotency_cache = Some(cache);
+        self
+    }
+
+    /// Cache reference data responses (currencies, confirmations, conversions, rates)
+    ///
+    /// # Arguments
+    /// * `cache` - Response cache with per-endpoint policies (clones share entries)
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, FileStore, ResponseCache};
+    /// # use std::sync::Arc;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = ResponseCache::with_store(Arc::new(FileStore::new("/var/cache/coinpayments")?));
+    /// let client = CoinPaymentsClient::new("client_id", "client_secret")
+    ///     .with_response_cache(cache);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Fail fast while the API is failing instead of waiting for every request to time out
+    ///
+    /// # Arguments
+    /// * `circuit_breaker` - Circuit breaker (clones share state)
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Get the state of the circuit breaker, if one is configured
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(CircuitBreaker::state)
+    }
+
+    /// Set the retry policy used for failed requests
+    ///
+    /// # Arguments
+    /// * `retry_policy` - Retry policy (use `RetryPolicy::none()` to disable retries)
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, RetryPolicy};
+    /// # fn example() {
+    /// let client = CoinPaymentsClient::new("client_id", "client_secret")
+    ///     .with_retry_policy(RetryPolicy::new().with_max_attempts(5));
+    /// # }
+    /// ```
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Seed the client-side rate limiter with known limits
+    ///
+    /// Limits are also picked up automatically from `get_client_info`.
+    ///
+    /// # Arguments
+    /// * `limits` - Requests allowed per minute/hour/day (0 = unlimited)
+    pub fn with_rate_limits(self, limits: &RateLimits) -> Self {
+        self.rate_limiter.seed(limits);
+        self
+    }
+
+    /// Use a specific rate limiter, e.g. to share one limiter between clients
+    ///
+    /// # Arguments
+    /// * `rate_limiter` - Rate limiter to use for all requests
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Get the latest rate limit information reported by the API
+    ///
+    /// Returns `None` until a response carrying `X-RateLimit-*` headers has been received.
+    pub fn rate_limit_info(&self) -> Option<RateLimitInfo> {
+        self.rate_limiter.info()
+    }
+
+    /// Use a specific server clock, e.g. to share a learned offset between clients
+    ///
+    /// # Arguments
+    /// * `server_clock` - Clock used to timestamp signed requests
+    pub fn with_server_clock(mut self, server_clock: ServerClock) -> Self {
+        self.server_clock = server_clock;
+        self
+    }
+
+    /// Get the estimated offset of the server clock from the local clock
+    ///
+    /// Returns `None` until a response carrying a `Date` header (or a `ping`) has been received.
+    pub fn clock_offset(&self) -> Option<chrono::Duration> {
+        self.server_clock.offset()
+    }
+
+    /// Use a different request signing scheme
+    ///
+    /// Requests are signed with [`V2Signer`] by default.
+    ///
+    /// # Arguments
+    /// * `signer` - Signer computing the timestamp and signature headers
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = signer;
         self
     }
 
-    /// Generate timestamp for API requests
+    /// Load the client credentials from a provider instead of fixed values
+    ///
+    /// Credentials are re-read after the provider's refresh interval and when the API
+    /// rejects a signature, so rotated secrets are used without a restart.
+    ///
+    /// # Arguments
+    /// * `provider` - Source of the client ID and secret
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, EnvCredentials};
+    /// # use std::sync::Arc;
+    /// # fn example() {
+    /// let client = CoinPaymentsClient::new("", "")
+    ///     .with_credential_provider(Arc::new(EnvCredentials::new()));
+    /// # }
+    /// ```
+    pub fn with_credential_provider(mut self, provider: Arc<dyn CredentialProvider>) -> Self {
+        self.credentials = CredentialCache::new(provider);
+        self
+    }
+
+    /// Generate timestamp for API requests, corrected by the estimated server clock offset
     fn generate_timestamp(&self) -> String {
-        chrono::Utc::now()
-            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-            .to_string()
+        self.signer.timestamp(self.server_clock.now())
     }
 
     /// Generate HMAC signature for API request
     fn generate_signature(
         &self,
+        credentials: &Credentials,
         timestamp: &str,
         method: &str,
+        url: &str,
         endpoint: &str,
         body: &str,
     ) -> String {
-        let data_to_sign = format!(
-            "{}{}{}{}",
-            self.client_id,
-            timestamp,
-            method.to_uppercase(),
-            endpoint
-        );
-        let data_with_body = if !body.is_empty() {
-            format!("{}{}", data_to_sign, body)
-        } else {
-            data_to_sign
-        };
-
-        utils::generate_hmac_signature(&self.client_secret, &data_with_body)
+        self.signer.sign(
+            credentials.client_secret(),
+            &SigningInput {
+                method,
+                url,
+                endpoint,
+                client_id: credentials.client_id(),
+                timestamp,
+                body,
+            },
+        )
     }
 
     /// Create authentication headers
     fn create_auth_headers(
         &self,
+        credentials: &Credentials,
         method: &str,
+        url: &str,
         endpoint: &str,
         body: &str,
     ) -> std::collections::HashMap<String, String> {
         let timestamp = self.generate_timestamp();
-        let signature = self.generate_signature(&timestamp, method, endpoint, body);
+        let signature =
+            self.generate_signature(credentials, &timestamp, method, url, endpoint, body);
 
-        utils::create_auth_headers(&self.client_id, &timestamp, &signature)
+        utils::create_auth_headers(credentials.client_id(), &timestamp, &signature)
     }
 
     /// Make a GET request to the API
@@ -254,128 +720,500 @@ impl CoinPaymentsClient {
     where
         T: for<'de> Deserialize<'de>,
     {
-        let url = format!("{}/{}", self.base_url, endpoint);
-        let query_string = utils::build_query_string(query_params);
-        let full_url = format!("{}{}", url, query_string);
-
-        let auth_headers = self.create_auth_headers("GET", endpoint, "");
+        self.send_request(Method::GET, endpoint, query_params, None, None)
+            .await
+    }
 
-        let mut request = self
-            .client
-            .get(&full_url)
-            .header("Content-Type", "application/json");
+    /// Make a GET request for cacheable reference data
+    ///
+    /// Without a response cache (or a policy for `cached`) this is a plain GET request.
+    pub(crate) async fn cached_get_request<T>(
+        &self,
+        cached: CachedEndpoint,
+        endpoint: &str,
+        query_params: &[(&str, String)],
+    ) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some((cache, policy)) = self
+            .response_cache
+            .as_ref()
+            .and_then(|cache| Some((cache, cache.policy(cached)?)))
+        else {
+            return self.get_request(endpoint, query_params).await;
+        };
 
-        for (key, value) in auth_headers {
-            request = request.header(&key, &value);
+        let path = format!("{}{}", endpoint, utils::build_query_string(query_params));
+        let key = ResponseCache::key(cached, &path);
+        match cache.lookup(&key, policy) {
+            cache::Lookup::Fresh(body) => return self.cache_hit(body),
+            cache::Lookup::Stale(body) => {
+                self.revalidate::<T>(cache.clone(), key, endpoint, query_params);
+                return self.cache_hit(body);
+            }
+            cache::Lookup::Miss => {}
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        // Concurrent misses wait for the first fetch and reuse its response
+        let _flight = cache.join_flight(&key).await;
+        if let cache::Lookup::Fresh(body) = cache.lookup(&key, policy) {
+            return self.cache_hit(body);
+        }
+        self.fetch_cached(cache, &key, endpoint, query_params).await
     }
 
-    /// Make a POST request to the API
-    pub(crate) async fn post_request<T, B>(&self, endpoint: &str, body: &B) -> Result<T>
+    /// Fetch a response and cache it if it parses as `T`
+    async fn fetch_cached<T>(
+        &self,
+        cache: &ResponseCache,
+        key: &str,
+        endpoint: &str,
+        query_params: &[(&str, String)],
+    ) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
-        B: Serialize,
     {
-        let url = format!("{}/{}", self.base_url, endpoint);
-        let body_json = serde_json::to_string(body)?;
-
-        let auth_headers = self.create_auth_headers("POST", endpoint, &body_json);
-
-        let mut request = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .body(body_json);
-
-        for (key, value) in auth_headers {
-            request = request.header(&key, &value);
+        let body: serde_json::Value = self.get_request(endpoint, query_params).await?;
+        let body = body.to_string();
+        let parsed = self.parse_cached(body.clone());
+        if parsed.is_ok() {
+            cache.store(key, body);
         }
+        parsed
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Refresh a stale cache entry in the background (at most one refresh per key)
+    fn revalidate<T>(
+        &self,
+        cache: ResponseCache,
+        key: String,
+        endpoint: &str,
+        query_params: &[(&str, String)],
+    ) where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some(flight) = cache.try_flight(&key) else {
+            return;
+        };
+        let parses: fn(&CoinPaymentsClient, String) -> bool =
+            |client, body| client.parse_cached::<T>(body).is_ok();
+
+        let client = self.clone();
+        let endpoint = endpoint.to_string();
+        let query_params: Vec<(String, String)> = query_params
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        tokio::spawn(async move {
+            let _flight = flight;
+            let query_params: Vec<(&str, String)> = query_params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.clone()))
+                .collect();
+            let fetched = client
+                .get_request::<serde_json::Value>(&endpoint, &query_params)
+                .await;
+            if let Ok(body) = fetched {
+                let body = body.to_string();
+                if parses(&client, body.clone()) {
+                    cache.store(&key, body);
+                }
+            }
+        });
     }
 
-    /// Make a PUT request to the API
-    pub(crate) async fn put_request<T, B>(&self, endpoint: &str, body: &B) -> Result<T>
+    /// Answer a call from the cache, recording metadata without a network attempt
+    fn cache_hit<T>(&self, body: String) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
-        B: Serialize,
     {
-        let url = format!("{}/{}", self.base_url, endpoint);
-        let body_json = serde_json::to_string(body)?;
-
-        let auth_headers = self.create_auth_headers("PUT", endpoint, &body_json);
+        meta::record(&ResponseMeta::new(
+            StatusCode::OK,
+            HeaderMap::new(),
+            std::time::Duration::ZERO,
+            0,
+        ));
+        self.parse_cached(body)
+    }
 
-        let mut request = self
-            .client
-            .put(&url)
-            .header("Content-Type", "application/json")
-            .body(body_json);
+    /// Parse a cached (or to be cached) response body
+    fn parse_cached<T>(&self, body: String) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.handle_response(HttpResponse::new(StatusCode::OK, body))
+    }
 
-        for (key, value) in auth_headers {
-            request = request.header(&key, &value);
-        }
+    /// Send a request and record its outcome on the request span
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "coinpayments.request",
+            skip_all,
+            fields(
+                http.method = %method,
+                endpoint = %endpoint,
+                idempotency_key = ?idempotency_key.as_ref().map(IdempotencyKey::as_str),
+                http.status_code = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                retries = tracing::field::Empty,
+                error.kind = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn send_request<T>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query_params: &[(&str, String)],
+        body: Option<String>,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let result = self
+            .execute_request(method, endpoint, query_params, body, idempotency_key)
+            .await;
+        #[cfg(feature = "tracing")]
+        telemetry::record_outcome(&result);
+        result
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Send a request with the options of the current call
+    async fn execute_request<T>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query_params: &[(&str, String)],
+        body: Option<String>,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let options = options::current().unwrap_or_default();
+        let attempts = self.execute_attempts(
+            method,
+            endpoint,
+            query_params,
+            body,
+            idempotency_key,
+            &options,
+        );
+        options::apply(&options, attempts).await
     }
 
-    /// Make a DELETE request to the API
-    pub(crate) async fn delete_request<T>(&self, endpoint: &str) -> Result<T>
+    /// Sign and send a request, retrying according to the retry policy
+    async fn execute_attempts<T>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query_params: &[(&str, String)],
+        body: Option<String>,
+        idempotency_key: Option<IdempotencyKey>,
+        options: &RequestOptions,
+    ) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
     {
+        let retry_policy = options.retry_policy.as_ref().unwrap_or(&self.retry_policy);
         let url = format!("{}/{}", self.base_url, endpoint);
+        let query_string = utils::build_query_string(query_params);
+        let full_url = format!("{}{}", url, query_string);
+        let body = body.unwrap_or_default();
+
+        // Replay the original response when an idempotency key is repeated; a key in
+        // flight is reserved so a concurrent duplicate waits for its response
+        let (cached_key, _reservation) = match (&self.idempotency_cache, &idempotency_key) {
+            (Some(cache), Some(key)) => {
+                let key = key.as_str();
+                let fingerprint = idempotency::fingerprint(method.as_str(), &full_url, &body);
+                let reservation = cache.reserve(key).await;
+                if let Some(response) = cache.lookup(key, &fingerprint)? {
+                    let mut response_meta = ResponseMeta::new(
+                        response.status,
+                        response.headers.clone(),
+                        std::time::Duration::ZERO,
+                        0,
+                    );
+                    response_meta.idempotency_key = Some(key.to_string());
+                    meta::record(&response_meta);
+                    return self.handle_response(response);
+                }
+                (Some((cache, key, fingerprint)), Some(reservation))
+            }
+            _ => (None, None),
+        };
 
-        let auth_headers = self.create_auth_headers("DELETE", endpoint, "");
+        retry_policy.record_request();
+        let started = std::time::Instant::now();
+        let mut attempt = 1;
+        let mut token_refreshed = false;
+        let mut clock_corrected = false;
+        let mut credentials_reloaded = false;
 
-        let mut request = self
-            .client
-            .delete(&url)
-            .header("Content-Type", "application/json");
+        loop {
+            // Extra headers go first so the client's own headers replace them
+            let mut headers = options.headers.clone();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            if let Some(key) = &idempotency_key {
+                headers.insert(retry::IDEMPOTENCY_KEY_HEADER, header_value(key.as_str())?);
+            }
+
+            let skewed_at_signing = self.server_clock.is_skewed();
+            let mut signed_with = None;
+            let bearer_token = match &self.token_cache {
+                Some(tokens) => {
+                    let token = tokens.token().await?;
+                    let value = Zeroizing::new(format!("Bearer {}", token.as_str()));
+                    headers.insert(AUTHORIZATION, sensitive_header_value(&value)?);
+                    Some(token)
+                }
+                None => {
+                    // Sign every attempt so retries carry a fresh timestamp
+                    let credentials = self.credentials.get()?;
+                    let auth_headers = self.create_auth_headers(
+                        &credentials,
+                        method.as_str(),
+                        &full_url,
+                        endpoint,
+                        &body,
+                    );
+                    signed_with = Some(credentials);
+                    for (key, value) in auth_headers {
+                        let name = HeaderName::from_bytes(key.as_bytes()).map_err(|_| {
+                            CoinPaymentsError::InvalidParameters(format!(
+                                "Invalid header name: {}",
+                                key
+                            ))
+                        })?;
+                        let value = if telemetry::is_sensitive_header(&name) {
+                            sensitive_header_value(&value)?
+                        } else {
+                            header_value(&value)?
+                        };
+                        headers.insert(name, value);
+                    }
+                    None
+                }
+            };
+
+            let request = HttpRequest {
+                method: method.clone(),
+                url: full_url.clone(),
+                headers,
+                body: body.clone().into_bytes(),
+                timeout: options.timeout,
+            };
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                attempt,
+                headers = ?telemetry::redact_headers(&request.headers),
+                "sending request"
+            );
+            let sent_at = chrono::Utc::now();
+            let (request, outcome) = self.dispatch(endpoint, request).await;
+            if let Ok(response) = &outcome {
+                self.server_clock.update_from_headers(
+                    &response.headers,
+                    sent_at,
+                    chrono::Utc::now(),
+                );
+            }
+            // A generated key is only deduplicated in this process, so it does not make a
+            // request that may have reached the API safe to resend
+            let retry_safe = match &idempotency_key {
+                Some(IdempotencyKey::Generated(_)) => {
+                    retry::is_retry_safe(&request.method, &HeaderMap::new())
+                }
+                _ => retry::is_retry_safe(&request.method, &request.headers),
+            };
 
-        for (key, value) in auth_headers {
-            request = request.header(&key, &value);
+            // A rejected token is refreshed once; the request was never processed
+            if let (Some(tokens), Some(token), Ok(response)) =
+                (&self.token_cache, &bearer_token, &outcome)
+            {
+                if response.status == StatusCode::UNAUTHORIZED && !token_refreshed {
+                    token_refreshed = true;
+                    tokens.invalidate(token).await;
+                    attempt += 1;
+                    continue;
+                }
+            }
+
+            // A signature rejected because of newly detected clock skew is re-signed once
+            let rejected_signature = bearer_token.is_none()
+                && matches!(&outcome, Ok(response) if response.status == StatusCode::UNAUTHORIZED);
+            if rejected_signature
+                && !skewed_at_signing
+                && self.server_clock.is_skewed()
+                && !clock_corrected
+            {
+                clock_corrected = true;
+                attempt += 1;
+                continue;
+            }
+
+            // A signature rejected after the secret was rotated is re-signed once
+            if let (true, Some(credentials)) = (rejected_signature, &signed_with) {
+                if !credentials_reloaded {
+                    credentials_reloaded = true;
+                    if self
+                        .credentials
+                        .reload()
+                        .is_ok_and(|reloaded| reloaded != *credentials)
+                    {
+                        attempt += 1;
+                        continue;
+                    }
+                }
+            }
+
+            let delay = match &outcome {
+                Ok(response) => {
+                    self.rate_limiter.update_from_headers(&response.headers);
+                    if response.status == StatusCode::TOO_MANY_REQUESTS {
+                        if let Some(delay) = retry::parse_retry_after(&response.headers) {
+                            self.rate_limiter.pause_for(delay);
+                        }
+                    }
+                    retry_policy.delay_for_response(attempt, response.status, &response.headers)
+                }
+                Err(error) => retry_policy.delay_for_error(attempt, error),
+            };
+
+            if let Some(delay) = delay {
+                if retry_safe && retry_policy.can_retry(attempt) {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "retrying request"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+            }
+
+            let response = outcome?;
+            if let Some((cache, key, fingerprint)) = &cached_key {
+                cache.store(key, fingerprint, &response);
+            }
+
+            let mut response_meta = ResponseMeta::new(
+                response.status,
+                response.headers.clone(),
+                started.elapsed(),
+                attempt,
+            );
+            response_meta.idempotency_key = idempotency_key.map(IdempotencyKey::into_string);
+            meta::record(&response_meta);
+            #[cfg(feature = "tracing")]
+            telemetry::record_response(&response_meta);
+
+            if rejected_signature && self.server_clock.is_skewed() {
+                let offset_ms = self
+                    .server_clock
+                    .offset()
+                    .map_or(0, |offset| offset.num_milliseconds());
+                return Err(CoinPaymentsError::ClockSkew { offset_ms });
+            }
+            return self.handle_response(response);
+        }
+    }
+
+    /// Run a request through the middleware chain and the transport
+    ///
+    /// Returns the request as modified by middleware together with the outcome.
+    async fn dispatch(
+        &self,
+        endpoint: &str,
+        mut request: HttpRequest,
+    ) -> (HttpRequest, Result<HttpResponse>) {
+        let mut entered = 0;
+        let mut short_circuit = None;
+        for middleware in &self.middleware {
+            entered += 1;
+            match middleware.before(endpoint, &mut request) {
+                Ok(None) => {}
+                Ok(Some(response)) => {
+                    short_circuit = Some(response);
+                    break;
+                }
+                Err(error) => return (request, Err(error)),
+            }
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        let outcome = match short_circuit {
+            Some(response) => Ok(response),
+            None => {
+                let permit = match self.circuit_breaker.as_ref().map(CircuitBreaker::acquire) {
+                    Some(Err(error)) => return (request, Err(error)),
+                    Some(Ok(permit)) => Some(permit),
+                    None => None,
+                };
+                self.rate_limiter.acquire().await;
+                let outcome = self.transport.send(request.clone()).await;
+                if let Some(permit) = permit {
+                    permit.record(
+                        outcome
+                            .as_ref()
+                            .map_or(true, |response| response.status.is_server_error()),
+                    );
+                }
+                outcome
+            }
+        };
+
+        let outcome = outcome.map(|mut response| {
+            for middleware in self.middleware[..entered].iter().rev() {
+                middleware.after(endpoint, &request, &mut response);
+            }
+            response
+        });
+        (request, outcome)
     }
 
     /// Handle API response and convert to Result
-    async fn handle_response<T>(&self, response: reqwest::Response) -> Result<T>
+    fn handle_response<T>(&self, response: HttpResponse) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
     {
-        let status = response.status();
-        let response_text = response.text().await?;
+        let status = response.status;
+        let response_text = response.text();
 
         // Handle HTTP error status codes
         if !status.is_success() {
-            let error_message = utils::extract_api_error_message(&response_text);
-
-            return Err(match status.as_u16() {
-                401 => CoinPaymentsError::Authentication,
-                404 => CoinPaymentsError::NotFound,
-                429 => CoinPaymentsError::RateLimit,
-                _ => CoinPaymentsError::Api {
-                    message: format!("HTTP {}: {}", status, error_message),
-                },
-            });
+            return Err(
+                ApiErrorDetails::from_response(status, &response.headers, &response_text)
+                    .into_error(),
+            );
         }
 
+        // An empty success body (e.g. `204 No Content`) decodes as `null`
+        let response_text = if response_text.trim().is_empty() {
+            "null".to_string()
+        } else {
+            response_text
+        };
+
         // Try to parse the response
         serde_json::from_str::<T>(&response_text).or_else(|parse_error| {
             // If direct parsing fails, try parsing as ApiResponse wrapper
             match serde_json::from_str::<ApiResponse<T>>(&response_text) {
                 Ok(api_response) => {
                     if let Some(error) = api_response.error {
-                        Err(CoinPaymentsError::Api {
-                            message: format!("API Error: {}", error.message),
-                        })
+                        let details = ApiErrorDetails {
+                            status,
+                            code: Some(error.code),
+                            message: error.message,
+                            details: error.details,
+                            request_id: meta::extract_request_id(&response.headers),
+                        };
+                        Err(details.into_error())
                     } else if let Some(data) = api_response.data {
                         Ok(data)
                     } else {
@@ -395,18 +1233,51 @@ impl CoinPaymentsClient {
     }
 
     /// Get client information
+    ///
+    /// The returned rate limits are used to seed the client-side rate limiter.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_client_info(&self) -> Result<ClientInfo> {
-        self.get_request("v1/client/info", &[]).await
+        let info = self.execute(GetClientInfo).await?;
+        self.rate_limiter.seed(&info.rate_limits);
+        Ok(info)
     }
 
     /// Test API connectivity and authentication
+    ///
+    /// The returned server timestamp is used to correct the clock of signed requests.
+    /// Pings are let through an open circuit breaker to probe whether the API recovered.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn ping(&self) -> Result<PingResponse> {
-        self.get_request("v1/ping", &[]).await
+        let sent_at = chrono::Utc::now();
+        let pong = circuit::probe(self.execute(Ping)).await?;
+        if let Ok(server_time) = chrono::DateTime::parse_from_rfc3339(&pong.timestamp) {
+            self.server_clock.update_from_server_time(
+                server_time.with_timezone(&chrono::Utc),
+                sent_at,
+                chrono::Utc::now(),
+            );
+        }
+        Ok(pong)
     }
 }
 
+/// Convert a string into a header value
+fn header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value).map_err(|_| {
+        CoinPaymentsError::InvalidParameters("Header value contains invalid characters".to_string())
+    })
+}
+
+/// Convert a credential into a header value that is hidden from `Debug` output
+fn sensitive_header_value(value: &str) -> Result<HeaderValue> {
+    let mut value = header_value(value)?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
 /// Client information
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ClientInfo {
     pub client_id: String,
     pub name: String,
@@ -417,7 +1288,8 @@ pub struct ClientInfo {
 }
 
 /// Rate limit information
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct RateLimits {
     pub requests_per_minute: u32,
     pub requests_per_hour: u32,
@@ -426,12 +1298,39 @@ pub struct RateLimits {
 
 /// Ping response
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PingResponse {
     pub message: String,
     pub timestamp: String,
     pub version: String,
 }
 
+/// Endpoint of [`CoinPaymentsClient::get_client_info`]
+#[derive(Debug, Clone, Default)]
+pub struct GetClientInfo;
+
+impl Endpoint for GetClientInfo {
+    type Request = ();
+    type Response = ClientInfo;
+
+    const NAME: &'static str = "get_client_info";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v1/client/info";
+}
+
+/// Endpoint of [`CoinPaymentsClient::ping`]
+#[derive(Debug, Clone, Default)]
+pub struct Ping;
+
+impl Endpoint for Ping {
+    type Request = ();
+    type Response = PingResponse;
+
+    const NAME: &'static str = "ping";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v1/ping";
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,15 +1338,31 @@ mod tests {
     #[test]
     fn test_client_creation() {
         let client = CoinPaymentsClient::new("test_client_id", "test_client_secret");
-        assert_eq!(client.client_id, "test_client_id");
-        assert_eq!(client.client_secret, "test_client_secret");
+        let credentials = client.credentials.get().unwrap();
+        assert_eq!(credentials.client_id(), "test_client_id");
+        assert_eq!(credentials.client_secret(), "test_client_secret");
         assert_eq!(client.base_url, API_BASE_URL);
+        assert!(!format!("{:?}", client).contains("test_client_secret"));
     }
 
     #[test]
     fn test_signature_generation() {
         let client = CoinPaymentsClient::new("test_client", "test_secret");
-        let signature = client.generate_signature("2023-01-01T00:00:00.000Z", "GET", "/test", "");
+        let url = "https://a-api.coinpayments.net/api/test?page=1";
+        let credentials = Credentials::new("test_client", "test_secret");
+        let signature =
+            client.generate_signature(&credentials, "2023-01-01T00:00:00", "GET", url, "test", "");
+        assert_eq!(signature.len(), 44); // Base64 SHA256 length
+
+        let legacy = client.with_signer(Arc::new(LegacySigner));
+        let signature = legacy.generate_signature(
+            &credentials,
+            "2023-01-01T00:00:00.000Z",
+            "GET",
+            url,
+            "/test",
+            "",
+        );
         assert!(!signature.is_empty());
         assert_eq!(signature.len(), 128); // SHA512 hex string length
     }
@@ -457,6 +1372,821 @@ mod tests {
         let client = CoinPaymentsClient::new("test_client", "test_secret")
             .with_base_url("https://custom-api.example.com");
         assert_eq!(client.base_url, "https://custom-api.example.com");
+
+        let client = client.with_base_url("https://custom-api.example.com/api/");
+        assert_eq!(client.base_url, "https://custom-api.example.com/api");
+    }
+
+    #[tokio::test]
+    async fn test_oauth_sends_bearer_token() {
+        let mut server = mockito::Server::new_async().await;
+        let ping = server
+            .mock("GET", "/v1/ping")
+            .match_header("Authorization", "Bearer access_token")
+            .match_header("X-CoinPayments-Signature", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_body(r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#)
+            .create_async()
+            .await;
+
+        let client =
+            CoinPaymentsClient::with_auth(AuthType::OAuth("access_token".to_string().into()))
+                .with_base_url(server.url());
+
+        client.ping().await.unwrap();
+        ping.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_oauth_refreshes_token_after_unauthorized() {
+        let mut server = mockito::Server::new_async().await;
+        let rejected = server
+            .mock("POST", "/v2/merchant/invoices")
+            .match_header("Authorization", "Bearer token-1")
+            .with_status(401)
+            .expect(1)
+            .create_async()
+            .await;
+        let still_rejected = server
+            .mock("POST", "/v2/merchant/invoices")
+            .match_header("Authorization", "Bearer token-2")
+            .with_status(401)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let calls = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = calls.clone();
+        let source = move || {
+            let n = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            async move { Ok(AccessToken::new(format!("token-{}", n))) }
+        };
+        let client = CoinPaymentsClient::with_auth(AuthType::TokenSource(Arc::new(source)))
+            .with_base_url(server.url())
+            .with_retry_policy(RetryPolicy::none());
+
+        let request = CreateInvoiceRequest::new("10.00", "USD", "Test");
+        let result = client.create_invoice(request).await;
+
+        // Only one refresh is attempted, so a second 401 is returned to the caller
+        assert!(matches!(result, Err(CoinPaymentsError::Authentication(_))));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        rejected.assert_async().await;
+        still_rejected.assert_async().await;
+    }
+
+    const PONG: &str = r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#;
+
+    #[tokio::test]
+    async fn test_mock_transport_receives_signed_requests() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, PONG);
+
+        let client =
+            CoinPaymentsClient::new("test_client", "test_secret").with_transport(transport.clone());
+
+        let ping = client.ping().await.unwrap();
+        assert_eq!(ping.message, "pong");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::GET);
+        assert_eq!(requests[0].path(), "/api/v1/ping");
+        assert_eq!(requests[0].headers["X-CoinPayments-Client"], "test_client");
+        assert!(requests[0].headers.contains_key("X-CoinPayments-Signature"));
+    }
+
+    #[tokio::test]
+    async fn test_request_debug_hides_credentials() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, PONG);
+        transport.push_json(200, PONG);
+
+        let signed =
+            CoinPaymentsClient::new("test_client", "test_secret").with_transport(transport.clone());
+        signed.ping().await.unwrap();
+        let bearer =
+            CoinPaymentsClient::with_auth(AuthType::OAuth("access_token".to_string().into()))
+                .with_transport(transport.clone());
+        bearer.ping().await.unwrap();
+
+        let requests = transport.requests();
+        let signature = requests[0].headers["X-CoinPayments-Signature"]
+            .to_str()
+            .unwrap();
+        assert_eq!(requests[1].headers[AUTHORIZATION], "Bearer access_token");
+
+        let debug = format!("{:?} {:?}", requests, transport);
+        assert!(debug.contains("test_client"));
+        assert!(!debug.contains(signature));
+        assert!(!debug.contains("access_token"));
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors_are_retried() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error("connection reset");
+        transport.push_json(200, PONG);
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(fast_retry_policy());
+
+        client.ping().await.unwrap();
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_requests_with_query_are_signed_over_full_url() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, r#"{"currencies":[]}"#);
+
+        let client =
+            CoinPaymentsClient::new("test_client", "test_secret").with_transport(transport.clone());
+        let _ = client.get_currencies(Some(2), Some(50)).await;
+
+        let request = &transport.requests()[0];
+        assert!(request.url.ends_with("/v2/currencies?page=2&perPage=50"));
+        let header = |name: &str| request.headers[name].to_str().unwrap().to_string();
+        let expected = V2Signer.sign(
+            "test_secret",
+            &SigningInput {
+                method: "GET",
+                url: &request.url,
+                endpoint: "v2/currencies",
+                client_id: "test_client",
+                timestamp: &header("X-CoinPayments-Timestamp"),
+                body: "",
+            },
+        );
+        assert_eq!(header("X-CoinPayments-Signature"), expected);
+    }
+
+    #[tokio::test]
+    async fn test_request_options_apply_to_single_call() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(503, "{}");
+        transport.push_json(200, PONG);
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(fast_retry_policy());
+
+        let options = RequestOptions::new()
+            .with_retry_policy(RetryPolicy::none())
+            .with_timeout(std::time::Duration::from_secs(5))
+            .with_header("X-Checkout-Id", "chk_1")
+            .unwrap();
+        assert!(client.ping().with_options(options).await.is_err());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers["X-Checkout-Id"], "chk_1");
+        assert_eq!(requests[0].timeout, Some(std::time::Duration::from_secs(5)));
+
+        // Later calls use the client's settings again
+        client.ping().await.unwrap();
+        assert!(!transport.requests()[1]
+            .headers
+            .contains_key("X-Checkout-Id"));
+    }
+
+    #[tokio::test]
+    async fn test_request_options_cannot_replace_client_headers() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, "{}");
+
+        let client =
+            CoinPaymentsClient::new("test_client", "test_secret").with_transport(transport.clone());
+
+        let mut options = RequestOptions::new().with_idempotency_key("payout-1");
+        for (name, value) in [
+            (retry::IDEMPOTENCY_KEY_HEADER, "other"),
+            ("Content-Type", "text/plain"),
+            ("X-CoinPayments-Client", "other_client"),
+        ] {
+            options
+                .headers
+                .insert(name, HeaderValue::from_static(value));
+        }
+        let _ = client
+            .create_spend_request("wallet", "4", spend_request())
+            .with_options(options)
+            .await;
+
+        let headers = &transport.requests()[0].headers;
+        assert_eq!(headers[retry::IDEMPOTENCY_KEY_HEADER], "payout-1");
+        assert_eq!(headers[CONTENT_TYPE], "application/json");
+        assert_eq!(headers["X-CoinPayments-Client"], "test_client");
+    }
+
+    #[tokio::test]
+    async fn test_request_options_cancellation() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(503, "{}");
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::new().with_backoff(
+                std::time::Duration::from_secs(60),
+                std::time::Duration::from_secs(60),
+            ));
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let options = RequestOptions::new().with_cancellation(token);
+        assert!(matches!(
+            client.ping().with_options(options).await,
+            Err(CoinPaymentsError::Cancelled)
+        ));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    fn rates_body(last_updated: &str) -> String {
+        format!(r#"{{"rates":[],"lastUpdated":"{}"}}"#, last_updated)
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_deduplicates_and_invalidates() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, rates_body("t1"));
+        transport.push_json(200, rates_body("t2"));
+        let cache = ResponseCache::new();
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_response_cache(cache.clone());
+
+        let (first, second) = tokio::join!(client.get_rates(None), client.get_rates(None));
+        assert_eq!(first.unwrap().last_updated, "t1");
+        assert_eq!(second.unwrap().last_updated, "t1");
+        assert_eq!(transport.requests().len(), 1);
+
+        cache.invalidate(CachedEndpoint::Rates);
+        assert_eq!(client.get_rates(None).await.unwrap().last_updated, "t2");
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_hits_record_metadata() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, rates_body("t1"));
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_response_cache(ResponseCache::new());
+
+        let (_, fetched) = client.get_rates(None).with_meta().await.unwrap();
+        let (rates, cached) = client.get_rates(None).with_meta().await.unwrap();
+        assert_eq!(rates.last_updated, "t1");
+        assert_eq!(fetched.attempts, 1);
+        assert_eq!(cached.status, StatusCode::OK);
+        assert_eq!(cached.attempts, 0);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_serves_stale_while_revalidating() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, rates_body("t1"));
+        transport.push_json(200, rates_body("t2"));
+        let cache = ResponseCache::new().with_policy(
+            CachedEndpoint::Rates,
+            CachePolicy::new(std::time::Duration::ZERO)
+                .with_stale_while_revalidate(std::time::Duration::from_secs(60)),
+        );
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_response_cache(cache);
+
+        assert_eq!(client.get_rates(None).await.unwrap().last_updated, "t1");
+        assert_eq!(client.get_rates(None).await.unwrap().last_updated, "t1");
+
+        // The stale hit triggered a background refresh
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(transport.requests().len(), 2);
+        transport.push_json(200, rates_body("t3"));
+        assert_eq!(client.get_rates(None).await.unwrap().last_updated, "t2");
+    }
+
+    #[tokio::test]
+    async fn test_uncached_endpoints_and_errors_bypass_cache() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(400, r#"{"error":"Bad query"}"#);
+        transport.push_json(200, rates_body("t1"));
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none())
+            .with_response_cache(ResponseCache::new().without_endpoint(CachedEndpoint::Currencies));
+
+        assert!(client.get_rates(None).await.is_err());
+        assert_eq!(client.get_rates(None).await.unwrap().last_updated, "t1");
+        assert!(client.get_currencies(None, None).await.is_err());
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast_and_ping_probes() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(503, "{}");
+        transport.push_error("connection reset");
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none())
+            .with_circuit_breaker(
+                CircuitBreaker::new()
+                    .with_minimum_requests(2)
+                    .with_open_duration(std::time::Duration::from_secs(60)),
+            );
+
+        assert!(client.get_rates(None).await.is_err());
+        assert!(client.get_rates(None).await.is_err());
+        assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+        let error = client.get_rates(None).await.unwrap_err();
+        assert!(matches!(error, CoinPaymentsError::CircuitOpen { .. }));
+        assert!(error.is_retryable());
+        assert_eq!(transport.requests().len(), 2);
+
+        transport.push_json(200, PONG);
+        client.ping().await.unwrap();
+        assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    fn rejected_with_date(server_time: chrono::DateTime<chrono::Utc>) -> HttpResponse {
+        HttpResponse::new(StatusCode::UNAUTHORIZED, "").with_header(
+            "Date",
+            &server_time.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_clock_skew_is_corrected_and_request_resigned() {
+        let server_time = chrono::Utc::now() + chrono::Duration::hours(1);
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(rejected_with_date(server_time));
+        transport.push_json(200, PONG);
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none());
+
+        client.ping().await.unwrap();
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+
+        let timestamp = |request: &HttpRequest| {
+            let value = request.headers["X-CoinPayments-Timestamp"]
+                .to_str()
+                .unwrap();
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap()
+        };
+        let correction = timestamp(&requests[1]) - timestamp(&requests[0]);
+        assert!(
+            (correction - chrono::Duration::hours(1))
+                .num_seconds()
+                .abs()
+                <= 2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rotated_secret_is_reloaded_after_rejection() {
+        #[derive(Debug)]
+        struct RotatingCredentials(std::sync::Mutex<&'static str>);
+
+        impl CredentialProvider for RotatingCredentials {
+            fn load(&self) -> Result<Credentials> {
+                Ok(Credentials::new("test_client", *self.0.lock().unwrap()))
+            }
+
+            fn refresh_interval(&self) -> Option<std::time::Duration> {
+                None
+            }
+        }
+
+        // A current server time, so the rejection is not attributed to clock skew
+        let pong = PONG.replace("2023-01-01T00:00:00Z", &chrono::Utc::now().to_rfc3339());
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, pong.clone());
+        transport.push_json(401, "");
+        transport.push_json(200, pong);
+        let provider = Arc::new(RotatingCredentials(std::sync::Mutex::new("old_secret")));
+        let client = CoinPaymentsClient::with_auth(AuthType::Provider(provider.clone()))
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none());
+
+        client.ping().await.unwrap();
+        *provider.0.lock().unwrap() = "new_secret";
+        client.ping().await.unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            client.credentials.get().unwrap().client_secret(),
+            "new_secret"
+        );
+
+        // Unchanged credentials are not retried
+        transport.push_json(401, "");
+        assert!(matches!(
+            client.ping().await,
+            Err(CoinPaymentsError::Authentication(_))
+        ));
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_authentication_failure_with_clock_skew() {
+        let server_time = chrono::Utc::now() - chrono::Duration::minutes(10);
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(rejected_with_date(server_time));
+        transport.push_response(rejected_with_date(server_time));
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none());
+
+        let error = client.get_client_info().await.unwrap_err();
+        match error {
+            CoinPaymentsError::ClockSkew { offset_ms } => {
+                assert!((offset_ms + 600_000).abs() < 5_000)
+            }
+            other => panic!("expected clock skew, got {:?}", other),
+        }
+        assert_eq!(transport.requests().len(), 2);
+
+        // Without drift a 401 stays an authentication failure
+        transport.push_response(rejected_with_date(chrono::Utc::now()));
+        assert!(matches!(
+            client.get_client_info().await,
+            Err(CoinPaymentsError::Authentication(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ping_timestamp_sets_clock_offset() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, PONG);
+
+        let client =
+            CoinPaymentsClient::new("test_client", "test_secret").with_transport(transport.clone());
+        assert!(client.clock_offset().is_none());
+
+        client.ping().await.unwrap();
+        assert!(client.clock_offset().unwrap() < chrono::Duration::days(-365));
+    }
+
+    fn spend_request() -> CreateSpendRequest {
+        CreateSpendRequest {
+            amount: "0.5".to_string(),
+            destination_address: Some("bc1qexample".to_string()),
+            destination_currency_id: None,
+            note: None,
+            auto_confirm: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spend_requests_with_generated_idempotency_key_are_not_retried() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(503, "{}");
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(fast_retry_policy());
+
+        assert!(client
+            .create_spend_request("wallet", "4", spend_request())
+            .await
+            .is_err());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers[retry::IDEMPOTENCY_KEY_HEADER].len(), 36);
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors_are_retryable_like_the_retry_policy() {
+        let policy = RetryPolicy::new();
+        let retryable = |error: CoinPaymentsError| {
+            let retried = policy.delay_for_error(1, &error).is_some();
+            assert_eq!(error.is_retryable(), retried, "{:?}", error);
+            retried
+        };
+
+        // Connection refused
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let refused = listener.local_addr().unwrap();
+        drop(listener);
+        let error = reqwest::get(format!("http://{}", refused))
+            .await
+            .unwrap_err();
+        assert!(error.is_connect());
+        assert!(retryable(error.into()));
+
+        // Connection closed before a response was sent
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                drop(socket);
+            }
+        });
+        let error = reqwest::get(format!("http://{}", address))
+            .await
+            .unwrap_err();
+        assert!(error.is_request() && !error.is_connect());
+        assert!(retryable(error.into()));
+
+        // Invalid request that never reached the network
+        let error = reqwest::get("not a url").await.unwrap_err();
+        assert!(error.is_builder());
+        assert!(!retryable(error.into()));
+
+        assert!(retryable(CoinPaymentsError::Network("reset".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_spend_requests_with_generated_idempotency_key_are_not_retried_on_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                accepted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                sockets.push(socket);
+            }
+        });
+
+        let client = CoinPaymentsClient::builder()
+            .with_credentials("test_client", "test_secret")
+            .with_base_url(format!("http://{}", address))
+            .with_timeout(std::time::Duration::from_millis(200))
+            .with_retry_policy(fast_retry_policy())
+            .build()
+            .unwrap();
+
+        let result = client
+            .create_spend_request("wallet", "4", spend_request())
+            .await;
+        assert!(matches!(result, Err(CoinPaymentsError::Http(error)) if error.is_timeout()));
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_spend_requests_with_caller_idempotency_key_are_retried() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(503, "{}");
+        transport.push_json(400, r#"{"error":"Insufficient balance"}"#);
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(fast_retry_policy());
+
+        assert!(client
+            .create_spend_request("wallet", "4", spend_request())
+            .with_idempotency_key("payout-7")
+            .await
+            .is_err());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert_eq!(request.headers[retry::IDEMPOTENCY_KEY_HEADER], "payout-7");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_cache_replays_duplicate_keys() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, r#"{"id":"payout-1"}"#);
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_idempotency_cache(IdempotencyCache::new(std::time::Duration::from_secs(60)));
+        let body = serde_json::json!({"amount": "1.0"});
+
+        let (first, first_meta) = client
+            .raw()
+            .post("v1/payouts")
+            .with_json(&body)
+            .send::<serde_json::Value>()
+            .with_idempotency_key("order-42")
+            .with_meta()
+            .await
+            .unwrap();
+        let (second, second_meta) = client
+            .raw()
+            .post("v1/payouts")
+            .with_json(&body)
+            .send::<serde_json::Value>()
+            .with_idempotency_key("order-42")
+            .with_meta()
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(
+            transport.requests()[0].headers[retry::IDEMPOTENCY_KEY_HEADER],
+            "order-42"
+        );
+        assert_eq!(first_meta.attempts, 1);
+        assert_eq!(second_meta.attempts, 0);
+        assert_eq!(second_meta.idempotency_key.as_deref(), Some("order-42"));
+
+        // The same key for a different request is rejected without a network call
+        let other = serde_json::json!({"amount": "2.0"});
+        let result = client
+            .raw()
+            .post("v1/payouts")
+            .with_json(&other)
+            .send::<serde_json::Value>()
+            .with_idempotency_key("order-42")
+            .await;
+        assert!(matches!(
+            result,
+            Err(CoinPaymentsError::InvalidParameters(_))
+        ));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    /// Transport answering after a short delay, so concurrent calls overlap
+    #[derive(Debug)]
+    struct SlowTransport(Arc<MockTransport>);
+
+    impl Transport for SlowTransport {
+        fn send(&self, request: HttpRequest) -> transport::TransportFuture<'_> {
+            Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                self.0.send(request).await
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_duplicate_keys_send_one_request() {
+        let transport = Arc::new(MockTransport::new());
+        let fixture: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/create_spend_request.json"))
+                .unwrap();
+        transport.push_json(200, fixture["response"].to_string());
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(Arc::new(SlowTransport(transport.clone())))
+            .with_retry_policy(RetryPolicy::none())
+            .with_idempotency_cache(IdempotencyCache::new(std::time::Duration::from_secs(60)));
+        let spend = || {
+            client
+                .create_spend_request("hot", "4", CreateSpendRequest::new("0.1"))
+                .with_idempotency_key("payout-42")
+        };
+
+        let (first, second) = tokio::join!(spend(), spend());
+        assert_eq!(first.unwrap().request.id, "spend_1");
+        assert_eq!(second.unwrap().request.id, "spend_1");
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_keep_status_code_and_request_id() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(
+            HttpResponse::new(
+                StatusCode::BAD_REQUEST,
+                r#"{"error":{"code":"INSUFFICIENT_FUNDS","message":"Balance too low","details":{"available":"0.1"}}}"#,
+            )
+            .with_header("X-Request-Id", "req-77"),
+        );
+        transport.push_json(503, r#"{"message":"Maintenance"}"#);
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy::none());
+
+        let error = client.get_wallet_count().await.unwrap_err();
+        assert!(matches!(error, CoinPaymentsError::InsufficientFunds(_)));
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.code(), Some("INSUFFICIENT_FUNDS"));
+        assert_eq!(error.request_id(), Some("req-77"));
+        assert_eq!(
+            error.details().unwrap().details.as_ref().unwrap()["available"],
+            "0.1"
+        );
+        assert!(error.is_client_error());
+        assert!(!error.is_retryable());
+
+        let error = client.get_wallet_count().await.unwrap_err();
+        assert!(matches!(error, CoinPaymentsError::Server(_)));
+        assert!(error.is_retryable());
+        assert!(!error.is_client_error());
+    }
+
+    fn fast_retry_policy() -> RetryPolicy {
+        RetryPolicy::new()
+            .with_backoff(
+                std::time::Duration::from_millis(1),
+                std::time::Duration::from_millis(5),
+            )
+            .with_budget(None)
+    }
+
+    #[tokio::test]
+    async fn test_get_request_retries_server_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/v1/ping")
+            .with_status(503)
+            .with_header("Retry-After", "0")
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", "/v1/ping")
+            .with_status(200)
+            .with_body(r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_base_url(server.url())
+            .with_retry_policy(fast_retry_policy());
+
+        let ping = client.ping().await.unwrap();
+        assert_eq!(ping.message, "pong");
+        unavailable.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_post_request_without_idempotency_key_is_not_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("POST", "/v1/merchant/invoices/inv_123/cancel")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_base_url(server.url())
+            .with_retry_policy(fast_retry_policy());
+
+        assert!(client.cancel_invoice("inv_123").await.is_err());
+        unavailable.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_info_from_headers() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/v1/ping")
+            .with_status(200)
+            .with_header("X-RateLimit-Limit", "100")
+            .with_header("X-RateLimit-Remaining", "97")
+            .with_header("X-RateLimit-Reset", "60")
+            .with_body(r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#)
+            .create_async()
+            .await;
+
+        let client =
+            CoinPaymentsClient::new("test_client", "test_secret").with_base_url(server.url());
+        assert!(client.rate_limit_info().is_none());
+
+        client.clone().ping().await.unwrap();
+        let info = client.rate_limit_info().unwrap();
+        assert_eq!(info.calls_made, 3);
+        assert_eq!(info.calls_left, 97);
+    }
+
+    #[tokio::test]
+    async fn test_with_meta_exposes_response_metadata() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/v1/ping")
+            .with_status(200)
+            .with_header("X-Request-Id", "req-123")
+            .with_body(r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#)
+            .create_async()
+            .await;
+
+        let client =
+            CoinPaymentsClient::new("test_client", "test_secret").with_base_url(server.url());
+
+        let handle = tokio::spawn(async move { client.ping().with_meta().await });
+        let (ping, meta) = handle.await.unwrap().unwrap();
+        assert_eq!(ping.message, "pong");
+        assert_eq!(meta.status, reqwest::StatusCode::OK);
+        assert_eq!(meta.request_id.as_deref(), Some("req-123"));
+        assert_eq!(meta.attempts, 1);
     }
 
     #[tokio::test]
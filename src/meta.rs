@@ -0,0 +1,161 @@
+//! Response metadata for CoinPayments API calls
+//!
+//! This module provides functionality for:
+//! - Capturing HTTP status, headers, request id, rate limits and latency of a call
+//! - Returning that metadata alongside typed results via [`ResponseMetaExt::with_meta`]
+
+use crate::utils::{self, RateLimitInfo};
+use crate::{CoinPaymentsError, Result};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Headers that may carry the server-side request/correlation id, in order of preference
+pub const REQUEST_ID_HEADERS: &[&str] = &[
+    "X-Request-Id",
+    "X-Correlation-Id",
+    "Request-Id",
+    "X-Amzn-RequestId",
+];
+
+tokio::task_local! {
+    static META_SLOT: Arc<Mutex<Option<ResponseMeta>>>;
+}
+
+// === Metadata Types ===
+
+/// Metadata of the HTTP response behind an API call
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// Raw response headers
+    pub headers: HeaderMap,
+    /// Server request/correlation id, if the API returned one
+    pub request_id: Option<String>,
+    /// Rate limit information parsed from `X-RateLimit-*` headers
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Time from the first attempt until the final response was received
+    pub latency: Duration,
+    /// Number of attempts made (1 when the call was not retried, 0 when replayed from cache)
+    pub attempts: u32,
+    /// Idempotency key sent with the request, if any
+    pub idempotency_key: Option<String>,
+}
+
+impl ResponseMeta {
+    /// Build metadata from a response status and headers
+    pub fn new(status: StatusCode, headers: HeaderMap, latency: Duration, attempts: u32) -> Self {
+        Self {
+            status,
+            request_id: extract_request_id(&headers),
+            rate_limit: utils::extract_rate_limit_info(&headers),
+            headers,
+            latency,
+            attempts,
+            idempotency_key: None,
+        }
+    }
+}
+
+/// Extension for endpoint futures that also returns the response metadata
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::CoinPaymentsClient;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use coinpayments::ResponseMetaExt;
+///
+/// let client = CoinPaymentsClient::new("client_id", "client_secret");
+/// let (currencies, meta) = client.get_currencies(None, None).with_meta().await?;
+/// println!("request id: {:?}, took {:?}", meta.request_id, meta.latency);
+/// # Ok(())
+/// # }
+/// ```
+pub trait ResponseMetaExt<T>: Future<Output = Result<T>> + Sized {
+    /// Resolve to the typed result together with the metadata of the last HTTP response
+    fn with_meta(self) -> impl Future<Output = Result<(T, ResponseMeta)>>;
+}
+
+impl<F, T> ResponseMetaExt<T> for F
+where
+    F: Future<Output = Result<T>>,
+{
+    async fn with_meta(self) -> Result<(T, ResponseMeta)> {
+        let slot = Arc::new(Mutex::new(None));
+        let value = META_SLOT.scope(slot.clone(), self).await?;
+        let meta = slot.lock().unwrap().take();
+
+        meta.map(|meta| (value, meta))
+            .ok_or_else(|| CoinPaymentsError::Api {
+                message: "No response metadata was recorded for this call".to_string(),
+            })
+    }
+}
+
+/// Record metadata for the enclosing `with_meta` call, if any
+pub(crate) fn record(meta: &ResponseMeta) {
+    let _ = META_SLOT.try_with(|slot| *slot.lock().unwrap() = Some(meta.clone()));
+}
+
+// === Helper Functions ===
+
+/// Extract the server request/correlation id from response headers
+pub fn extract_request_id(headers: &HeaderMap) -> Option<String> {
+    REQUEST_ID_HEADERS.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_extract_request_id() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(extract_request_id(&headers), None);
+
+        headers.insert("X-Correlation-Id", HeaderValue::from_static("corr-1"));
+        assert_eq!(extract_request_id(&headers), Some("corr-1".to_string()));
+
+        headers.insert("X-Request-Id", HeaderValue::from_static("req-1"));
+        assert_eq!(extract_request_id(&headers), Some("req-1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_with_meta_returns_recorded_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Request-Id", HeaderValue::from_static("req-42"));
+
+        let (value, meta) = async {
+            record(&ResponseMeta::new(
+                StatusCode::OK,
+                headers,
+                Duration::from_millis(12),
+                1,
+            ));
+            Ok(7)
+        }
+        .with_meta()
+        .await
+        .unwrap();
+
+        assert_eq!(value, 7);
+        assert_eq!(meta.status, StatusCode::OK);
+        assert_eq!(meta.request_id.as_deref(), Some("req-42"));
+        assert_eq!(meta.latency, Duration::from_millis(12));
+    }
+
+    #[tokio::test]
+    async fn test_with_meta_without_response_is_an_error() {
+        let result = async { Ok(1) }.with_meta().await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Request/response middleware for CoinPayments API calls
+//!
+//! This module provides functionality for:
+//! - Inspecting and modifying signed requests before they are sent
+//! - Short-circuiting calls with a canned response or an error (e.g. dry-run guards)
+//! - Inspecting and modifying responses before they are parsed
+
+use crate::transport::{HttpRequest, HttpResponse};
+use crate::Result;
+
+/// Hook into every HTTP attempt made by a client
+///
+/// Middleware runs in the order it was added: `before` hooks first to last, then
+/// `after` hooks last to first. Hooks run once per attempt, so retried calls are
+/// seen again with freshly signed headers.
+///
+/// Changing the method, URL or body in `before` invalidates the HMAC signature;
+/// adding headers is safe.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, CoinPaymentsError, HttpRequest, HttpResponse, Middleware, Result};
+/// # use std::sync::Arc;
+/// # use reqwest::Method;
+/// # fn example() {
+/// #[derive(Debug)]
+/// struct DryRun;
+///
+/// impl Middleware for DryRun {
+///     fn before(&self, endpoint: &str, request: &mut HttpRequest) -> Result<Option<HttpResponse>> {
+///         if request.method != Method::GET {
+///             return Err(CoinPaymentsError::InvalidParameters(format!("dry run: {}", endpoint)));
+///         }
+///         Ok(None)
+///     }
+/// }
+///
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_middleware(Arc::new(DryRun));
+/// # }
+/// ```
+pub trait Middleware: Send + Sync + std::fmt::Debug {
+    /// Called before a request is sent
+    ///
+    /// Return `Ok(Some(response))` to skip the transport and use `response` instead,
+    /// or an error to abort the call.
+    fn before(&self, endpoint: &str, request: &mut HttpRequest) -> Result<Option<HttpResponse>> {
+        let _ = (endpoint, request);
+        Ok(None)
+    }
+
+    /// Called after a response was received (or produced by a short-circuit)
+    fn after(&self, endpoint: &str, request: &HttpRequest, response: &mut HttpResponse) {
+        let _ = (endpoint, request, response);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CoinPaymentsClient, CoinPaymentsError, MockTransport, RetryPolicy};
+    use reqwest::header::HeaderValue;
+    use reqwest::{Method, StatusCode};
+    use std::sync::{Arc, Mutex};
+
+    const PONG: &str = r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#;
+
+    #[derive(Debug)]
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Middleware for Recorder {
+        fn before(
+            &self,
+            endpoint: &str,
+            request: &mut HttpRequest,
+        ) -> Result<Option<HttpResponse>> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} before {}", self.name, endpoint));
+            request
+                .headers
+                .append("X-Middleware", HeaderValue::from_static(self.name));
+            Ok(None)
+        }
+
+        fn after(&self, _endpoint: &str, _request: &HttpRequest, response: &mut HttpResponse) {
+            self.log.lock().unwrap().push(format!(
+                "{} after {}",
+                self.name,
+                response.status.as_u16()
+            ));
+        }
+    }
+
+    #[derive(Debug)]
+    struct DryRun;
+
+    impl Middleware for DryRun {
+        fn before(
+            &self,
+            endpoint: &str,
+            request: &mut HttpRequest,
+        ) -> Result<Option<HttpResponse>> {
+            if request.method == Method::GET {
+                return Ok(None);
+            }
+            Err(CoinPaymentsError::InvalidParameters(format!(
+                "dry run: {} {}",
+                request.method, endpoint
+            )))
+        }
+    }
+
+    #[derive(Debug)]
+    struct CannedPong;
+
+    impl Middleware for CannedPong {
+        fn before(
+            &self,
+            _endpoint: &str,
+            _request: &mut HttpRequest,
+        ) -> Result<Option<HttpResponse>> {
+            Ok(Some(HttpResponse::new(StatusCode::OK, PONG)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_order_and_header_injection() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, PONG);
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_middleware(Arc::new(Recorder {
+                name: "outer",
+                log: log.clone(),
+            }))
+            .with_middleware(Arc::new(Recorder {
+                name: "inner",
+                log: log.clone(),
+            }));
+
+        client.ping().await.unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "outer before v1/ping",
+                "inner before v1/ping",
+                "inner after 200",
+                "outer after 200",
+            ]
+        );
+        let request = &transport.requests()[0];
+        let injected: Vec<_> = request.headers.get_all("X-Middleware").iter().collect();
+        assert_eq!(injected, vec!["outer", "inner"]);
+        assert!(request.headers.contains_key("X-CoinPayments-Signature"));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_can_abort_calls() {
+        let transport = Arc::new(MockTransport::new());
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy::none())
+            .with_middleware(Arc::new(DryRun));
+
+        let request = crate::CreateInvoiceRequest::new("10.00", "USD", "Test");
+        let result = client.create_invoice(request).await;
+
+        assert!(matches!(
+            result,
+            Err(CoinPaymentsError::InvalidParameters(_))
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_middleware_can_short_circuit_with_response() {
+        let transport = Arc::new(MockTransport::new());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport.clone())
+            .with_middleware(Arc::new(Recorder {
+                name: "outer",
+                log: log.clone(),
+            }))
+            .with_middleware(Arc::new(CannedPong))
+            .with_middleware(Arc::new(Recorder {
+                name: "skipped",
+                log: log.clone(),
+            }));
+
+        assert_eq!(client.ping().await.unwrap().message, "pong");
+        assert!(transport.requests().is_empty());
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["outer before v1/ping", "outer after 200"]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Per-call options for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Overriding the timeout, retry policy and idempotency key of a single call
+//! - Sending extra headers with a single call
+//! - Cancelling a call in flight via a [`CancellationToken`]
+
+use crate::retry::IDEMPOTENCY_KEY_HEADER;
+use crate::{CoinPaymentsError, Result, RetryPolicy};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use std::future::Future;
+use std::time::Duration;
+
+pub use tokio_util::sync::CancellationToken;
+
+tokio::task_local! {
+    static OPTIONS: RequestOptions;
+}
+
+/// Signing headers the client sets on every request
+const SIGNING_HEADERS: [&str; 3] = [
+    "X-CoinPayments-Client",
+    "X-CoinPayments-Timestamp",
+    "X-CoinPayments-Signature",
+];
+
+// === Request Options ===
+
+/// Options applied to every HTTP request made by a single API call
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, RequestOptions, RequestOptionsExt, RetryPolicy};
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+/// use coinpayments::{RequestOptions, RequestOptionsExt};
+///
+/// let options = RequestOptions::new()
+///     .with_timeout(Duration::from_millis(500))
+///     .with_retry_policy(RetryPolicy::none());
+/// let rate = client.get_rate("BTC", "USD").with_options(options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Deadline for the whole call, including retries (also overrides the per-request timeout)
+    pub timeout: Option<Duration>,
+    /// Extra headers sent with every attempt
+    ///
+    /// Headers the client sets itself (content type, idempotency key and authentication)
+    /// always replace entries with the same name.
+    pub headers: HeaderMap,
+    /// Idempotency key sent instead of a generated one
+    pub idempotency_key: Option<String>,
+    /// Retry policy used instead of the client's
+    pub retry_policy: Option<RetryPolicy>,
+    /// Token cancelling the call when triggered
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl RequestOptions {
+    /// Create options that change nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail the call with [`CoinPaymentsError::Timeout`] if it takes longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send an extra header
+    ///
+    /// Fails if the name or value is not a valid header, or if the client sets the header
+    /// itself (`Content-Type`, `Idempotency-Key`, `Authorization` and the signing headers).
+    /// Use [`RequestOptions::with_idempotency_key`] to choose the idempotency key.
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            CoinPaymentsError::InvalidParameters(format!("Invalid header name: {}", name))
+        })?;
+        let reserved = [CONTENT_TYPE, AUTHORIZATION].contains(&name)
+            || name.as_str().eq_ignore_ascii_case(IDEMPOTENCY_KEY_HEADER)
+            || SIGNING_HEADERS
+                .iter()
+                .any(|header| name.as_str().eq_ignore_ascii_case(header));
+        if reserved {
+            return Err(CoinPaymentsError::InvalidParameters(format!(
+                "Header {} is set by the client",
+                name
+            )));
+        }
+        let value = HeaderValue::from_str(value).map_err(|_| {
+            CoinPaymentsError::InvalidParameters(format!("Invalid value for header {}", name))
+        })?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Send the call with the given idempotency key instead of a generated one
+    ///
+    /// [`crate::IdempotencyKeyExt::with_idempotency_key`] sets the same option.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Retry the call according to `retry_policy` instead of the client's policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Fail the call with [`CoinPaymentsError::Cancelled`] once `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+}
+
+/// Extension for endpoint futures that applies [`RequestOptions`] to the call
+pub trait RequestOptionsExt<T>: Future<Output = Result<T>> + Sized {
+    /// Run the call with the given options
+    fn with_options(self, options: RequestOptions) -> impl Future<Output = Result<T>>;
+}
+
+impl<F, T> RequestOptionsExt<T> for F
+where
+    F: Future<Output = Result<T>>,
+{
+    async fn with_options(self, options: RequestOptions) -> Result<T> {
+        OPTIONS.scope(options, self).await
+    }
+}
+
+/// Options of the current call, if any were given
+pub(crate) fn current() -> Option<RequestOptions> {
+    OPTIONS.try_with(Clone::clone).ok()
+}
+
+/// Run a call, enforcing the timeout and cancellation of `options`
+pub(crate) async fn apply<T>(
+    options: &RequestOptions,
+    call: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let call = async {
+        match options.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .unwrap_or(Err(CoinPaymentsError::Timeout)),
+            None => call.await,
+        }
+    };
+
+    match &options.cancellation {
+        Some(token) => token
+            .run_until_cancelled(call)
+            .await
+            .unwrap_or(Err(CoinPaymentsError::Cancelled)),
+        None => call.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_options_are_scoped_to_call() {
+        assert!(current().is_none());
+
+        let options = RequestOptions::new()
+            .with_idempotency_key("key-1")
+            .with_header("X-Trace", "abc")
+            .unwrap();
+        let seen = async { Ok(current()) }
+            .with_options(options)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(seen.idempotency_key.as_deref(), Some("key-1"));
+        assert_eq!(seen.headers["X-Trace"], "abc");
+        assert!(current().is_none());
+        assert!(RequestOptions::new().with_header("bad name", "x").is_err());
+    }
+
+    #[test]
+    fn test_client_headers_are_rejected() {
+        for name in [
+            "Content-Type",
+            "idempotency-key",
+            "Authorization",
+            "X-CoinPayments-Signature",
+        ] {
+            assert!(
+                RequestOptions::new().with_header(name, "x").is_err(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_cancellation() {
+        let slow = || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        };
+
+        let options = RequestOptions::new().with_timeout(Duration::from_millis(10));
+        assert!(matches!(
+            apply(&options, slow()).await,
+            Err(CoinPaymentsError::Timeout)
+        ));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let options = RequestOptions::new().with_cancellation(token);
+        assert!(matches!(
+            apply(&options, slow()).await,
+            Err(CoinPaymentsError::Cancelled)
+        ));
+
+        assert!(apply(&RequestOptions::new(), async { Ok(()) })
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,284 @@
+//! Client-side rate limiting for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Token buckets seeded from the per-minute/hour/day limits in `ClientInfo`
+//! - Pausing requests when `X-RateLimit-*` headers report an exhausted quota
+//! - Sharing limiter state across clones of a client so concurrent tasks queue
+
+use crate::utils::{self, RateLimitInfo};
+use crate::RateLimits;
+use reqwest::header::HeaderMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// === Rate Limiter Types ===
+
+/// Token-bucket rate limiter shared by all clones of a client
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    state: Arc<Mutex<LimiterState>>,
+    // Held while waiting for a token so callers are served in FIFO order
+    queue: Arc<tokio::sync::Mutex<()>>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    buckets: Vec<TokenBucket>,
+    info: Option<RateLimitInfo>,
+    paused_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    period_secs: u64,
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter without configured limits (only response headers are honoured)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a limiter seeded with the given limits
+    pub fn with_limits(limits: &RateLimits) -> Self {
+        let limiter = Self::new();
+        limiter.seed(limits);
+        limiter
+    }
+
+    /// Replace the configured limits
+    ///
+    /// Buckets for newly reported limits start full. Buckets that already existed keep
+    /// their current tokens (clamped to a lowered limit), so re-seeding with the same
+    /// limits, e.g. on every `get_client_info`, does not refill them.
+    pub fn seed(&self, limits: &RateLimits) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let buckets = [
+            (limits.requests_per_minute, 60),
+            (limits.requests_per_hour, 60 * 60),
+            (limits.requests_per_day, 24 * 60 * 60),
+        ]
+        .into_iter()
+        .filter(|(limit, _)| *limit > 0) // zero means "no limit reported"
+        .map(|(limit, period_secs)| {
+            let mut bucket = TokenBucket::new(limit, period_secs, now);
+            if let Some(current) = state
+                .buckets
+                .iter_mut()
+                .find(|current| current.period_secs == period_secs)
+            {
+                current.refill(now);
+                bucket.tokens = current.tokens.min(bucket.capacity);
+            }
+            bucket
+        })
+        .collect();
+
+        state.buckets = buckets;
+    }
+
+    /// Latest rate limit information reported by the API
+    pub fn info(&self) -> Option<RateLimitInfo> {
+        self.state.lock().unwrap().info.clone()
+    }
+
+    /// Wait until a request may be sent
+    pub async fn acquire(&self) {
+        let _turn = self.queue.lock().await;
+
+        loop {
+            let wait = self.state.lock().unwrap().try_acquire(Instant::now());
+            match wait {
+                None => return,
+                Some(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Update the limiter from `X-RateLimit-*` response headers
+    pub fn update_from_headers(&self, headers: &HeaderMap) {
+        let Some(info) = utils::extract_rate_limit_info(headers) else {
+            return;
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if info.calls_left == 0 {
+            if let Some(delay) = crate::retry::parse_rate_limit_reset(headers) {
+                state.pause_for(delay, Instant::now());
+            }
+        }
+        state.info = Some(info);
+    }
+
+    /// Hold back all requests for the given duration (e.g. after a 429 response)
+    pub fn pause_for(&self, delay: Duration) {
+        self.state.lock().unwrap().pause_for(delay, Instant::now());
+    }
+}
+
+impl LimiterState {
+    /// Take a token from every bucket, or return how long to wait for one
+    fn try_acquire(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if paused_until > now {
+                return Some(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+
+        for bucket in &mut self.buckets {
+            bucket.refill(now);
+        }
+
+        let wait = self
+            .buckets
+            .iter()
+            .map(TokenBucket::time_to_token)
+            .max()
+            .unwrap_or(Duration::ZERO);
+
+        if wait.is_zero() {
+            for bucket in &mut self.buckets {
+                bucket.tokens -= 1.0;
+            }
+            None
+        } else {
+            Some(wait)
+        }
+    }
+
+    fn pause_for(&mut self, delay: Duration, now: Instant) {
+        let until = now + delay;
+        if self.paused_until.is_none_or(|current| current < until) {
+            self.paused_until = Some(until);
+        }
+    }
+}
+
+impl TokenBucket {
+    fn new(limit: u32, period_secs: u64, now: Instant) -> Self {
+        Self {
+            period_secs,
+            capacity: limit as f64,
+            tokens: limit as f64,
+            refill_per_sec: limit as f64 / period_secs as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    fn time_to_token(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn limits(per_minute: u32, per_hour: u32, per_day: u32) -> RateLimits {
+        RateLimits {
+            requests_per_minute: per_minute,
+            requests_per_hour: per_hour,
+            requests_per_day: per_day,
+        }
+    }
+
+    #[test]
+    fn test_bucket_exhaustion_and_refill() {
+        let limiter = RateLimiter::with_limits(&limits(2, 0, 0));
+        let mut state = limiter.state.lock().unwrap();
+        let start = state.buckets[0].last_refill;
+
+        assert_eq!(state.try_acquire(start), None);
+        assert_eq!(state.try_acquire(start), None);
+
+        let wait = state.try_acquire(start).unwrap();
+        assert_eq!(wait.as_secs_f64().round(), 30.0); // 2 per minute
+
+        assert_eq!(state.try_acquire(start + Duration::from_secs(31)), None);
+    }
+
+    #[test]
+    fn test_strictest_bucket_wins() {
+        let limiter = RateLimiter::with_limits(&limits(100, 1, 0));
+        let mut state = limiter.state.lock().unwrap();
+        let start = state.buckets[0].last_refill;
+
+        assert_eq!(state.try_acquire(start), None);
+        let wait = state.try_acquire(start).unwrap();
+        assert_eq!(wait.as_secs_f64().round(), 3600.0);
+    }
+
+    #[test]
+    fn test_update_from_headers_pauses_when_exhausted() {
+        let limiter = RateLimiter::new();
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", HeaderValue::from_static("60"));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("0"));
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("20"));
+
+        limiter.update_from_headers(&headers);
+
+        let info = limiter.info().unwrap();
+        assert_eq!(info.calls_made, 60);
+        assert_eq!(info.calls_left, 0);
+
+        let wait = limiter
+            .state
+            .lock()
+            .unwrap()
+            .try_acquire(Instant::now())
+            .unwrap();
+        assert!(wait > Duration::from_secs(19) && wait <= Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_reseeding_keeps_current_tokens() {
+        let limiter = RateLimiter::with_limits(&limits(10, 0, 0));
+        for _ in 0..8 {
+            assert_eq!(
+                limiter.state.lock().unwrap().try_acquire(Instant::now()),
+                None
+            );
+        }
+
+        // Unchanged limits keep the remaining tokens
+        limiter.seed(&limits(10, 0, 0));
+        let tokens = limiter.state.lock().unwrap().buckets[0].tokens;
+        assert!((2.0..2.1).contains(&tokens));
+
+        // A lowered limit clamps them, a new limit starts full
+        limiter.seed(&limits(1, 100, 0));
+        let state = limiter.state.lock().unwrap();
+        assert!((1.0..1.1).contains(&state.buckets[0].tokens));
+        assert_eq!(state.buckets[1].tokens, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_state() {
+        let limiter = RateLimiter::with_limits(&limits(1, 0, 0));
+        let clone = limiter.clone();
+
+        limiter.acquire().await;
+        let wait = clone.state.lock().unwrap().try_acquire(Instant::now());
+        assert!(wait.is_some());
+    }
+}
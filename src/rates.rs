@@ -5,7 +5,9 @@
 //! - Real-time rate information
 //! - Rate filtering and querying
 
-use crate::{CoinPaymentsClient, Result};
+use crate::endpoint::Endpoint;
+use crate::{CachedEndpoint, CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,6 +15,7 @@ use std::collections::HashMap;
 
 /// Exchange rate information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ExchangeRate {
     pub from_currency_id: String,
     pub to_currency_id: String,
@@ -27,6 +30,7 @@ pub struct ExchangeRate {
 
 /// Response for getting exchange rates
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetRatesResponse {
     pub rates: Vec<ExchangeRate>,
     pub base_currency: Option<String>,
@@ -36,6 +40,7 @@ pub struct GetRatesResponse {
 
 /// Pagination information for rates
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RatePaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -123,16 +128,45 @@ impl RateQuery {
             params.push(("page", page.to_string()));
         }
         if let Some(per_page) = self.per_page {
-            params.push(("per_page", per_page.to_string()));
+            params.push(("perPage", per_page.to_string()));
         }
         if let Some(include_inactive) = self.include_inactive {
-            params.push(("include_inactive", include_inactive.to_string()));
+            params.push(("includeInactive", include_inactive.to_string()));
         }
 
         params
     }
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::get_rates`]
+#[derive(Debug, Clone, Default)]
+pub struct GetRates {
+    /// Rate filters (`None` sends no query parameters)
+    pub query: Option<RateQuery>,
+}
+
+impl Endpoint for GetRates {
+    type Request = ();
+    type Response = GetRatesResponse;
+
+    const NAME: &'static str = "get_rates";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v2/rates";
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        self.query
+            .as_ref()
+            .map(RateQuery::to_query_params)
+            .unwrap_or_default()
+    }
+
+    fn cache(&self) -> Option<CachedEndpoint> {
+        Some(CachedEndpoint::Rates)
+    }
+}
+
 impl CoinPaymentsClient {
     /// Get current conversion rates between currencies
     ///
@@ -140,7 +174,9 @@ impl CoinPaymentsClient {
     /// * `query` - Optional query parameters to filter rates
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, RateQuery};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     ///
     /// // Get all rates
@@ -157,14 +193,12 @@ impl CoinPaymentsClient {
     ///         .from_currency("4")  // Bitcoin
     ///         .to_currency("61")   // Ethereum
     /// )).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_rates(&self, query: Option<RateQuery>) -> Result<GetRatesResponse> {
-        let query_params = match &query {
-            Some(q) => q.to_query_params(),
-            None => Vec::new(),
-        };
-
-        self.get_request("v2/rates", &query_params).await
+        self.execute(GetRates { query }).await
     }
 
     /// Get rate for a specific currency pair
@@ -174,10 +208,24 @@ impl CoinPaymentsClient {
     /// * `to_currency` - Target currency ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let rate = client.get_rate("4", "61").await?; // BTC to ETH
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                from_currency = %from_currency,
+                to_currency = %to_currency
+            )
+        )
+    )]
     pub async fn get_rate(&self, from_currency: &str, to_currency: &str) -> Result<ExchangeRate> {
         let query = RateQuery::new()
             .from_currency(from_currency)
@@ -203,7 +251,9 @@ impl CoinPaymentsClient {
     /// * `as_base` - If true, get rates from this currency to others; if false, get rates to this currency
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     ///
     /// // Get rates from Bitcoin to all other currencies
@@ -211,7 +261,13 @@ impl CoinPaymentsClient {
     ///
     /// // Get rates from all currencies to Bitcoin
     /// let to_btc_rates = client.get_currency_rates("4", false).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = %currency_id))
+    )]
     pub async fn get_currency_rates(
         &self,
         currency_id: &str,
@@ -233,11 +289,16 @@ impl CoinPaymentsClient {
     /// * `currency_ids` - List of currency IDs to get rates for
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let currencies = vec!["4".to_string(), "61".to_string(), "3".to_string()]; // BTC, ETH, LTC
     /// let rates = client.get_multiple_currency_rates(currencies).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_multiple_currency_rates(
         &self,
         currency_ids: Vec<String>,
@@ -378,8 +439,18 @@ mod tests {
         assert!(params.contains(&("from", "4".to_string())));
         assert!(params.contains(&("to", "61".to_string())));
         assert!(params.contains(&("page", "1".to_string())));
-        assert!(params.contains(&("per_page", "10".to_string())));
-        assert!(params.contains(&("include_inactive", "true".to_string())));
+        assert!(params.contains(&("perPage", "10".to_string())));
+        assert!(params.contains(&("includeInactive", "true".to_string())));
+    }
+
+    #[test]
+    fn test_get_rates_without_query_sends_no_parameters() {
+        assert!(GetRates::default().query().is_empty());
+        assert!(!GetRates {
+            query: Some(RateQuery::new())
+        }
+        .query()
+        .is_empty());
     }
 
     #[test]
@@ -0,0 +1,254 @@
+//! Raw requests for CoinPayments API endpoints without a typed wrapper
+//!
+//! This module provides functionality for:
+//! - Signing and sending arbitrary method/path/query/body combinations
+//! - Decoding responses into any `DeserializeOwned` type or a raw JSON value
+//! - Reusing the client's retries, rate limiting, middleware and error mapping
+
+use crate::idempotency::{self, IdempotencyKey};
+use crate::{CoinPaymentsClient, ResponseMeta, ResponseMetaExt, Result};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// === Raw Requests ===
+
+/// Entry point for raw requests, returned by [`CoinPaymentsClient::raw`]
+#[derive(Debug, Clone, Copy)]
+pub struct RawClient<'a> {
+    client: &'a CoinPaymentsClient,
+}
+
+impl<'a> RawClient<'a> {
+    /// Start a request with any HTTP method
+    ///
+    /// # Arguments
+    /// * `method` - HTTP method
+    /// * `path` - Endpoint path relative to the base URL (e.g. `v2/merchant/wallets`)
+    pub fn request(self, method: Method, path: &str) -> RawRequest<'a> {
+        RawRequest {
+            client: self.client,
+            method,
+            path: path.trim_start_matches('/').to_string(),
+            query: Vec::new(),
+            body: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Start a GET request
+    pub fn get(self, path: &str) -> RawRequest<'a> {
+        self.request(Method::GET, path)
+    }
+
+    /// Start a POST request
+    pub fn post(self, path: &str) -> RawRequest<'a> {
+        self.request(Method::POST, path)
+    }
+
+    /// Start a PUT request
+    pub fn put(self, path: &str) -> RawRequest<'a> {
+        self.request(Method::PUT, path)
+    }
+
+    /// Start a DELETE request
+    pub fn delete(self, path: &str) -> RawRequest<'a> {
+        self.request(Method::DELETE, path)
+    }
+}
+
+/// A raw request being built
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::CoinPaymentsClient;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+/// let (balances, meta) = client
+///     .raw()
+///     .get("v3/merchant/wallets/balances")
+///     .with_query("currency", "BTC")
+///     .send_value()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawRequest<'a> {
+    client: &'a CoinPaymentsClient,
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Option<std::result::Result<String, String>>,
+    idempotency_key: Option<String>,
+}
+
+impl RawRequest<'_> {
+    /// Add a query parameter
+    pub fn with_query(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.query.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Send `body` serialized as JSON
+    ///
+    /// Serialization errors are returned when the request is sent.
+    pub fn with_json<B: Serialize>(mut self, body: &B) -> Self {
+        self.body = Some(serde_json::to_string(body).map_err(|error| error.to_string()));
+        self
+    }
+
+    /// Send the request with an idempotency key, making it safe to retry
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Send the request and decode the response into `T`
+    ///
+    /// Responses wrapped in `{"data": ...}` are unwrapped, error responses are mapped to
+    /// [`crate::CoinPaymentsError`] like for typed endpoints, and an empty body decodes
+    /// as JSON `null` (e.g. into `()`).
+    pub async fn send<T: DeserializeOwned>(self) -> Result<T> {
+        let body = match self.body {
+            Some(Ok(body)) => Some(body),
+            Some(Err(message)) => {
+                return Err(crate::CoinPaymentsError::InvalidParameters(format!(
+                    "Cannot serialize request body: {}",
+                    message
+                )))
+            }
+            None => None,
+        };
+        let idempotency_key = self
+            .idempotency_key
+            .or_else(idempotency::caller_key)
+            .map(IdempotencyKey::Caller);
+        let query: Vec<(&str, String)> = self
+            .query
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect();
+
+        self.client
+            .send_request(self.method, &self.path, &query, body, idempotency_key)
+            .await
+    }
+
+    /// Send the request and return the JSON response with its metadata
+    pub async fn send_value(self) -> Result<(serde_json::Value, ResponseMeta)> {
+        self.send::<serde_json::Value>().with_meta().await
+    }
+}
+
+impl CoinPaymentsClient {
+    /// Send requests to endpoints that have no typed wrapper yet
+    ///
+    /// Raw requests are signed and sent like every other call: retries, rate limiting,
+    /// middleware, per-call options and error mapping all apply.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = CoinPaymentsClient::new("client_id", "client_secret");
+    /// #[derive(Deserialize)]
+    /// struct Payout { id: String, status: String }
+    ///
+    /// let payout: Payout = client
+    ///     .raw()
+    ///     .post("v2/merchant/payouts")
+    ///     .with_json(&json!({"amount": "10.00", "currency": "USDT"}))
+    ///     .with_idempotency_key("payout-42")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn raw(&self) -> RawClient<'_> {
+        RawClient { client: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CoinPaymentsError, MockTransport, RetryPolicy};
+    use std::sync::Arc;
+
+    fn client(transport: Arc<MockTransport>) -> CoinPaymentsClient {
+        CoinPaymentsClient::new("test_client", "test_secret")
+            .with_transport(transport)
+            .with_base_url("https://api.example.com")
+            .with_retry_policy(RetryPolicy::none())
+    }
+
+    #[tokio::test]
+    async fn test_raw_get_is_signed_and_typed() {
+        #[derive(serde::Deserialize)]
+        struct Balance {
+            amount: String,
+        }
+
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, r#"{"data":{"amount":"1.5"}}"#);
+        let balance: Balance = client(transport.clone())
+            .raw()
+            .get("/v3/balances")
+            .with_query("currency", "BTC")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(balance.amount, "1.5");
+        let request = &transport.requests()[0];
+        assert_eq!(
+            request.url,
+            "https://api.example.com/v3/balances?currency=BTC"
+        );
+        assert!(request.headers.contains_key("X-CoinPayments-Signature"));
+    }
+
+    #[tokio::test]
+    async fn test_raw_value_with_meta_and_body() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(
+            crate::HttpResponse::new(reqwest::StatusCode::CREATED, r#"{"id":"p1"}"#)
+                .with_header("X-Request-Id", "req-7"),
+        );
+        let (value, meta) = client(transport.clone())
+            .raw()
+            .post("v2/payouts")
+            .with_json(&serde_json::json!({"amount": "10"}))
+            .with_idempotency_key("payout-1")
+            .send_value()
+            .await
+            .unwrap();
+
+        assert_eq!(value["id"], "p1");
+        assert_eq!(meta.request_id.as_deref(), Some("req-7"));
+        assert_eq!(meta.idempotency_key.as_deref(), Some("payout-1"));
+        assert_eq!(transport.requests()[0].body_text(), r#"{"amount":"10"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_raw_errors_and_empty_bodies() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(204, "");
+        transport.push_json(404, r#"{"message":"not here"}"#);
+        let client = client(transport);
+
+        client
+            .raw()
+            .delete("v2/things/1")
+            .send::<()>()
+            .await
+            .unwrap();
+        assert!(matches!(
+            client.raw().get("v2/things/2").send_value().await,
+            Err(CoinPaymentsError::NotFound(_))
+        ));
+    }
+}
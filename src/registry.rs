@@ -0,0 +1,546 @@
+//! Multi-tenant client registry for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - One client per tenant (merchant account), created on first use
+//! - Loading tenant credentials lazily through a [`TenantCredentials`] provider
+//! - Sharing one HTTP connection pool while keeping per-tenant rate limiters
+//! - Evicting idle tenants and tracking per-tenant health
+
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+use crate::{AuthType, CoinPaymentsClient, Middleware, RateLimits, Result, RetryPolicy};
+use reqwest::{Client, StatusCode};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A tenant is reported unhealthy after this many consecutive failed responses
+pub const UNHEALTHY_AFTER_FAILURES: u32 = 3;
+
+/// Future returned by [`TenantCredentials::load`]
+pub type CredentialsFuture<'a> = Pin<Box<dyn Future<Output = Result<AuthType>> + Send + 'a>>;
+
+// === Credential Providers ===
+
+/// Source of the credentials of each tenant
+///
+/// Implemented for closures taking the tenant id, so a database or secret store
+/// lookup can be plugged in without a dedicated type.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{AuthType, ClientRegistry, Credentials, Result};
+/// # use std::sync::Arc;
+/// # fn example() {
+/// # struct Merchant { coinpayments_client_id: String, coinpayments_client_secret: String }
+/// # struct Db;
+/// # impl Db {
+/// #     async fn merchant(&self, _id: &str) -> Result<Merchant> { unimplemented!() }
+/// # }
+/// # static db: Db = Db;
+/// let provider = Arc::new(|tenant_id: String| async move {
+///     let merchant = db.merchant(&tenant_id).await?;
+///     Ok(AuthType::ClientCredentials(Credentials::new(
+///         merchant.coinpayments_client_id,
+///         merchant.coinpayments_client_secret,
+///     )))
+/// });
+/// let registry = ClientRegistry::new(provider);
+/// # }
+/// ```
+pub trait TenantCredentials: Send + Sync {
+    /// Load the credentials of a tenant
+    fn load(&self, tenant_id: &str) -> CredentialsFuture<'_>;
+}
+
+impl<F, Fut> TenantCredentials for F
+where
+    F: Fn(String) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<AuthType>> + Send + 'static,
+{
+    fn load(&self, tenant_id: &str) -> CredentialsFuture<'_> {
+        Box::pin(self(tenant_id.to_string()))
+    }
+}
+
+// === Tenant Health ===
+
+/// Outcome of the recent requests of a tenant
+#[derive(Debug, Clone, Default)]
+pub struct TenantHealth {
+    /// Responses received
+    pub requests: u64,
+    /// Responses with an authentication, rate limit or server error status
+    pub failures: u64,
+    /// Failed responses since the last successful one
+    pub consecutive_failures: u32,
+    /// Status of the latest response
+    pub last_status: Option<StatusCode>,
+    /// When the latest failed response was received
+    pub last_failure: Option<Instant>,
+}
+
+impl TenantHealth {
+    /// Check whether the tenant's recent requests are succeeding
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures < UNHEALTHY_AFTER_FAILURES
+    }
+
+    fn record(&mut self, status: StatusCode) {
+        self.requests += 1;
+        self.last_status = Some(status);
+
+        let failed = matches!(
+            status,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+        ) || status.is_server_error();
+        if failed {
+            self.failures += 1;
+            self.consecutive_failures += 1;
+            self.last_failure = Some(Instant::now());
+        } else {
+            self.consecutive_failures = 0;
+        }
+    }
+}
+
+/// Middleware recording every response of a tenant client
+#[derive(Debug)]
+struct HealthRecorder(Arc<Mutex<TenantHealth>>);
+
+impl Middleware for HealthRecorder {
+    fn after(&self, _endpoint: &str, _request: &HttpRequest, response: &mut HttpResponse) {
+        self.0.lock().unwrap().record(response.status);
+    }
+}
+
+// === Client Registry ===
+
+/// Clients for many tenants sharing one connection pool
+///
+/// Clients are created on first use from the credentials returned by the provider,
+/// and each gets its own rate limiter. Clones of the registry share the same tenants.
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{AuthType, ClientRegistry, CreateInvoiceRequest};
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let provider = Arc::new(|_tenant_id: String| async {
+/// #     Ok(AuthType::OAuth("token".to_string().into()))
+/// # });
+/// # let merchant_id = "merchant-1";
+/// # let request = CreateInvoiceRequest::new("10.00", "USD", "Order 1");
+/// let registry = ClientRegistry::new(provider)
+///     .with_idle_timeout(Duration::from_secs(30 * 60))
+///     .with_max_tenants(500);
+///
+/// let invoice = registry
+///     .client(&merchant_id)
+///     .await?
+///     .create_invoice(request)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ClientRegistry {
+    provider: Arc<dyn TenantCredentials>,
+    http_client: Client,
+    transport: Option<Arc<dyn Transport>>,
+    base_url: Option<String>,
+    retry_policy: RetryPolicy,
+    rate_limits: Option<RateLimits>,
+    idle_timeout: Option<Duration>,
+    max_tenants: Option<usize>,
+    tenants: Arc<Mutex<HashMap<String, Arc<TenantSlot>>>>,
+}
+
+struct TenantSlot {
+    // Initialized once; concurrent first calls share a single credential load
+    client: tokio::sync::OnceCell<CoinPaymentsClient>,
+    health: Arc<Mutex<TenantHealth>>,
+    last_used: Mutex<Instant>,
+}
+
+impl TenantSlot {
+    fn new() -> Self {
+        Self {
+            client: tokio::sync::OnceCell::new(),
+            health: Arc::new(Mutex::new(TenantHealth::default())),
+            last_used: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn last_used(&self) -> Instant {
+        *self.last_used.lock().unwrap()
+    }
+}
+
+impl std::fmt::Debug for ClientRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientRegistry")
+            .field("base_url", &self.base_url)
+            .field("retry_policy", &self.retry_policy)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_tenants", &self.max_tenants)
+            .field("tenants", &self.len())
+            .finish()
+    }
+}
+
+impl ClientRegistry {
+    /// Create a registry loading tenant credentials from `provider`
+    ///
+    /// # Arguments
+    /// * `provider` - Credentials lookup by tenant id
+    pub fn new(provider: Arc<dyn TenantCredentials>) -> Self {
+        Self {
+            provider,
+            http_client: crate::utils::create_http_client().unwrap_or_else(|_| Client::new()),
+            transport: None,
+            base_url: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limits: None,
+            idle_timeout: None,
+            max_tenants: None,
+            tenants: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Share a custom reqwest client (and its connection pool) between all tenants
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.http_client = client;
+        self
+    }
+
+    /// Send the requests of all tenants through a custom transport
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Set a custom base URL for all tenants
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set the retry policy of all tenants
+    ///
+    /// Every tenant gets its own copy of the retry budget, so one failing tenant
+    /// cannot use up the retries of the others.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Seed the rate limiter of every new tenant with known limits
+    pub fn with_rate_limits(mut self, limits: RateLimits) -> Self {
+        self.rate_limits = Some(limits);
+        self
+    }
+
+    /// Drop tenants that were not used for `idle_timeout`
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Keep at most `max_tenants` clients, dropping the least recently used ones
+    pub fn with_max_tenants(mut self, max_tenants: usize) -> Self {
+        self.max_tenants = Some(max_tenants.max(1));
+        self
+    }
+
+    /// Get the client of a tenant, loading its credentials on first use
+    ///
+    /// # Arguments
+    /// * `tenant_id` - Tenant (merchant) identifier passed to the credentials provider
+    pub async fn client(&self, tenant_id: &str) -> Result<CoinPaymentsClient> {
+        let slot = self.slot(tenant_id);
+        let client = slot
+            .client
+            .get_or_try_init(|| async {
+                let auth = self.provider.load(tenant_id).await?;
+                Ok(self.build_client(auth, &slot.health))
+            })
+            .await
+            .cloned();
+
+        let mut tenants = self.tenants.lock().unwrap();
+        if client.is_ok() {
+            self.evict_least_recently_used(&mut tenants, tenant_id);
+        } else if tenants
+            .get(tenant_id)
+            .is_some_and(|current| Arc::ptr_eq(current, &slot) && current.client.get().is_none())
+        {
+            // A tenant whose credentials failed to load must not hold a slot
+            tenants.remove(tenant_id);
+        }
+        client
+    }
+
+    /// Drop the client of a tenant (e.g. after its credentials were rotated)
+    ///
+    /// Returns `true` if the tenant was loaded.
+    pub fn evict(&self, tenant_id: &str) -> bool {
+        self.tenants.lock().unwrap().remove(tenant_id).is_some()
+    }
+
+    /// Drop all tenants idle for longer than the idle timeout, returning how many were dropped
+    pub fn evict_idle(&self) -> usize {
+        let mut tenants = self.tenants.lock().unwrap();
+        let before = tenants.len();
+        self.evict_expired(&mut tenants, Instant::now());
+        before - tenants.len()
+    }
+
+    /// Health of a tenant's recent requests, if the tenant is loaded
+    pub fn health(&self, tenant_id: &str) -> Option<TenantHealth> {
+        self.tenants
+            .lock()
+            .unwrap()
+            .get(tenant_id)
+            .map(|slot| slot.health.lock().unwrap().clone())
+    }
+
+    /// Ids of the loaded tenants
+    pub fn tenant_ids(&self) -> Vec<String> {
+        self.tenants.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Number of loaded tenants
+    pub fn len(&self) -> usize {
+        self.tenants.lock().unwrap().len()
+    }
+
+    /// Check whether no tenant is loaded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get or create the slot of a tenant, dropping idle tenants
+    fn slot(&self, tenant_id: &str) -> Arc<TenantSlot> {
+        let now = Instant::now();
+        let mut tenants = self.tenants.lock().unwrap();
+        self.evict_expired(&mut tenants, now);
+
+        let slot = tenants
+            .entry(tenant_id.to_string())
+            .or_insert_with(|| Arc::new(TenantSlot::new()))
+            .clone();
+        *slot.last_used.lock().unwrap() = now;
+        slot
+    }
+
+    /// Drop the least recently used tenants other than `keep` beyond the tenant limit
+    ///
+    /// Applied once a client is loaded, so tenants that fail to load never evict others.
+    fn evict_least_recently_used(
+        &self,
+        tenants: &mut HashMap<String, Arc<TenantSlot>>,
+        keep: &str,
+    ) {
+        let Some(max_tenants) = self.max_tenants else {
+            return;
+        };
+        while tenants.len() > max_tenants {
+            let Some(oldest) = tenants
+                .iter()
+                .filter(|(id, _)| id.as_str() != keep)
+                .min_by_key(|(_, slot)| slot.last_used())
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            tenants.remove(&oldest);
+        }
+    }
+
+    fn evict_expired(&self, tenants: &mut HashMap<String, Arc<TenantSlot>>, now: Instant) {
+        if let Some(idle_timeout) = self.idle_timeout {
+            tenants.retain(|_, slot| now.duration_since(slot.last_used()) < idle_timeout);
+        }
+    }
+
+    /// Create a tenant client sharing the registry's connection pool
+    fn build_client(
+        &self,
+        auth: AuthType,
+        health: &Arc<Mutex<TenantHealth>>,
+    ) -> CoinPaymentsClient {
+        let mut client = CoinPaymentsClient::with_client_and_auth(self.http_client.clone(), auth)
+            .with_retry_policy(self.retry_policy.with_own_budget())
+            .with_middleware(Arc::new(HealthRecorder(health.clone())));
+        if let Some(base_url) = &self.base_url {
+            client = client.with_base_url(base_url.clone());
+        }
+        if let Some(transport) = &self.transport {
+            client = client.with_transport(transport.clone());
+        }
+        if let Some(limits) = &self.rate_limits {
+            client = client.with_rate_limits(limits);
+        }
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CoinPaymentsError, Credentials, MockTransport, RetryBudget};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const PONG: &str = r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#;
+
+    fn registry(loads: Arc<AtomicU32>) -> ClientRegistry {
+        let provider = move |tenant_id: String| {
+            loads.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if tenant_id == "unknown" {
+                    return Err(CoinPaymentsError::InvalidParameters(format!(
+                        "Unknown tenant {}",
+                        tenant_id
+                    )));
+                }
+                Ok(AuthType::ClientCredentials(Credentials::new(
+                    format!("{}-id", tenant_id),
+                    format!("{}-secret", tenant_id),
+                )))
+            }
+        };
+        ClientRegistry::new(Arc::new(provider)).with_retry_policy(RetryPolicy::none())
+    }
+
+    #[tokio::test]
+    async fn test_clients_are_loaded_once_per_tenant() {
+        let loads = Arc::new(AtomicU32::new(0));
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(200, PONG);
+        transport.push_json(200, PONG);
+        let registry = registry(loads.clone()).with_transport(transport.clone());
+
+        registry
+            .client("merchant-a")
+            .await
+            .unwrap()
+            .ping()
+            .await
+            .unwrap();
+        registry
+            .client("merchant-b")
+            .await
+            .unwrap()
+            .ping()
+            .await
+            .unwrap();
+        registry.client("merchant-a").await.unwrap();
+
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+        assert_eq!(registry.len(), 2);
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].headers["X-CoinPayments-Client"],
+            "merchant-a-id"
+        );
+        assert_eq!(
+            requests[1].headers["X-CoinPayments-Client"],
+            "merchant-b-id"
+        );
+
+        assert!(matches!(
+            registry.client("unknown").await,
+            Err(CoinPaymentsError::InvalidParameters(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tenants_have_separate_retry_budgets() {
+        let transport = Arc::new(MockTransport::new());
+        for _ in 0..3 {
+            transport.push_json(503, "{}");
+        }
+        transport.push_json(200, PONG);
+        let registry = registry(Arc::new(AtomicU32::new(0)))
+            .with_transport(transport.clone())
+            .with_retry_policy(
+                RetryPolicy::new()
+                    .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+                    .with_budget(Some(RetryBudget::new(0.0, 1))),
+            );
+
+        // The first tenant spends its only retry
+        let failing = registry.client("failing").await.unwrap();
+        assert!(failing.ping().await.is_err());
+        assert_eq!(transport.requests().len(), 2);
+
+        let healthy = registry.client("healthy").await.unwrap();
+        healthy.ping().await.unwrap();
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_failed_loads_do_not_take_tenant_slots() {
+        let registry = registry(Arc::new(AtomicU32::new(0))).with_max_tenants(1);
+
+        registry.client("merchant").await.unwrap();
+        for _ in 0..3 {
+            assert!(registry.client("unknown").await.is_err());
+        }
+
+        assert_eq!(registry.tenant_ids(), vec!["merchant"]);
+        assert!(registry.health("merchant").is_some());
+        assert!(registry.health("unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_eviction() {
+        let loads = Arc::new(AtomicU32::new(0));
+        let registry = registry(loads.clone()).with_max_tenants(2);
+
+        registry.client("a").await.unwrap();
+        registry.client("b").await.unwrap();
+        registry.client("a").await.unwrap();
+        registry.client("c").await.unwrap();
+
+        let mut ids = registry.tenant_ids();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "c"]);
+
+        assert!(registry.evict("a"));
+        assert!(!registry.evict("a"));
+        registry.client("a").await.unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 4);
+
+        let idle = registry.clone().with_idle_timeout(Duration::ZERO);
+        assert_eq!(idle.evict_idle(), 2);
+        assert!(registry.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_health() {
+        let transport = Arc::new(MockTransport::new());
+        for _ in 0..UNHEALTHY_AFTER_FAILURES {
+            transport.push_json(503, "{}");
+        }
+        transport.push_json(200, PONG);
+        let registry = registry(Arc::new(AtomicU32::new(0))).with_transport(transport);
+
+        let client = registry.client("merchant").await.unwrap();
+        for _ in 0..UNHEALTHY_AFTER_FAILURES {
+            assert!(client.ping().await.is_err());
+        }
+        let health = registry.health("merchant").unwrap();
+        assert_eq!(health.failures, u64::from(UNHEALTHY_AFTER_FAILURES));
+        assert!(!health.is_healthy());
+
+        client.ping().await.unwrap();
+        let health = registry.health("merchant").unwrap();
+        assert!(health.is_healthy());
+        assert_eq!(health.last_status, Some(StatusCode::OK));
+        assert!(registry.health("other").is_none());
+    }
+}
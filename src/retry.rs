@@ -0,0 +1,402 @@
+//! Retry policy for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - Retrying failed requests with exponential backoff and jitter
+//! - Honouring `Retry-After` and `X-RateLimit-Reset` response headers
+//! - Limiting retries with a shared retry budget
+//! - Deciding which requests are safe to retry
+
+use crate::CoinPaymentsError;
+use rand::Rng;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Header carrying an idempotency key; POST requests are only retried when the caller set it
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+// === Retry Types ===
+
+/// Retry policy applied to every API request made by a client
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on every further attempt
+    pub base_delay: Duration,
+    /// Upper bound for a single backoff delay (also caps server-requested delays)
+    pub max_delay: Duration,
+    /// Randomise backoff delays to avoid synchronised retries
+    pub jitter: bool,
+    /// Optional budget limiting retries relative to regular traffic
+    pub budget: Option<RetryBudget>,
+}
+
+/// Retry budget shared by all clones of a client
+///
+/// Every request deposits `ratio` tokens (up to `max_tokens`) and every retry
+/// withdraws one, so retries can never exceed a fixed share of the traffic.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    ratio: f64,
+    max_tokens: f64,
+    tokens: Arc<Mutex<f64>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            budget: Some(RetryBudget::default()),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create the default retry policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            budget: None,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of attempts (including the first one)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the base and maximum backoff delays
+    pub fn with_backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Enable or disable jitter
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the retry budget (`None` disables the budget)
+    pub fn with_budget(mut self, budget: Option<RetryBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Calculate the exponential backoff delay before retry number `attempt` (1-based)
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(exponent))
+            .min(self.max_delay);
+
+        if self.jitter && !delay.is_zero() {
+            // Equal jitter: keep half the delay, randomise the other half
+            let half = delay / 2;
+            half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+        } else {
+            delay
+        }
+    }
+
+    /// Delay to wait after a response, or `None` if the response should not be retried
+    pub(crate) fn delay_for_response(
+        &self,
+        attempt: u32,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Option<Duration> {
+        if !is_retryable_status(status) {
+            return None;
+        }
+
+        let delay = parse_retry_after(headers)
+            .or_else(|| parse_rate_limit_reset(headers))
+            .map(|delay| delay.min(self.max_delay))
+            .unwrap_or_else(|| self.backoff_delay(attempt));
+        Some(delay)
+    }
+
+    /// Delay to wait after a transport error, or `None` if it should not be retried
+    pub(crate) fn delay_for_error(
+        &self,
+        attempt: u32,
+        error: &CoinPaymentsError,
+    ) -> Option<Duration> {
+        is_retryable_transport_error(error).then(|| self.backoff_delay(attempt))
+    }
+
+    /// Check whether another attempt may be made after `attempt` attempts
+    pub(crate) fn can_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+            && self
+                .budget
+                .as_ref()
+                .is_none_or(|budget| budget.try_withdraw())
+    }
+
+    /// Copy of the policy with a separate, full retry budget
+    pub(crate) fn with_own_budget(&self) -> Self {
+        Self {
+            budget: self.budget.as_ref().map(RetryBudget::detached),
+            ..self.clone()
+        }
+    }
+
+    /// Record a new (non-retry) request against the budget
+    pub(crate) fn record_request(&self) {
+        if let Some(budget) = &self.budget {
+            budget.deposit();
+        }
+    }
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(0.2, 10)
+    }
+}
+
+impl RetryBudget {
+    /// Create a retry budget
+    ///
+    /// # Arguments
+    /// * `ratio` - Retries allowed per regular request (e.g. 0.2 = one retry per five requests)
+    /// * `min_retries` - Retries always available, even without prior traffic
+    pub fn new(ratio: f64, min_retries: u32) -> Self {
+        let max_tokens = (min_retries as f64).max(1.0);
+        Self {
+            ratio: ratio.max(0.0),
+            max_tokens,
+            tokens: Arc::new(Mutex::new(max_tokens)),
+        }
+    }
+
+    /// Retries currently available in the budget
+    pub fn available(&self) -> u32 {
+        *self.tokens.lock().unwrap() as u32
+    }
+
+    /// New, full budget with the same settings that is not shared with this one
+    fn detached(&self) -> Self {
+        Self {
+            ratio: self.ratio,
+            max_tokens: self.max_tokens,
+            tokens: Arc::new(Mutex::new(self.max_tokens)),
+        }
+    }
+
+    fn deposit(&self) {
+        let mut tokens = self.tokens.lock().unwrap();
+        *tokens = (*tokens + self.ratio).min(self.max_tokens);
+    }
+
+    fn try_withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap();
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// === Helper Functions ===
+
+/// Check if a response status is worth retrying
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 500 | 502 | 503 | 504)
+}
+
+/// Check if a transport error (timeout, failed connection or send) is worth retrying
+pub(crate) fn is_retryable_transport_error(error: &CoinPaymentsError) -> bool {
+    match error {
+        CoinPaymentsError::Http(error) => {
+            error.is_timeout()
+                || error.is_connect()
+                || error.is_request()
+                || error.status().is_some_and(is_retryable_status)
+        }
+        CoinPaymentsError::Network(_) => true,
+        _ => false,
+    }
+}
+
+/// Check if a request may be retried automatically
+///
+/// Idempotent methods are always retryable; POST requests only when they
+/// carry an [`IDEMPOTENCY_KEY_HEADER`]. The client does not count keys it
+/// generated itself, since those are only deduplicated within the process.
+pub fn is_retry_safe(method: &Method, headers: &HeaderMap) -> bool {
+    match *method {
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS => true,
+        _ => headers.contains_key(IDEMPOTENCY_KEY_HEADER),
+    }
+}
+
+/// Parse the `Retry-After` header (delta seconds or HTTP date)
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get("Retry-After")?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let now = chrono::Utc::now();
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Parse the `X-RateLimit-Reset` header (unix timestamp or delta seconds)
+pub fn parse_rate_limit_reset(headers: &HeaderMap) -> Option<Duration> {
+    let reset = headers
+        .get("X-RateLimit-Reset")?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    // Values before 2001 cannot be timestamps, so they are relative seconds
+    if reset < 1_000_000_000 {
+        Some(Duration::from_secs(reset))
+    } else {
+        Some(Duration::from_secs(reset.saturating_sub(now)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_delay_without_jitter() {
+        let policy = RetryPolicy::new()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(350))
+            .with_jitter(false);
+
+        assert_eq!(policy.backoff_delay(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_delay(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_delay(3), Duration::from_millis(350)); // capped
+    }
+
+    #[test]
+    fn test_backoff_delay_with_jitter() {
+        let policy =
+            RetryPolicy::new().with_backoff(Duration::from_secs(1), Duration::from_secs(10));
+
+        for _ in 0..20 {
+            let delay = policy.backoff_delay(2);
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn test_is_retry_safe() {
+        let mut headers = HeaderMap::new();
+        assert!(is_retry_safe(&Method::GET, &headers));
+        assert!(is_retry_safe(&Method::PUT, &headers));
+        assert!(!is_retry_safe(&Method::POST, &headers));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("key-123"));
+        assert!(is_retry_safe(&Method::POST, &headers));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Retry-After", HeaderValue::from_static("7"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            "Retry-After",
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert("Retry-After", HeaderValue::from_static("soon"));
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_parse_rate_limit_reset() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("30"));
+        assert_eq!(
+            parse_rate_limit_reset(&headers),
+            Some(Duration::from_secs(30))
+        );
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let reset = HeaderValue::from_str(&(now + 60).to_string()).unwrap();
+        headers.insert("X-RateLimit-Reset", reset);
+        let delay = parse_rate_limit_reset(&headers).unwrap();
+        assert!(delay <= Duration::from_secs(60) && delay >= Duration::from_secs(58));
+    }
+
+    #[test]
+    fn test_delay_for_response_honours_headers() {
+        let policy = RetryPolicy::new().with_jitter(false);
+        let mut headers = HeaderMap::new();
+
+        assert_eq!(
+            policy.delay_for_response(1, StatusCode::BAD_REQUEST, &headers),
+            None
+        );
+        assert_eq!(
+            policy.delay_for_response(1, StatusCode::SERVICE_UNAVAILABLE, &headers),
+            Some(Duration::from_millis(500))
+        );
+
+        headers.insert("Retry-After", HeaderValue::from_static("120"));
+        assert_eq!(
+            policy.delay_for_response(1, StatusCode::TOO_MANY_REQUESTS, &headers),
+            Some(Duration::from_secs(30)) // capped at max_delay
+        );
+    }
+
+    #[test]
+    fn test_retry_budget() {
+        let budget = RetryBudget::new(0.5, 1);
+        let policy = RetryPolicy::new()
+            .with_max_attempts(5)
+            .with_budget(Some(budget.clone()));
+
+        assert!(policy.can_retry(1));
+        assert!(!policy.can_retry(1)); // budget exhausted
+
+        policy.record_request();
+        policy.record_request();
+        assert_eq!(budget.available(), 1);
+        assert!(policy.can_retry(2));
+        assert!(!policy.can_retry(5)); // max attempts reached
+    }
+}
@@ -0,0 +1,217 @@
+//! Request signing for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - A pluggable [`Signer`] trait producing the `X-CoinPayments-*` authentication headers
+//! - The signing scheme documented for the CoinPayments v2 API (the default)
+//! - The legacy scheme previously used by this client
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Byte order mark prefixed to the signed message by the v2 scheme
+const BYTE_ORDER_MARK: &str = "\u{feff}";
+
+// === Signer Types ===
+
+/// Everything a signer may include in a request signature
+#[derive(Debug, Clone, Copy)]
+pub struct SigningInput<'a> {
+    /// Upper-case HTTP method
+    pub method: &'a str,
+    /// Absolute URL including the query string
+    pub url: &'a str,
+    /// Endpoint path relative to the base URL, without the query string
+    pub endpoint: &'a str,
+    /// Client ID sent in `X-CoinPayments-Client`
+    pub client_id: &'a str,
+    /// Timestamp sent in `X-CoinPayments-Timestamp`, as returned by [`Signer::timestamp`]
+    pub timestamp: &'a str,
+    /// Request body (empty for requests without a body)
+    pub body: &'a str,
+}
+
+/// Computes the `X-CoinPayments-Timestamp` and `X-CoinPayments-Signature` headers
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, LegacySigner};
+/// # use std::sync::Arc;
+/// # fn example() {
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_signer(Arc::new(LegacySigner));
+/// # }
+/// ```
+pub trait Signer: Send + Sync + std::fmt::Debug {
+    /// Format the (server clock corrected) request time for `X-CoinPayments-Timestamp`
+    fn timestamp(&self, now: DateTime<Utc>) -> String;
+
+    /// Sign a request with the client secret
+    fn sign(&self, client_secret: &str, input: &SigningInput<'_>) -> String;
+}
+
+// === CoinPayments v2 Signer ===
+
+/// Signing scheme documented for the CoinPayments v2 API
+///
+/// The signature is the base64 encoded HMAC-SHA256 of a byte order mark followed by
+/// the method, full URL (including the query string), client ID, timestamp and body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct V2Signer;
+
+impl Signer for V2Signer {
+    fn timestamp(&self, now: DateTime<Utc>) -> String {
+        now.format("%Y-%m-%dT%H:%M:%S").to_string()
+    }
+
+    fn sign(&self, client_secret: &str, input: &SigningInput<'_>) -> String {
+        let message = format!(
+            "{}{}{}{}{}{}",
+            BYTE_ORDER_MARK, input.method, input.url, input.client_id, input.timestamp, input.body
+        );
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(client_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(message.as_bytes());
+        base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+    }
+}
+
+// === Legacy Signer ===
+
+/// Signing scheme used by earlier versions of this client
+///
+/// The signature is the hex encoded HMAC-SHA512 of the client ID, timestamp, method,
+/// relative endpoint and body. The query string is not signed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LegacySigner;
+
+impl Signer for LegacySigner {
+    fn timestamp(&self, now: DateTime<Utc>) -> String {
+        now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+    }
+
+    fn sign(&self, client_secret: &str, input: &SigningInput<'_>) -> String {
+        let message = format!(
+            "{}{}{}{}{}",
+            input.client_id,
+            input.timestamp,
+            input.method.to_uppercase(),
+            input.endpoint,
+            input.body
+        );
+
+        crate::utils::generate_hmac_signature(client_secret, &message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(
+        method: &'a str,
+        url: &'a str,
+        timestamp: &'a str,
+        body: &'a str,
+    ) -> SigningInput<'a> {
+        let endpoint = url
+            .trim_start_matches("https://a-api.coinpayments.net/api/")
+            .split('?')
+            .next()
+            .unwrap();
+        SigningInput {
+            method,
+            url,
+            endpoint,
+            client_id: "test_client",
+            timestamp,
+            body,
+        }
+    }
+
+    #[test]
+    fn test_v2_known_answers() {
+        let signer = V2Signer;
+        assert_eq!(
+            signer.sign(
+                "test_secret",
+                &input(
+                    "GET",
+                    "https://a-api.coinpayments.net/api/v1/rates?from=BTC&to=USD",
+                    "2024-01-01T12:00:00",
+                    ""
+                )
+            ),
+            "p2uv9941uu+QJEouTyigKFVTpfwJXJ2n4ww4+hJ3A3E="
+        );
+        assert_eq!(
+            signer.sign(
+                "test_secret",
+                &input(
+                    "POST",
+                    "https://a-api.coinpayments.net/api/v2/merchant/invoices",
+                    "2024-01-01T12:00:00",
+                    r#"{"amount":"10.00"}"#
+                )
+            ),
+            "B6A8csdswf6J44vCi3tvGlpUd309awOJdIlIKJVQ4/A="
+        );
+    }
+
+    #[test]
+    fn test_v2_signs_query_string() {
+        let signer = V2Signer;
+        let url = "https://a-api.coinpayments.net/api/v1/rates";
+        let without_query = signer.sign("secret", &input("GET", url, "2024-01-01T12:00:00", ""));
+        let with_query = signer.sign(
+            "secret",
+            &input(
+                "GET",
+                &format!("{}?from=BTC", url),
+                "2024-01-01T12:00:00",
+                "",
+            ),
+        );
+        assert_ne!(without_query, with_query);
+    }
+
+    #[test]
+    fn test_legacy_known_answers() {
+        let signer = LegacySigner;
+        assert_eq!(
+            signer.sign(
+                "test_secret",
+                &input(
+                    "GET",
+                    "https://a-api.coinpayments.net/api/v1/ping",
+                    "2024-01-01T12:00:00.000Z",
+                    ""
+                )
+            ),
+            "a09ab4a374b9517f99640786b8cfc39aada185522b368898e297b58ed2f2f4a0a388440c79a320db0fa832c86193948a4adf7f6d8a89b38cf3862fed6ea9d286"
+        );
+        assert_eq!(
+            signer.sign(
+                "test_secret",
+                &input(
+                    "POST",
+                    "https://a-api.coinpayments.net/api/v2/merchant/invoices",
+                    "2024-01-01T12:00:00.000Z",
+                    r#"{"amount":"10.00"}"#
+                )
+            ),
+            "831aff85c660a36cbad72c02b4124775fd2c1f72bbe7b1482787ca07720736141e037e026bc431102c5bbdd53712153e3d72e35fc1e9e71ca93f8e174681d8c9"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T12:00:00.123Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(V2Signer.timestamp(now), "2024-01-01T12:00:00");
+        assert_eq!(LegacySigner.timestamp(now), "2024-01-01T12:00:00.123Z");
+    }
+}
@@ -0,0 +1,210 @@
+//! Tracing instrumentation for CoinPayments API calls
+//!
+//! This module provides functionality for:
+//! - Redacting secrets, signatures and emails before they reach logs or spans
+//! - Recording status, latency, retries and error kind on request spans (`tracing` feature)
+//!
+//! With the `tracing` feature every public operation opens a span named after the
+//! method (e.g. `create_spend_request`) with its identifying arguments, and every
+//! HTTP call opens a child `coinpayments.request` span.
+
+use reqwest::header::{HeaderMap, HeaderName};
+
+/// Headers whose values are never logged
+pub const SENSITIVE_HEADERS: &[&str] = &[
+    "Authorization",
+    "Cookie",
+    "Set-Cookie",
+    "X-CoinPayments-Signature",
+];
+
+/// Placeholder used for redacted values
+pub const REDACTED: &str = "[redacted]";
+
+// === Redaction ===
+
+/// Redact an email address, keeping the first character and the domain
+///
+/// # Example
+/// ```rust
+/// # use coinpayments::telemetry::redact_email;
+/// assert_eq!(redact_email("john.doe@example.com"), "j***@example.com");
+/// ```
+pub fn redact_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((local, domain)) => match local.chars().next() {
+            Some(first) => format!("{}***@{}", first, domain),
+            None => format!("***@{}", domain),
+        },
+        None => REDACTED.to_string(),
+    }
+}
+
+/// Check whether a header is one of the [`SENSITIVE_HEADERS`]
+pub(crate) fn is_sensitive_header(name: &HeaderName) -> bool {
+    SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.as_str().eq_ignore_ascii_case(sensitive))
+}
+
+/// Render headers for logging with sensitive values redacted
+pub fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive_header(name) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or(REDACTED).to_string()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+// === Span Recording ===
+
+/// Record the final response of a request on the current span
+#[cfg(feature = "tracing")]
+pub(crate) fn record_response(meta: &crate::ResponseMeta) {
+    let span = tracing::Span::current();
+    span.record("http.status_code", meta.status.as_u16());
+    span.record("latency_ms", meta.latency.as_millis() as u64);
+    span.record("retries", meta.attempts.saturating_sub(1));
+}
+
+/// Record the outcome of a request on the current span
+#[cfg(feature = "tracing")]
+pub(crate) fn record_outcome<T>(result: &crate::Result<T>) {
+    if let Err(error) = result {
+        // Only the kind is recorded: messages may contain response bodies
+        tracing::Span::current().record("error.kind", error.kind());
+        tracing::warn!(error.kind = error.kind(), "CoinPayments request failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_redact_email() {
+        assert_eq!(redact_email("john.doe@example.com"), "j***@example.com");
+        assert_eq!(redact_email("@example.com"), "***@example.com");
+        assert_eq!(redact_email("not-an-email"), REDACTED);
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-CoinPayments-Client", HeaderValue::from_static("client"));
+        headers.insert("X-CoinPayments-Signature", HeaderValue::from_static("abc"));
+        headers.insert("Authorization", HeaderValue::from_static("Bearer token"));
+
+        let redacted = redact_headers(&headers);
+        let value = |name: &str| {
+            redacted
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+
+        assert_eq!(value("X-CoinPayments-Client"), Some("client"));
+        assert_eq!(value("X-CoinPayments-Signature"), Some(REDACTED));
+        assert_eq!(value("Authorization"), Some(REDACTED));
+    }
+
+    #[cfg(feature = "tracing")]
+    mod spans {
+        use crate::{CoinPaymentsClient, CreateInvoiceRequest, MockTransport, RetryPolicy};
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::Subscriber;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::registry::LookupSpan;
+        use tracing_subscriber::Layer;
+
+        type Fields = HashMap<String, String>;
+
+        /// Layer capturing the name and fields of every span
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<HashMap<u64, (String, Fields)>>>);
+
+        struct Visitor<'a>(&'a mut Fields);
+
+        impl tracing::field::Visit for Visitor<'_> {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{:?}", value));
+            }
+
+            fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+        }
+
+        impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+            fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+                let mut fields = Fields::new();
+                attrs.record(&mut Visitor(&mut fields));
+                self.0
+                    .lock()
+                    .unwrap()
+                    .insert(id.into_u64(), (attrs.metadata().name().to_string(), fields));
+            }
+
+            fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+                if let Some((_, fields)) = self.0.lock().unwrap().get_mut(&id.into_u64()) {
+                    values.record(&mut Visitor(fields));
+                }
+            }
+        }
+
+        impl Capture {
+            fn span(&self, name: &str) -> Fields {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .find(|(span, _)| span == name)
+                    .map(|(_, fields)| fields.clone())
+                    .unwrap_or_else(|| panic!("no span named {}", name))
+            }
+        }
+
+        #[tokio::test]
+        async fn test_operation_and_request_spans() {
+            let capture = Capture::default();
+            let _guard = tracing::subscriber::set_default(
+                tracing_subscriber::registry().with(capture.clone()),
+            );
+
+            let transport = Arc::new(MockTransport::new());
+            transport.push_json(400, r#"{"error":"Invalid amount"}"#);
+            let client = CoinPaymentsClient::new("test_client", "test_secret")
+                .with_transport(transport)
+                .with_retry_policy(RetryPolicy::none());
+
+            let request = CreateInvoiceRequest::new("10.00", "USD", "Test")
+                .with_buyer("john.doe@example.com", None);
+            assert!(client.create_invoice(request).await.is_err());
+
+            let operation = capture.span("create_invoice");
+            assert_eq!(operation["amount"], "10.00");
+            assert_eq!(operation["buyer_email"], r#"Some("j***@example.com")"#);
+
+            let request = capture.span("coinpayments.request");
+            assert_eq!(request["http.method"], "POST");
+            assert_eq!(request["endpoint"], "v2/merchant/invoices");
+            assert_eq!(request["http.status_code"], "400");
+            assert_eq!(request["retries"], "0");
+            assert_eq!(request["error.kind"], "client");
+
+            let all = format!("{:?}", capture.0.lock().unwrap());
+            assert!(!all.contains("john.doe"));
+            assert!(!all.contains("test_secret"));
+        }
+    }
+}
@@ -0,0 +1,2224 @@
+//! In-process fake CoinPayments API for integration tests
+//!
+//! This module provides functionality for:
+//! - A stateful fake of the endpoints wrapped by this crate: currencies, rates, fees, wallets,
+//!   addresses, transactions, spend requests, consolidations, invoices and webhook configuration
+//! - Verifying request signatures against the fake's client credentials
+//! - Driving deposits and invoice payments, and recording the signed webhooks they emit
+//! - Serving the fake over HTTP on localhost, or plugging it in directly as a [`Transport`]
+
+use crate::endpoint::wire_value;
+use crate::fees::TransactionType as FeeTransactionType;
+use crate::transport::TransportFuture;
+use crate::{
+    retry, wallet_event_to_string, AddressType, ApiErrorDetails, BlockchainFee, ClientInfo,
+    ClientWebhook, ClientWebhookEvent, ClientWebhookPayload, CoinPaymentsClient, CoinPaymentsError,
+    CongestionLevel, ConsolidationInfo, ConsolidationStatus, CurrencyCapability, CurrencyStatus,
+    CurrencyV2, ExchangeRate, FeePriority, FeeType, HttpRequest, HttpResponse, Invoice,
+    InvoiceEventType, InvoiceHistoryEntry, InvoicePayout, InvoiceStatus, NetworkStatus,
+    PaymentData, PaymentInfo, PaymentStatus, PaymentStatusType, PaymentUrl, PayoutStatus,
+    Permission, RateLimits, Signer, SigningInput, SpendPreview, SpendRequest, SpendRequestStatus,
+    Transaction, TransactionStatus, TransactionType, Transport, V2Signer, Wallet, WalletAddress,
+    WalletStatus, WalletWebhookEvent, WalletWebhookPayload, WebhookHeaders,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, HOST};
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Network fee charged by the fake for spends and consolidations
+pub const FAKE_NETWORK_FEE: &str = "0.0001";
+
+// Amounts are kept as integers with 8 decimals
+const SCALE: i128 = 100_000_000;
+const NETWORK_FEE: i128 = 10_000;
+
+// === Fake Server ===
+
+/// Stateful in-memory fake of the CoinPayments API
+///
+/// Seeded with Bitcoin (`"4"`), Ethereum (`"61"`) and US dollar (`"5057"`) currencies and rates
+/// between them. Every request must be signed with the fake's client credentials (default v2
+/// scheme), otherwise it is rejected with `401`. Wallets start empty; fund them with
+/// [`FakeServer::deposit`]. Webhooks are not delivered but recorded, signed like real
+/// CoinPayments webhooks, and available from [`FakeServer::webhooks`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, CreateSpendRequest, CreateWalletRequest};
+/// # use coinpayments::testing::FakeServer;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let fake = FakeServer::new("client_id", "client_secret");
+/// let server = fake.serve().await?;
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_base_url(server.base_url());
+///
+/// client.create_wallet(CreateWalletRequest::new("hot", "4")).await?;
+/// fake.deposit("hot", "4", "1.5")?;
+/// let spend = client
+///     .create_spend_request("hot", "4", CreateSpendRequest::new("0.5").to_address("bc1q..."))
+///     .await?;
+/// assert_eq!(fake.wallet("hot", "4").unwrap().available_balance, "0.9999");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FakeServer {
+    state: Arc<Mutex<State>>,
+}
+
+/// Webhook emitted by the fake server
+#[derive(Debug, Clone)]
+pub struct SentWebhook {
+    /// URL the webhook was addressed to
+    pub url: String,
+    /// Signature headers, verifiable with [`crate::verify_webhook_signature`]
+    pub headers: WebhookHeaders,
+    /// Key the webhook was signed with (the webhook secret, or the client secret)
+    pub signing_key: String,
+    /// JSON payload
+    pub body: String,
+}
+
+impl SentWebhook {
+    /// Headers in the form accepted by [`crate::parse_webhook_headers`]
+    pub fn header_map(&self) -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "X-CoinPayments-Client".to_string(),
+                self.headers.client_id.clone(),
+            ),
+            (
+                "X-CoinPayments-Timestamp".to_string(),
+                self.headers.timestamp.clone(),
+            ),
+            (
+                "X-CoinPayments-Signature".to_string(),
+                self.headers.signature.clone(),
+            ),
+        ])
+    }
+
+    /// Parse the payload (e.g. into a [`ClientWebhookPayload`])
+    pub fn payload<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&self.body)
+    }
+}
+
+impl FakeServer {
+    /// Create a fake accepting requests signed with the given credentials
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        let mut state = State {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            next_id: 0,
+            block_number: 800_000,
+            currencies: Vec::new(),
+            rates: Vec::new(),
+            wallets: Vec::new(),
+            spends: Vec::new(),
+            consolidations: Vec::new(),
+            invoices: Vec::new(),
+            client_webhooks: Vec::new(),
+            idempotent: HashMap::new(),
+            webhooks: Vec::new(),
+        };
+        state.seed();
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Set the rate between two currencies (and its inverse)
+    pub fn with_rate(self, from_currency_id: &str, to_currency_id: &str, rate: f64) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .set_rate(from_currency_id, to_currency_id, rate);
+        self
+    }
+
+    /// Client sending its requests directly to this fake, without HTTP
+    pub fn client(&self) -> CoinPaymentsClient {
+        let state = self.state.lock().unwrap();
+        CoinPaymentsClient::new(state.client_id.clone(), state.client_secret.clone())
+            .with_transport(Arc::new(self.clone()))
+    }
+
+    /// Serve the fake over HTTP on a random localhost port until the handle is dropped
+    pub async fn serve(&self) -> std::io::Result<FakeServerHandle> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let server = self.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let server = server.clone();
+                tokio::spawn(async move {
+                    let _ = server.serve_connection(stream).await;
+                });
+            }
+        });
+        Ok(FakeServerHandle { base_url, task })
+    }
+
+    /// Credit a wallet as if an external deposit to its first address confirmed
+    pub fn deposit(
+        &self,
+        wallet_label: &str,
+        currency_id: &str,
+        amount: &str,
+    ) -> crate::Result<Transaction> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .deposit(wallet_label, currency_id, amount)
+            .map_err(FakeError::into_error)
+    }
+
+    /// Mark an unpaid invoice as paid, emitting an `invoicePaid` webhook
+    pub fn pay_invoice(&self, invoice_id: &str) -> crate::Result<Invoice> {
+        let mut state = self.state.lock().unwrap();
+        state.pay_invoice(invoice_id).map_err(FakeError::into_error)
+    }
+
+    /// Complete a paid invoice and pay it out, emitting an `invoiceCompleted` webhook
+    pub fn complete_invoice(&self, invoice_id: &str) -> crate::Result<Invoice> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .complete_invoice(invoice_id)
+            .map_err(FakeError::into_error)
+    }
+
+    /// Current state of a wallet
+    pub fn wallet(&self, wallet_label: &str, currency_id: &str) -> Option<Wallet> {
+        let state = self.state.lock().unwrap();
+        let index = state.wallet_index(wallet_label, currency_id).ok()?;
+        Some(state.wallets[index].to_wallet())
+    }
+
+    /// Current state of an invoice
+    pub fn invoice(&self, invoice_id: &str) -> Option<Invoice> {
+        let state = self.state.lock().unwrap();
+        let index = state.invoice_index(invoice_id).ok()?;
+        Some(state.invoices[index].invoice.clone())
+    }
+
+    /// Webhooks emitted so far, in order
+    pub fn webhooks(&self) -> Vec<SentWebhook> {
+        self.state.lock().unwrap().webhooks.clone()
+    }
+
+    /// Handle a signed API request
+    pub fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let mut state = self.state.lock().unwrap();
+        if let Err(error) = state.authenticate(request) {
+            return error.into_response();
+        }
+
+        let (path, query) = request.url.split_once('?').unwrap_or((&request.url, ""));
+        let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+        let segments: Vec<&str> = path
+            .split('/')
+            .skip_while(|segment| !is_version(segment))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let query: HashMap<String, String> = url::form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+
+        // Repeated idempotency keys replay the original response
+        let replay_key = request
+            .headers
+            .get(retry::IDEMPOTENCY_KEY_HEADER)
+            .and_then(|key| key.to_str().ok())
+            .map(|key| format!("{} {} {}", request.method, path, key));
+        if let Some(response) = replay_key
+            .as_ref()
+            .and_then(|key| state.idempotent.get(key))
+        {
+            return response.clone();
+        }
+
+        let response = match state.route(&request.method, &segments, &query, &body) {
+            Ok(value) => json_response(StatusCode::OK, &value),
+            Err(error) => error.into_response(),
+        };
+        if let (Some(key), true) = (replay_key, response.status.is_success()) {
+            state.idempotent.insert(key, response.clone());
+        }
+        response
+    }
+
+    async fn serve_connection(&self, mut stream: tokio::net::TcpStream) -> std::io::Result<()> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            if let Some(index) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break index + 4;
+            }
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default();
+        let target = request_line.next().unwrap_or("/");
+        let mut headers = HeaderMap::new();
+        for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.trim().as_bytes()),
+                HeaderValue::from_str(value.trim()),
+            ) {
+                headers.append(name, value);
+            }
+        }
+
+        let content_length = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok()?.parse::<usize>().ok())
+            .unwrap_or(0);
+        while buffer.len() < header_end + content_length {
+            let read = stream.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+        let body_end = buffer.len().min(header_end + content_length);
+        let host = headers
+            .get(HOST)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("127.0.0.1");
+
+        let request = HttpRequest {
+            method: Method::from_bytes(method.as_bytes()).unwrap_or(Method::GET),
+            url: format!("http://{}{}", host, target),
+            headers: headers.clone(),
+            body: buffer[header_end..body_end].to_vec(),
+            timeout: None,
+        };
+        let response = self.handle(&request);
+
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            response.status.as_u16(),
+            response.status.canonical_reason().unwrap_or(""),
+            response.body.len()
+        );
+        for (name, value) in &response.headers {
+            head.push_str(&format!(
+                "{}: {}\r\n",
+                name,
+                value.to_str().unwrap_or_default()
+            ));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&response.body).await?;
+        stream.shutdown().await
+    }
+}
+
+impl std::fmt::Debug for FakeServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("FakeServer")
+            .field("client_id", &state.client_id)
+            .field("wallets", &state.wallets.len())
+            .field("invoices", &state.invoices.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for FakeServer {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let response = self.handle(&request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// HTTP listener of a [`FakeServer`], stopped when dropped
+#[derive(Debug)]
+pub struct FakeServerHandle {
+    base_url: String,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl FakeServerHandle {
+    /// Base URL to pass to `with_base_url`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+impl Drop for FakeServerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// === State ===
+
+struct State {
+    client_id: String,
+    client_secret: String,
+    next_id: u64,
+    block_number: u64,
+    currencies: Vec<FakeCurrency>,
+    rates: Vec<(String, String, f64)>,
+    wallets: Vec<FakeWallet>,
+    spends: Vec<FakeSpend>,
+    consolidations: Vec<(ConsolidationInfo, Vec<Transaction>)>,
+    invoices: Vec<FakeInvoice>,
+    client_webhooks: Vec<ClientWebhook>,
+    idempotent: HashMap<String, HttpResponse>,
+    webhooks: Vec<SentWebhook>,
+}
+
+struct FakeCurrency {
+    currency: CurrencyV2,
+    confirmations: u32,
+    network: &'static str,
+}
+
+struct FakeWallet {
+    id: String,
+    label: String,
+    currency_id: String,
+    currency_symbol: String,
+    address_type: AddressType,
+    created_at: String,
+    updated_at: String,
+    balance: i128,
+    available: i128,
+    addresses: Vec<FakeAddress>,
+    transactions: Vec<Transaction>,
+    webhook: Option<FakeWebhook>,
+}
+
+struct FakeAddress {
+    address: WalletAddress,
+    balance: i128,
+    webhook: Option<FakeWebhook>,
+}
+
+#[derive(Clone)]
+struct FakeWebhook {
+    url: String,
+    events: Vec<String>,
+    secret: Option<String>,
+}
+
+struct FakeSpend {
+    wallet_id: String,
+    request: SpendRequest,
+    total: i128,
+    transaction_id: Option<String>,
+}
+
+struct FakeInvoice {
+    invoice: Invoice,
+    payments: Vec<PaymentInfo>,
+    statuses: Vec<PaymentStatus>,
+    history: Vec<InvoiceHistoryEntry>,
+    payouts: Vec<InvoicePayout>,
+}
+
+/// Error response of the fake
+struct FakeError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+type Reply = std::result::Result<Value, FakeError>;
+
+impl FakeError {
+    fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(what: impl std::fmt::Display) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            "NotFound",
+            format!("{} not found", what),
+        )
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "InvalidRequest", message)
+    }
+
+    fn into_response(self) -> HttpResponse {
+        json_response(
+            self.status,
+            &json!({"error": {"code": self.code, "message": self.message}}),
+        )
+    }
+
+    fn into_error(self) -> CoinPaymentsError {
+        ApiErrorDetails {
+            status: self.status,
+            code: Some(self.code.to_string()),
+            message: self.message,
+            details: None,
+            request_id: None,
+        }
+        .into_error()
+    }
+}
+
+impl FakeWallet {
+    fn to_wallet(&self) -> Wallet {
+        let pending = self.balance - self.available;
+        Wallet {
+            id: self.id.clone(),
+            label: self.label.clone(),
+            currency_id: self.currency_id.clone(),
+            currency_symbol: self.currency_symbol.clone(),
+            balance: format_amount(self.balance),
+            balance_f: as_f64(self.balance),
+            available_balance: format_amount(self.available),
+            available_balance_f: as_f64(self.available),
+            pending_balance: format_amount(pending),
+            pending_balance_f: as_f64(pending),
+            address_type: self.address_type.clone(),
+            status: WalletStatus::Active,
+            created_at: self.created_at.clone(),
+            updated_at: self.updated_at.clone(),
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Add an address; the first one is labelled `default`
+    fn add_address(&mut self, id: String) {
+        let address = FakeAddress {
+            address: WalletAddress {
+                address: format!("fake-{}-{}", self.currency_symbol.to_lowercase(), id),
+                label: if self.addresses.is_empty() {
+                    "default".to_string()
+                } else {
+                    id.clone()
+                },
+                id,
+                wallet_id: self.id.clone(),
+                currency_id: self.currency_id.clone(),
+                address_type: self.address_type.clone(),
+                balance: "0".to_string(),
+                balance_f: 0.0,
+                is_activated: false,
+                webhook_url: None,
+                created_at: now(),
+                updated_at: now(),
+            },
+            balance: 0,
+            webhook: None,
+        };
+        self.addresses.push(address);
+    }
+
+    /// Move `amount` into or out of the wallet and its addresses (first address for credits)
+    fn adjust(&mut self, amount: i128) {
+        self.balance += amount;
+        self.available += amount;
+        self.updated_at = now();
+        if amount > 0 {
+            if let Some(address) = self.addresses.first_mut() {
+                address.set_balance(address.balance + amount);
+            }
+        }
+    }
+}
+
+impl FakeAddress {
+    fn set_balance(&mut self, balance: i128) {
+        self.balance = balance;
+        self.address.balance = format_amount(balance);
+        self.address.balance_f = as_f64(balance);
+        self.address.is_activated = true;
+        self.address.updated_at = now();
+    }
+
+    fn matches(&self, reference: &str) -> bool {
+        self.address.address == reference
+            || self.address.id == reference
+            || self.address.label == reference
+    }
+}
+
+impl State {
+    fn seed(&mut self) {
+        for (id, name, symbol, decimals, is_fiat, confirmations, network) in [
+            ("4", "Bitcoin", "BTC", 8, false, 2, "bitcoin"),
+            ("61", "Ethereum", "ETH", 18, false, 12, "ethereum"),
+            ("5057", "US Dollar", "USD", 2, true, 0, "fiat"),
+        ] {
+            let capabilities = if is_fiat {
+                vec![CurrencyCapability::InvoicePayment]
+            } else {
+                vec![
+                    CurrencyCapability::Deposit,
+                    CurrencyCapability::Withdrawal,
+                    CurrencyCapability::Conversion,
+                    CurrencyCapability::InvoicePayment,
+                    CurrencyCapability::WalletCreation,
+                ]
+            };
+            self.currencies.push(FakeCurrency {
+                currency: CurrencyV2 {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    symbol: symbol.to_string(),
+                    blockchain_id: (!is_fiat).then(|| network.to_string()),
+                    smart_contract_address: None,
+                    decimals,
+                    is_fiat,
+                    status: CurrencyStatus::Active,
+                    capabilities,
+                    created_at: "2023-01-01T00:00:00Z".to_string(),
+                    updated_at: "2023-01-01T00:00:00Z".to_string(),
+                    extra: HashMap::new(),
+                },
+                confirmations,
+                network,
+            });
+        }
+        self.set_rate("4", "5057", 60_000.0);
+        self.set_rate("61", "5057", 3_000.0);
+        self.set_rate("4", "61", 20.0);
+    }
+
+    fn set_rate(&mut self, from: &str, to: &str, rate: f64) {
+        self.rates
+            .retain(|(f, t, _)| !((f == from && t == to) || (f == to && t == from)));
+        self.rates.push((from.to_string(), to.to_string(), rate));
+        self.rates
+            .push((to.to_string(), from.to_string(), 1.0 / rate));
+    }
+
+    fn id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}_{}", prefix, self.next_id)
+    }
+
+    fn authenticate(&self, request: &HttpRequest) -> std::result::Result<(), FakeError> {
+        let header = |name: &str| {
+            request
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let unauthorized =
+            |message: &str| FakeError::new(StatusCode::UNAUTHORIZED, "Unauthorized", message);
+
+        let (Some(client_id), Some(timestamp), Some(signature)) = (
+            header("X-CoinPayments-Client"),
+            header("X-CoinPayments-Timestamp"),
+            header("X-CoinPayments-Signature"),
+        ) else {
+            return Err(unauthorized("Missing authentication headers"));
+        };
+        if client_id != self.client_id {
+            return Err(unauthorized("Unknown client"));
+        }
+
+        let body = String::from_utf8_lossy(&request.body);
+        let expected = V2Signer.sign(
+            &self.client_secret,
+            &SigningInput {
+                method: request.method.as_str(),
+                url: &request.url,
+                endpoint: request.path(),
+                client_id,
+                timestamp,
+                body: &body,
+            },
+        );
+        if expected != signature {
+            return Err(unauthorized("Invalid signature"));
+        }
+        Ok(())
+    }
+
+    fn route(
+        &mut self,
+        method: &Method,
+        segments: &[&str],
+        query: &HashMap<String, String>,
+        body: &Value,
+    ) -> Reply {
+        match (method.as_str(), segments) {
+            // Client
+            ("GET", ["v1", "ping"]) => Ok(json!({
+                "message": "pong",
+                "timestamp": now(),
+                "version": "1",
+            })),
+            ("GET", ["v1", "client", "info"]) => self.client_info(),
+
+            // Currencies
+            ("GET", ["v2", "currencies"]) => {
+                let currencies: Vec<_> = self.currencies.iter().map(|c| &c.currency).collect();
+                let (currencies, pagination) = paginate(&currencies, query);
+                Ok(json!({"currencies": currencies, "pagination": pagination}))
+            }
+            ("GET", ["v2", "currencies", "required-confirmations"]) => {
+                let confirmations: Vec<_> = self
+                    .currencies
+                    .iter()
+                    .filter(|c| !c.currency.is_fiat)
+                    .map(|c| {
+                        json!({
+                            "currencyId": c.currency.id,
+                            "confirmations": c.confirmations,
+                            "network": c.network,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "confirmations": confirmations }))
+            }
+            ("GET", ["v2", "currencies", "conversions"]) => {
+                let conversions: Vec<_> = self
+                    .rates
+                    .iter()
+                    .filter(|(from, to, _)| !self.is_fiat(from) && !self.is_fiat(to))
+                    .map(|(from, to, _)| {
+                        json!({
+                            "fromCurrencyId": from,
+                            "toCurrencyId": to,
+                            "available": true,
+                            "minAmount": "0.0001",
+                            "maxAmount": "100",
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "conversions": conversions }))
+            }
+            (
+                "GET",
+                ["v2", "currencies", "blockchain-nodes", currency_id, "latest-block-number"],
+            ) => {
+                let currency = self.currency(currency_id)?;
+                let network = currency.network;
+                self.block_number += 1;
+                Ok(json!({
+                    "currencyId": currency_id,
+                    "latestBlockNumber": self.block_number,
+                    "synced": true,
+                    "network": network,
+                }))
+            }
+            ("GET", ["v2", "currencies", "limits", from, to]) => {
+                self.currency(from)?;
+                self.currency(to)?;
+                Ok(json!({
+                    "fromCurrencyId": from,
+                    "toCurrencyId": to,
+                    "minAmount": "0.0001",
+                    "maxAmount": "100",
+                    "dailyLimit": "1000",
+                    "monthlyLimit": null,
+                }))
+            }
+            ("GET", ["v2", "currencies", currency_id]) => {
+                Ok(json!(self.currency(currency_id)?.currency))
+            }
+            ("GET", ["v1", "merchant", "currencies"]) => {
+                let currencies: Vec<_> = self
+                    .currencies
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, c)| {
+                        json!({
+                            "currencyId": c.currency.id,
+                            "rank": rank + 1,
+                            "enabled": true,
+                            "createdAt": c.currency.created_at,
+                            "updatedAt": c.currency.updated_at,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "currencies": currencies }))
+            }
+
+            // Rates
+            ("GET", ["v2", "rates"]) => self.rates(query),
+
+            // Fees
+            ("POST", ["v2", "fees", "blockchain", currency_id]) => {
+                self.calculate_fee(currency_id, body)
+            }
+            ("GET", ["v2", "fees", "gas", currency_id]) => {
+                self.currency(currency_id)?;
+                let gas_limit = query
+                    .get("gasLimit")
+                    .and_then(|limit| limit.parse::<u64>().ok())
+                    .unwrap_or(21_000);
+                let cost = (gas_limit as i128) * 20 * SCALE / 1_000_000_000;
+                Ok(json!({
+                    "currencyId": currency_id,
+                    "gasPrice": "20",
+                    "gasLimit": gas_limit,
+                    "baseFee": "18",
+                    "priorityFee": "2",
+                    "maxFee": "40",
+                    "estimatedCost": format_amount(cost),
+                }))
+            }
+            ("GET", ["v2", "fees", "network-status", currency_id]) => {
+                self.currency(currency_id)?;
+                Ok(json!(self.network_status(currency_id)))
+            }
+
+            // Wallets
+            ("GET", ["v3", "merchant", "wallets"]) => {
+                let wallets: Vec<Wallet> = self
+                    .wallets
+                    .iter()
+                    .filter(|w| {
+                        query
+                            .get("currencyId")
+                            .is_none_or(|id| &w.currency_id == id)
+                    })
+                    .filter(|_| query.get("status").is_none_or(|status| status == "active"))
+                    .map(FakeWallet::to_wallet)
+                    .collect();
+                let (wallets, pagination) = paginate(&wallets, query);
+                Ok(json!({"wallets": wallets, "pagination": pagination}))
+            }
+            ("PUT", ["v3", "merchant", "wallets"]) => self.create_wallet(body),
+            ("GET", ["v3", "merchant", "wallets", "count"]) => Ok(json!({
+                "count": self.wallets.len(),
+                "activeCount": self.wallets.len(),
+                "inactiveCount": 0,
+            })),
+            ("POST", ["v3", "merchant", "wallets", "consolidation-preview"]) => {
+                self.preview_consolidation(body)
+            }
+            ("POST", ["v3", "merchant", "wallets", "consolidation", target]) => {
+                let target = self.wallet_by_label(target)?;
+                let currency_id = self.wallets[target].currency_id.clone();
+                let sources = (0..self.wallets.len())
+                    .filter(|&i| i != target && self.wallets[i].currency_id == currency_id)
+                    .collect();
+                self.consolidate(sources, target, body)
+            }
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "addresses"]) => {
+                let wallet = &self.wallets[self.wallet_index(label, currency_id)?];
+                let addresses: Vec<_> = wallet.addresses.iter().map(|a| &a.address).collect();
+                let (addresses, pagination) = paginate(&addresses, query);
+                Ok(json!({"addresses": addresses, "pagination": pagination}))
+            }
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "addresses", "count"]) => {
+                let wallet = &self.wallets[self.wallet_index(label, currency_id)?];
+                let activated = wallet
+                    .addresses
+                    .iter()
+                    .filter(|a| a.address.is_activated)
+                    .count();
+                Ok(json!({
+                    "count": wallet.addresses.len(),
+                    "activatedCount": activated,
+                    "unactivatedCount": wallet.addresses.len() - activated,
+                }))
+            }
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "addresses", address]) => {
+                let wallet = &self.wallets[self.wallet_index(label, currency_id)?];
+                let address = wallet
+                    .addresses
+                    .iter()
+                    .find(|a| a.matches(address))
+                    .ok_or_else(|| FakeError::not_found(format!("Address {}", address)))?;
+                Ok(json!(address.address))
+            }
+
+            // Webhook configuration
+            ("PUT", ["v3", "merchant", "wallets", label, currency_id, "webhook"]) => {
+                let index = self.wallet_index(label, currency_id)?;
+                self.wallets[index].webhook = Some(parse_webhook(body)?);
+                Ok(Value::Null)
+            }
+            (
+                "PUT",
+                ["v3", "merchant", "wallets", label, currency_id, "addresses", address, "webhook"],
+            ) => {
+                let index = self.wallet_index(label, currency_id)?;
+                self.set_address_webhook(index, address, body)
+            }
+            ("PUT", ["v2", "merchant", "wallets", wallet_id, "webhook"]) => {
+                let index = self.wallet_by_id(wallet_id)?;
+                self.wallets[index].webhook = Some(parse_webhook(body)?);
+                Ok(Value::Null)
+            }
+            ("PUT", ["v2", "merchant", "wallets", wallet_id, "addresses", address, "webhook"]) => {
+                let index = self.wallet_by_id(wallet_id)?;
+                self.set_address_webhook(index, address, body)
+            }
+            ("POST", ["v1", "merchant", "clients", client_id, "webhooks"]) => {
+                self.create_client_webhook(client_id, body)
+            }
+
+            // Transactions and spends
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "transactions"]) => {
+                let wallet = &self.wallets[self.wallet_index(label, currency_id)?];
+                let transactions: Vec<_> = wallet
+                    .transactions
+                    .iter()
+                    .filter(|t| {
+                        query
+                            .get("status")
+                            .is_none_or(|status| wire_value(&t.status) == *status)
+                    })
+                    .filter(|t| {
+                        query
+                            .get("type")
+                            .is_none_or(|kind| wire_value(&t.transaction_type) == *kind)
+                    })
+                    .collect();
+                let (transactions, pagination) = paginate(&transactions, query);
+                Ok(json!({"transactions": transactions, "pagination": pagination}))
+            }
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "transactions", "count"]) => {
+                let wallet = &self.wallets[self.wallet_index(label, currency_id)?];
+                let count = |status: TransactionStatus| {
+                    wallet
+                        .transactions
+                        .iter()
+                        .filter(|t| t.status == status)
+                        .count()
+                };
+                Ok(json!({
+                    "count": wallet.transactions.len(),
+                    "pendingCount": count(TransactionStatus::Pending),
+                    "completedCount": count(TransactionStatus::Completed),
+                    "failedCount": count(TransactionStatus::Failed),
+                }))
+            }
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "transaction"]) => {
+                let wallet = &self.wallets[self.wallet_index(label, currency_id)?];
+                let transaction_id = match query.get("spendRequestId") {
+                    Some(spend_id) => self
+                        .spends
+                        .iter()
+                        .find(|s| &s.request.id == spend_id)
+                        .and_then(|s| s.transaction_id.clone()),
+                    None => query.get("transactionId").cloned(),
+                };
+                wallet
+                    .transactions
+                    .iter()
+                    .find(|t| Some(&t.id) == transaction_id.as_ref())
+                    .map(|t| json!(t))
+                    .ok_or_else(|| FakeError::not_found("Transaction"))
+            }
+            ("POST", ["v3", "merchant", "wallets", label, currency_id, "spend", "request"]) => {
+                let index = self.wallet_index(label, currency_id)?;
+                self.create_spend(index, body)
+            }
+            (
+                "POST",
+                ["v3", "merchant", "wallets", label, currency_id, "spend", "confirmation"],
+            ) => {
+                let index = self.wallet_index(label, currency_id)?;
+                let spend_id = body["spendRequestId"].as_str().unwrap_or_default();
+                Ok(json!(self.confirm_spend(index, spend_id)?))
+            }
+
+            // Consolidations
+            ("GET", ["v3", "merchant", "wallets", label, currency_id, "consolidation"]) => {
+                self.wallet_index(label, currency_id)?;
+                let consolidations: Vec<_> = self
+                    .consolidations
+                    .iter()
+                    .map(|(info, _)| info)
+                    .filter(|info| info.wallet_label == *label && info.currency_id == *currency_id)
+                    .collect();
+                Ok(json!(paginate(&consolidations, query).0))
+            }
+            (
+                "POST",
+                ["v3", "merchant", "wallets", label, currency_id, "consolidation", target],
+            ) => {
+                let source = self.wallet_index(label, currency_id)?;
+                let target = self.wallet_index(target, currency_id)?;
+                self.consolidate(vec![source], target, body)
+            }
+            ("GET", ["v3", "merchant", "wallets", _, _, "consolidation-transactions", id]) => self
+                .consolidations
+                .iter()
+                .find(|(info, _)| info.id == *id)
+                .map(|(_, transactions)| json!(transactions))
+                .ok_or_else(|| FakeError::not_found(format!("Consolidation {}", id))),
+
+            // Invoices
+            ("POST", ["v2", "merchant", "invoices"]) => self.create_invoice(body),
+            ("GET", ["v2", "merchant", "invoices"]) => {
+                let invoices: Vec<_> = self
+                    .invoices
+                    .iter()
+                    .map(|i| &i.invoice)
+                    .filter(|i| {
+                        query
+                            .get("status")
+                            .is_none_or(|status| json!(i.status) == json!(status))
+                    })
+                    .filter(|i| query.get("currency").is_none_or(|c| &i.currency == c))
+                    .collect();
+                let (invoices, pagination) = paginate(&invoices, query);
+                Ok(json!({"invoices": invoices, "pagination": pagination}))
+            }
+            ("GET", ["v2", "merchant", "invoices", id]) => {
+                Ok(json!(self.invoices[self.invoice_index(id)?].invoice))
+            }
+            ("GET", ["v2", "merchant", "invoices", id, "payouts"]) => {
+                Ok(json!({"payouts": self.invoices[self.invoice_index(id)?].payouts}))
+            }
+            ("GET", ["v2", "merchant", "invoices", id, "history"]) => {
+                Ok(json!({"history": self.invoices[self.invoice_index(id)?].history}))
+            }
+            ("POST", ["v1", "merchant", "invoices", id, "cancel"]) => {
+                self.cancel_invoice(id)?;
+                Ok(Value::Null)
+            }
+            ("GET", ["v1", "invoices", id, "payment-currencies", currency_id]) => {
+                let invoice = &self.invoices[self.invoice_index(id)?];
+                invoice
+                    .payments
+                    .iter()
+                    .find(|p| p.currency_id == *currency_id)
+                    .map(|p| json!(p))
+                    .ok_or_else(|| {
+                        FakeError::not_found(format!("Payment currency {}", currency_id))
+                    })
+            }
+            ("GET", ["v1", "invoices", id, "payment-currencies", currency_id, "status"]) => {
+                let invoice = &self.invoices[self.invoice_index(id)?];
+                invoice
+                    .statuses
+                    .iter()
+                    .find(|s| s.currency_id == *currency_id)
+                    .map(|s| json!(s))
+                    .ok_or_else(|| {
+                        FakeError::not_found(format!("Payment currency {}", currency_id))
+                    })
+            }
+
+            _ => Err(FakeError::not_found(format!(
+                "{} /{}",
+                method,
+                segments.join("/")
+            ))),
+        }
+    }
+
+    // --- Lookups ---
+
+    fn currency(&self, reference: &str) -> std::result::Result<&FakeCurrency, FakeError> {
+        self.currencies
+            .iter()
+            .find(|c| c.currency.id == reference || c.currency.symbol == reference)
+            .ok_or_else(|| FakeError::not_found(format!("Currency {}", reference)))
+    }
+
+    fn is_fiat(&self, currency_id: &str) -> bool {
+        self.currency(currency_id)
+            .is_ok_and(|currency| currency.currency.is_fiat)
+    }
+
+    fn rate(&self, from: &str, to: &str) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        self.rates
+            .iter()
+            .find(|(f, t, _)| f == from && t == to)
+            .map(|(_, _, rate)| *rate)
+    }
+
+    fn wallet_index(
+        &self,
+        label: &str,
+        currency_id: &str,
+    ) -> std::result::Result<usize, FakeError> {
+        self.wallets
+            .iter()
+            .position(|w| w.label == label && w.currency_id == currency_id)
+            .ok_or_else(|| FakeError::not_found(format!("Wallet {}/{}", label, currency_id)))
+    }
+
+    fn wallet_by_label(&self, label: &str) -> std::result::Result<usize, FakeError> {
+        self.wallets
+            .iter()
+            .position(|w| w.label == label)
+            .ok_or_else(|| FakeError::not_found(format!("Wallet {}", label)))
+    }
+
+    fn wallet_by_id(&self, wallet_id: &str) -> std::result::Result<usize, FakeError> {
+        self.wallets
+            .iter()
+            .position(|w| w.id == wallet_id)
+            .ok_or_else(|| FakeError::not_found(format!("Wallet {}", wallet_id)))
+    }
+
+    fn invoice_index(&self, invoice_id: &str) -> std::result::Result<usize, FakeError> {
+        self.invoices
+            .iter()
+            .position(|i| i.invoice.id == invoice_id)
+            .ok_or_else(|| FakeError::not_found(format!("Invoice {}", invoice_id)))
+    }
+
+    // --- Client, rates and fees ---
+
+    fn client_info(&self) -> Reply {
+        let permissions = [
+            Permission::InvoiceCreate,
+            Permission::InvoiceRead,
+            Permission::WalletCreate,
+            Permission::WalletRead,
+            Permission::WalletSpend,
+            Permission::WebhookManage,
+        ];
+        Ok(json!(ClientInfo {
+            client_id: self.client_id.clone(),
+            name: "Fake CoinPayments client".to_string(),
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            rate_limits: RateLimits {
+                requests_per_minute: 0,
+                requests_per_hour: 0,
+                requests_per_day: 0,
+            },
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            updated_at: "2023-01-01T00:00:00Z".to_string(),
+        }))
+    }
+
+    fn rates(&self, query: &HashMap<String, String>) -> Reply {
+        let resolve = |name: &str| {
+            query.get(name).map(|reference| {
+                self.currency(reference)
+                    .map_or(reference.clone(), |c| c.currency.id.clone())
+            })
+        };
+        let from = resolve("from");
+        let to = resolve("to");
+        let currencies: Option<Vec<&str>> = query
+            .get("currencies")
+            .map(|list| list.split(',').collect());
+
+        let updated = now();
+        let rates: Vec<ExchangeRate> = self
+            .rates
+            .iter()
+            .filter(|(f, _, _)| from.as_ref().is_none_or(|from| f == from))
+            .filter(|(_, t, _)| to.as_ref().is_none_or(|to| t == to))
+            .filter(|(f, t, _)| {
+                currencies
+                    .as_ref()
+                    .is_none_or(|list| list.contains(&f.as_str()) || list.contains(&t.as_str()))
+            })
+            .map(|(f, t, rate)| ExchangeRate {
+                from_currency_id: f.clone(),
+                to_currency_id: t.clone(),
+                rate: rate.to_string(),
+                rate_f: *rate,
+                last_updated: updated.clone(),
+                market_cap: None,
+                volume_24h: None,
+                change_24h: None,
+                change_percentage_24h: None,
+            })
+            .collect();
+        Ok(json!({
+            "rates": rates,
+            "baseCurrency": from,
+            "lastUpdated": updated,
+            "pagination": null,
+        }))
+    }
+
+    fn network_status(&self, currency_id: &str) -> NetworkStatus {
+        NetworkStatus {
+            currency_id: currency_id.to_string(),
+            congestion_level: CongestionLevel::Low,
+            average_confirmation_time: 10,
+            mempool_size: Some(1_000),
+            last_updated: now(),
+        }
+    }
+
+    fn calculate_fee(&self, currency_id: &str, body: &Value) -> Reply {
+        let currency = self.currency(currency_id)?;
+        let symbol = currency.currency.symbol.clone();
+        let transaction_type =
+            serde_json::from_value::<FeeTransactionType>(body["transactionType"].clone())
+                .unwrap_or(FeeTransactionType::Send);
+
+        let fees: Vec<BlockchainFee> = [
+            (FeePriority::Slow, 1, 60),
+            (FeePriority::Standard, 2, 30),
+            (FeePriority::Fast, 4, 10),
+        ]
+        .into_iter()
+        .map(|(priority_level, factor, minutes)| {
+            let amount = NETWORK_FEE * factor / 2;
+            BlockchainFee {
+                currency_id: currency_id.to_string(),
+                fee_type: FeeType::Dynamic,
+                amount: format_amount(amount),
+                amount_f: as_f64(amount),
+                currency_symbol: symbol.clone(),
+                estimated_confirmation_time: Some(minutes),
+                priority_level,
+            }
+        })
+        .collect();
+        Ok(json!({
+            "currencyId": currency_id,
+            "transactionType": transaction_type,
+            "recommendedFee": fees[1],
+            "fees": fees,
+            "networkStatus": self.network_status(currency_id),
+        }))
+    }
+
+    // --- Wallets ---
+
+    fn create_wallet(&mut self, body: &Value) -> Reply {
+        let label = body["label"].as_str().unwrap_or_default().to_string();
+        let currency_id = body["currencyId"].as_str().unwrap_or_default().to_string();
+        if label.is_empty() {
+            return Err(FakeError::invalid("Wallet label is required"));
+        }
+        let symbol = self.currency(&currency_id)?.currency.symbol.clone();
+        if self.wallet_index(&label, &currency_id).is_ok() {
+            return Err(FakeError::invalid(format!(
+                "Wallet {}/{} already exists",
+                label, currency_id
+            )));
+        }
+
+        let address_type = if body["usePermanentAddresses"].as_bool() == Some(true) {
+            AddressType::Permanent
+        } else {
+            AddressType::Temporary
+        };
+        let id = self.id("wallet");
+        let mut wallet = FakeWallet {
+            id,
+            label,
+            currency_id,
+            currency_symbol: symbol,
+            address_type,
+            created_at: now(),
+            updated_at: now(),
+            balance: 0,
+            available: 0,
+            addresses: Vec::new(),
+            transactions: Vec::new(),
+            webhook: body["webhookUrl"].as_str().map(|url| FakeWebhook {
+                url: url.to_string(),
+                events: Vec::new(),
+                secret: None,
+            }),
+        };
+        if body["autoCreateAddress"].as_bool() != Some(false) {
+            let address_id = self.id("address");
+            wallet.add_address(address_id);
+        }
+
+        let response = json!({
+            "wallet": wallet.to_wallet(),
+            "addresses": wallet.addresses.iter().map(|a| &a.address).collect::<Vec<_>>(),
+        });
+        self.wallets.push(wallet);
+        Ok(response)
+    }
+
+    fn set_address_webhook(&mut self, wallet: usize, address: &str, body: &Value) -> Reply {
+        let webhook = parse_webhook(body)?;
+        let address = self.wallets[wallet]
+            .addresses
+            .iter_mut()
+            .find(|a| a.matches(address))
+            .ok_or_else(|| FakeError::not_found(format!("Address {}", address)))?;
+        address.address.webhook_url = Some(webhook.url.clone());
+        address.webhook = Some(webhook);
+        Ok(Value::Null)
+    }
+
+    fn transaction(
+        &mut self,
+        wallet: usize,
+        transaction_type: TransactionType,
+        amount: i128,
+        fee: Option<i128>,
+        address: Option<String>,
+    ) -> Transaction {
+        let id = self.id("tx");
+        let txid = format!("fake-txid-{}", id);
+        let wallet = &self.wallets[wallet];
+        let required_confirmations = self
+            .currency(&wallet.currency_id)
+            .map_or(1, |c| c.confirmations);
+        let network = self
+            .currency(&wallet.currency_id)
+            .map_or("fake", |c| c.network)
+            .to_string();
+        Transaction {
+            id,
+            wallet_id: wallet.id.clone(),
+            currency_id: wallet.currency_id.clone(),
+            transaction_type,
+            amount: format_amount(amount),
+            amount_f: as_f64(amount),
+            fee: fee.map(format_amount),
+            fee_f: fee.map(as_f64),
+            status: TransactionStatus::Completed,
+            address,
+            txid: Some(txid),
+            confirmations: required_confirmations,
+            required_confirmations,
+            network,
+            created_at: now(),
+            updated_at: now(),
+            completed_at: Some(now()),
+            extra: HashMap::new(),
+        }
+    }
+
+    fn deposit(
+        &mut self,
+        label: &str,
+        currency_id: &str,
+        amount: &str,
+    ) -> std::result::Result<Transaction, FakeError> {
+        let index = self.wallet_index(label, currency_id)?;
+        let amount = parse_positive(amount)?;
+        if self.wallets[index].addresses.is_empty() {
+            let address_id = self.id("address");
+            self.wallets[index].add_address(address_id);
+        }
+        let address = self.wallets[index].addresses[0].address.clone();
+
+        self.wallets[index].adjust(amount);
+        let transaction = self.transaction(
+            index,
+            TransactionType::UtxoExternalReceive,
+            amount,
+            None,
+            Some(address.address.clone()),
+        );
+        self.wallets[index].transactions.push(transaction.clone());
+        self.emit_wallet_event(
+            index,
+            WalletWebhookEvent::UtxoExternalReceive,
+            &transaction,
+            Some(&address),
+        );
+        Ok(transaction)
+    }
+
+    // --- Spends ---
+
+    fn create_spend(&mut self, wallet: usize, body: &Value) -> Reply {
+        let amount = parse_positive(body["amount"].as_str().unwrap_or_default())?;
+        let total = amount + NETWORK_FEE;
+        if self.wallets[wallet].available < total {
+            return Err(FakeError::new(
+                StatusCode::BAD_REQUEST,
+                "InsufficientFunds",
+                format!(
+                    "Insufficient funds: {} available, {} required",
+                    format_amount(self.wallets[wallet].available),
+                    format_amount(total)
+                ),
+            ));
+        }
+
+        // The spend is reserved from the available balance until it is confirmed
+        self.wallets[wallet].available -= total;
+        let id = self.id("spend");
+        let text = |name: &str| body[name].as_str().map(str::to_string);
+        let wallet_ref = &self.wallets[wallet];
+        let exchange_rate = text("destinationCurrencyId")
+            .and_then(|to| self.rate(&wallet_ref.currency_id, &to))
+            .map(|rate| rate.to_string());
+        let mut request = SpendRequest {
+            id: id.clone(),
+            wallet_label: wallet_ref.label.clone(),
+            currency_id: wallet_ref.currency_id.clone(),
+            amount: format_amount(amount),
+            amount_f: as_f64(amount),
+            fee: FAKE_NETWORK_FEE.to_string(),
+            fee_f: as_f64(NETWORK_FEE),
+            total_amount: format_amount(total),
+            total_amount_f: as_f64(total),
+            destination_address: text("destinationAddress"),
+            destination_currency_id: text("destinationCurrencyId"),
+            note: text("note"),
+            status: SpendRequestStatus::Pending,
+            created_at: now(),
+            expires_at: (chrono::Utc::now() + chrono::Duration::minutes(15)).to_rfc3339(),
+        };
+        let preview = SpendPreview {
+            amount: request.amount.clone(),
+            amount_f: request.amount_f,
+            fee: request.fee.clone(),
+            fee_f: request.fee_f,
+            total: request.total_amount.clone(),
+            total_f: request.total_amount_f,
+            exchange_rate,
+            estimated_confirmation_time: Some(10),
+        };
+        self.spends.push(FakeSpend {
+            wallet_id: wallet_ref.id.clone(),
+            request: request.clone(),
+            total,
+            transaction_id: None,
+        });
+
+        if body["autoConfirm"].as_bool() == Some(true) {
+            self.confirm_spend(wallet, &id)?;
+            request.status = SpendRequestStatus::Confirmed;
+        }
+        Ok(json!({"request": request, "preview": preview}))
+    }
+
+    fn confirm_spend(
+        &mut self,
+        wallet: usize,
+        spend_id: &str,
+    ) -> std::result::Result<Transaction, FakeError> {
+        let wallet_id = self.wallets[wallet].id.clone();
+        let spend = self
+            .spends
+            .iter()
+            .position(|s| s.request.id == spend_id && s.wallet_id == wallet_id)
+            .ok_or_else(|| FakeError::not_found(format!("Spend request {}", spend_id)))?;
+        if self.spends[spend].request.status != SpendRequestStatus::Pending {
+            return Err(FakeError::invalid(format!(
+                "Spend request {} is not pending",
+                spend_id
+            )));
+        }
+
+        let total = self.spends[spend].total;
+        let amount = total - NETWORK_FEE;
+        self.wallets[wallet].balance -= total;
+        self.wallets[wallet].updated_at = now();
+        let transaction = self.transaction(
+            wallet,
+            TransactionType::ExternalSpend,
+            amount,
+            Some(NETWORK_FEE),
+            self.spends[spend].request.destination_address.clone(),
+        );
+        self.wallets[wallet].transactions.push(transaction.clone());
+        self.spends[spend].request.status = SpendRequestStatus::Confirmed;
+        self.spends[spend].transaction_id = Some(transaction.id.clone());
+        self.emit_wallet_event(
+            wallet,
+            WalletWebhookEvent::ExternalSpend,
+            &transaction,
+            None,
+        );
+        Ok(transaction)
+    }
+
+    // --- Consolidations ---
+
+    /// Amount a consolidation takes from a wallet: the named addresses, or everything available
+    fn consolidation_amount(&self, wallet: usize, addresses: &[String]) -> (i128, Vec<usize>) {
+        let wallet = &self.wallets[wallet];
+        if addresses.is_empty() {
+            return (wallet.available, Vec::new());
+        }
+        let matched: Vec<usize> = (0..wallet.addresses.len())
+            .filter(|&i| addresses.iter().any(|a| wallet.addresses[i].matches(a)))
+            .collect();
+        let amount = matched
+            .iter()
+            .map(|&i| wallet.addresses[i].balance)
+            .sum::<i128>()
+            .min(wallet.available);
+        (amount, matched)
+    }
+
+    fn consolidate(&mut self, sources: Vec<usize>, target: usize, body: &Value) -> Reply {
+        let addresses: Vec<String> =
+            serde_json::from_value(body["sourceAddresses"].clone()).unwrap_or_default();
+        let requested = match body["amount"].as_str() {
+            Some(amount) => Some(parse_positive(amount)?),
+            None => None,
+        };
+
+        let mut total = 0;
+        let mut fees = 0;
+        let mut transactions = Vec::new();
+        let mut source_addresses = Vec::new();
+        for source in sources {
+            let (available, matched) = self.consolidation_amount(source, &addresses);
+            let amount = requested.map_or(available, |requested| requested.min(available));
+            if amount <= NETWORK_FEE {
+                continue;
+            }
+
+            let wallet = &mut self.wallets[source];
+            wallet.balance -= amount;
+            wallet.available -= amount;
+            wallet.updated_at = now();
+            let mut remaining = amount;
+            for &index in &matched {
+                let address = &mut wallet.addresses[index];
+                let taken = remaining.min(address.balance);
+                address.set_balance(address.balance - taken);
+                source_addresses.push(address.address.address.clone());
+                remaining -= taken;
+            }
+
+            let spent = self.transaction(
+                source,
+                TransactionType::InternalSpend,
+                amount - NETWORK_FEE,
+                Some(NETWORK_FEE),
+                None,
+            );
+            self.wallets[source].transactions.push(spent.clone());
+            transactions.push(spent);
+            total += amount;
+            fees += NETWORK_FEE;
+        }
+        if total == 0 {
+            return Err(FakeError::invalid("Nothing to consolidate"));
+        }
+
+        self.wallets[target].adjust(total - fees);
+        let target_address = self.wallets[target]
+            .addresses
+            .first()
+            .map(|a| a.address.address.clone())
+            .unwrap_or_default();
+        let received = self.transaction(
+            target,
+            TransactionType::InternalReceive,
+            total - fees,
+            None,
+            Some(target_address.clone()),
+        );
+        self.wallets[target].transactions.push(received.clone());
+        transactions.push(received);
+
+        let id = self.id("consolidation");
+        let source_label = transactions
+            .first()
+            .and_then(|t| self.wallets.iter().find(|w| w.id == t.wallet_id))
+            .map_or(String::new(), |w| w.label.clone());
+        let info = ConsolidationInfo {
+            id,
+            wallet_label: source_label,
+            currency_id: self.wallets[target].currency_id.clone(),
+            source_addresses,
+            target_address,
+            amount: format_amount(total),
+            amount_f: as_f64(total),
+            fee: format_amount(fees),
+            fee_f: as_f64(fees),
+            status: ConsolidationStatus::Completed,
+            created_at: now(),
+            completed_at: Some(now()),
+        };
+        self.consolidations.push((info.clone(), transactions));
+        Ok(json!(info))
+    }
+
+    fn preview_consolidation(&self, body: &Value) -> Reply {
+        let mut total = 0;
+        let mut fees = 0;
+        let mut address_count = 0;
+        for source in body["sourceWallets"].as_array().into_iter().flatten() {
+            let index = self.wallet_index(
+                source["walletLabel"].as_str().unwrap_or_default(),
+                source["currencyId"].as_str().unwrap_or_default(),
+            )?;
+            let addresses: Vec<String> =
+                serde_json::from_value(source["addresses"].clone()).unwrap_or_default();
+            let (amount, matched) = self.consolidation_amount(index, &addresses);
+            if amount > NETWORK_FEE {
+                total += amount;
+                fees += NETWORK_FEE;
+                address_count += matched.len().max(1);
+            }
+        }
+        Ok(json!({
+            "totalAmount": format_amount(total),
+            "totalAmountF": as_f64(total),
+            "totalFee": format_amount(fees),
+            "totalFeeF": as_f64(fees),
+            "netAmount": format_amount(total - fees),
+            "netAmountF": as_f64(total - fees),
+            "addressCount": address_count,
+            "estimatedTime": 10,
+        }))
+    }
+
+    // --- Invoices ---
+
+    fn create_invoice(&mut self, body: &Value) -> Reply {
+        let amount = parse_positive(body["amount"].as_str().unwrap_or_default())?;
+        let currency = body["currency"].as_str().unwrap_or_default().to_string();
+        let currency_id = self
+            .currency(&currency)
+            .map_err(|_| FakeError::invalid(format!("Invalid currency {}", currency)))?
+            .currency
+            .id
+            .clone();
+        let expires_in = body["expiresIn"].as_u64().unwrap_or(3600);
+        let payment_currencies: Vec<String> =
+            serde_json::from_value(body["paymentCurrencies"].clone()).unwrap_or_else(|_| {
+                self.currencies
+                    .iter()
+                    .filter(|c| !c.currency.is_fiat)
+                    .map(|c| c.currency.id.clone())
+                    .collect()
+            });
+
+        let id = self.id("inv");
+        let invoice_url = format!("https://fake.coinpayments.net/invoices/{}", id);
+        let mut payments = Vec::new();
+        for payment_currency in &payment_currencies {
+            let currency = self.currency(payment_currency)?;
+            let rate = self
+                .rate(&currency.currency.id, &currency_id)
+                .ok_or_else(|| {
+                    FakeError::invalid(format!(
+                        "No rate for {} to {}",
+                        payment_currency, currency_id
+                    ))
+                })?;
+            let due = (as_f64(amount) / rate * SCALE as f64).ceil() as i128;
+            payments.push(PaymentInfo {
+                currency_id: currency.currency.id.clone(),
+                currency_symbol: currency.currency.symbol.clone(),
+                address: format!("fake-{}-{}", currency.currency.symbol.to_lowercase(), id),
+                amount: format_amount(due),
+                amount_f: as_f64(due),
+                qr_code_url: format!("{}/qr/{}", invoice_url, currency.currency.id),
+                payment_url: format!("{}/pay/{}", invoice_url, currency.currency.id),
+                timeout: expires_in as u32,
+                required_confirmations: currency.confirmations,
+            });
+        }
+
+        let text = |name: &str| body[name].as_str().map(str::to_string);
+        let invoice = Invoice {
+            id: id.clone(),
+            merchant_id: self.client_id.clone(),
+            invoice_number: text("invoiceNumber"),
+            amount: format_amount(amount),
+            amount_f: as_f64(amount),
+            currency,
+            description: text("description").unwrap_or_default(),
+            item_name: text("itemName"),
+            item_number: text("itemNumber"),
+            buyer_email: text("buyerEmail"),
+            buyer_name: text("buyerName"),
+            status: InvoiceStatus::Unpaid,
+            created_at: now(),
+            updated_at: now(),
+            expires_at: (chrono::Utc::now() + chrono::Duration::seconds(expires_in as i64))
+                .to_rfc3339(),
+            paid_at: None,
+            completed_at: None,
+            invoice_url,
+            payment_urls: Some(
+                payments
+                    .iter()
+                    .map(|p| PaymentUrl {
+                        currency_id: p.currency_id.clone(),
+                        currency_symbol: p.currency_symbol.clone(),
+                        url: p.payment_url.clone(),
+                    })
+                    .collect(),
+            ),
+            extra: HashMap::new(),
+        };
+        let statuses = payments
+            .iter()
+            .map(|p| PaymentStatus {
+                currency_id: p.currency_id.clone(),
+                amount_paid: "0".to_string(),
+                amount_paid_f: 0.0,
+                amount_received: "0".to_string(),
+                amount_received_f: 0.0,
+                confirmations: 0,
+                required_confirmations: p.required_confirmations,
+                status: PaymentStatusType::Waiting,
+                txid: None,
+                first_seen: None,
+                last_updated: now(),
+            })
+            .collect();
+
+        self.invoices.push(FakeInvoice {
+            invoice: invoice.clone(),
+            payments: payments.clone(),
+            statuses,
+            history: Vec::new(),
+            payouts: Vec::new(),
+        });
+        let index = self.invoices.len() - 1;
+        self.record_history(index, InvoiceEventType::Created, "Invoice created");
+        self.emit_invoice_event(index, ClientWebhookEvent::InvoiceCreated, None);
+        Ok(json!({"invoice": invoice, "paymentInfo": payments}))
+    }
+
+    fn record_history(&mut self, invoice: usize, event_type: InvoiceEventType, description: &str) {
+        let id = self.id("history");
+        let invoice = &mut self.invoices[invoice];
+        invoice.history.push(InvoiceHistoryEntry {
+            id,
+            invoice_id: invoice.invoice.id.clone(),
+            event_type,
+            description: description.to_string(),
+            metadata: None,
+            created_at: now(),
+        });
+    }
+
+    fn set_invoice_status(&mut self, invoice: usize, status: InvoiceStatus) {
+        let invoice = &mut self.invoices[invoice].invoice;
+        invoice.status = status;
+        invoice.updated_at = now();
+    }
+
+    fn pay_invoice(&mut self, invoice_id: &str) -> std::result::Result<Invoice, FakeError> {
+        let index = self.invoice_index(invoice_id)?;
+        if !matches!(
+            self.invoices[index].invoice.status,
+            InvoiceStatus::Unpaid | InvoiceStatus::Pending
+        ) {
+            return Err(FakeError::invalid(format!(
+                "Invoice {} cannot be paid",
+                invoice_id
+            )));
+        }
+
+        let txid = format!("fake-txid-{}", self.id("payment"));
+        let invoice = &mut self.invoices[index];
+        let payment = invoice.payments.first().cloned();
+        if let (Some(payment), Some(status)) = (&payment, invoice.statuses.first_mut()) {
+            status.amount_paid = payment.amount.clone();
+            status.amount_paid_f = payment.amount_f;
+            status.amount_received = payment.amount.clone();
+            status.amount_received_f = payment.amount_f;
+            status.confirmations = payment.required_confirmations;
+            status.status = PaymentStatusType::Confirmed;
+            status.txid = Some(txid.clone());
+            status.first_seen = Some(now());
+            status.last_updated = now();
+        }
+        invoice.invoice.paid_at = Some(now());
+        self.set_invoice_status(index, InvoiceStatus::Paid);
+        self.record_history(index, InvoiceEventType::PaymentReceived, "Payment received");
+        self.record_history(index, InvoiceEventType::Paid, "Invoice paid");
+
+        let payment_data = payment.map(|payment| PaymentData {
+            currency_id: payment.currency_id,
+            address: payment.address,
+            amount: payment.amount,
+            txid: Some(txid),
+            confirmations: Some(payment.required_confirmations),
+            first_seen: Some(now()),
+        });
+        self.emit_invoice_event(index, ClientWebhookEvent::InvoicePaid, payment_data);
+        Ok(self.invoices[index].invoice.clone())
+    }
+
+    fn complete_invoice(&mut self, invoice_id: &str) -> std::result::Result<Invoice, FakeError> {
+        let index = self.invoice_index(invoice_id)?;
+        if self.invoices[index].invoice.status != InvoiceStatus::Paid {
+            return Err(FakeError::invalid(format!(
+                "Invoice {} is not paid",
+                invoice_id
+            )));
+        }
+
+        let payout_id = self.id("payout");
+        let invoice = &mut self.invoices[index];
+        invoice.invoice.completed_at = Some(now());
+        invoice.payouts.push(InvoicePayout {
+            id: payout_id,
+            invoice_id: invoice_id.to_string(),
+            amount: invoice.invoice.amount.clone(),
+            amount_f: invoice.invoice.amount_f,
+            currency: invoice.invoice.currency.clone(),
+            destination_address: "fake-merchant-payout-address".to_string(),
+            txid: None,
+            status: PayoutStatus::Completed,
+            fee: Some("0".to_string()),
+            fee_f: Some(0.0),
+            created_at: now(),
+            completed_at: Some(now()),
+        });
+        self.set_invoice_status(index, InvoiceStatus::Completed);
+        self.record_history(index, InvoiceEventType::Completed, "Invoice completed");
+        self.record_history(index, InvoiceEventType::PayoutCompleted, "Payout completed");
+        self.emit_invoice_event(index, ClientWebhookEvent::InvoiceCompleted, None);
+        Ok(self.invoices[index].invoice.clone())
+    }
+
+    fn cancel_invoice(&mut self, invoice_id: &str) -> std::result::Result<(), FakeError> {
+        let index = self.invoice_index(invoice_id)?;
+        if !matches!(
+            self.invoices[index].invoice.status,
+            InvoiceStatus::Unpaid | InvoiceStatus::Pending
+        ) {
+            return Err(FakeError::invalid(format!(
+                "Invoice {} cannot be cancelled",
+                invoice_id
+            )));
+        }
+        self.set_invoice_status(index, InvoiceStatus::Cancelled);
+        self.record_history(index, InvoiceEventType::Cancelled, "Invoice cancelled");
+        self.emit_invoice_event(index, ClientWebhookEvent::InvoiceCancelled, None);
+        Ok(())
+    }
+
+    // --- Webhooks ---
+
+    fn create_client_webhook(&mut self, client_id: &str, body: &Value) -> Reply {
+        if client_id != self.client_id {
+            return Err(FakeError::not_found(format!("Client {}", client_id)));
+        }
+        let url = body["url"].as_str().unwrap_or_default().to_string();
+        if url.is_empty() {
+            return Err(FakeError::invalid("Webhook URL is required"));
+        }
+        let events: Vec<ClientWebhookEvent> = serde_json::from_value(body["events"].clone())
+            .map_err(|error| FakeError::invalid(format!("Invalid events: {}", error)))?;
+
+        let webhook = ClientWebhook {
+            id: self.id("webhook"),
+            client_id: client_id.to_string(),
+            url,
+            events,
+            secret: body["secret"].as_str().map(str::to_string),
+            is_active: body["isActive"].as_bool().unwrap_or(true),
+            created_at: now(),
+            updated_at: now(),
+        };
+        self.client_webhooks.push(webhook.clone());
+        Ok(json!(webhook))
+    }
+
+    fn emit(&mut self, url: &str, secret: Option<&str>, payload: &impl Serialize) {
+        let body = serde_json::to_string(payload).unwrap_or_default();
+        let signing_key = secret.unwrap_or(&self.client_secret).to_string();
+        let timestamp = now();
+        let signature = sign_webhook(&signing_key, &self.client_id, &timestamp, body.as_bytes());
+        self.webhooks.push(SentWebhook {
+            url: url.to_string(),
+            headers: WebhookHeaders {
+                client_id: self.client_id.clone(),
+                timestamp,
+                signature,
+            },
+            signing_key,
+            body,
+        });
+    }
+
+    fn emit_invoice_event(
+        &mut self,
+        invoice: usize,
+        event: ClientWebhookEvent,
+        payment_data: Option<PaymentData>,
+    ) {
+        let invoice = &self.invoices[invoice].invoice;
+        let payload = ClientWebhookPayload {
+            event: event.clone(),
+            invoice_id: invoice.id.clone(),
+            merchant_id: invoice.merchant_id.clone(),
+            amount: invoice.amount.clone(),
+            currency: invoice.currency.clone(),
+            status: json!(invoice.status)
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            created_at: now(),
+            payment_data,
+            metadata: None,
+            extra: HashMap::new(),
+        };
+        let targets: Vec<(String, Option<String>)> = self
+            .client_webhooks
+            .iter()
+            .filter(|w| w.is_active && w.events.contains(&event))
+            .map(|w| (w.url.clone(), w.secret.clone()))
+            .collect();
+        for (url, secret) in targets {
+            self.emit(&url, secret.as_deref(), &payload);
+        }
+    }
+
+    fn emit_wallet_event(
+        &mut self,
+        wallet: usize,
+        event: WalletWebhookEvent,
+        transaction: &Transaction,
+        address: Option<&WalletAddress>,
+    ) {
+        let wallet_ref = &self.wallets[wallet];
+        let name = wallet_event_to_string(&event);
+        let subscribed = |webhook: &FakeWebhook| {
+            webhook.events.is_empty() || webhook.events.iter().any(|e| *e == name)
+        };
+        let mut targets: Vec<FakeWebhook> = wallet_ref
+            .webhook
+            .iter()
+            .filter(|w| subscribed(w))
+            .cloned()
+            .collect();
+        if let Some(address) = address {
+            targets.extend(
+                wallet_ref
+                    .addresses
+                    .iter()
+                    .filter(|a| a.address.id == address.id)
+                    .filter_map(|a| a.webhook.clone())
+                    .filter(|w| subscribed(w)),
+            );
+        }
+
+        let payload = WalletWebhookPayload {
+            event,
+            wallet_id: wallet_ref.id.clone(),
+            wallet_label: wallet_ref.label.clone(),
+            address_id: address.map(|a| a.id.clone()),
+            address: address
+                .map(|a| a.address.clone())
+                .or_else(|| transaction.address.clone())
+                .unwrap_or_default(),
+            currency_id: wallet_ref.currency_id.clone(),
+            transaction_id: transaction.id.clone(),
+            amount: transaction.amount.clone(),
+            fee: transaction.fee.clone(),
+            txid: transaction.txid.clone(),
+            confirmations: transaction.confirmations,
+            status: "completed".to_string(),
+            created_at: now(),
+            metadata: None,
+            extra: HashMap::new(),
+        };
+        for webhook in targets {
+            self.emit(&webhook.url, webhook.secret.as_deref(), &payload);
+        }
+    }
+}
+
+// === Helper Functions ===
+
+/// Sign a webhook payload the way CoinPayments does (hex HMAC-SHA512 of client id, timestamp
+/// and body), matching [`crate::verify_webhook_signature`]
+pub fn sign_webhook(key: &str, client_id: &str, timestamp: &str, payload: &[u8]) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha512;
+
+    let mut mac =
+        Hmac::<Sha512>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(client_id.as_bytes());
+    mac.update(timestamp.as_bytes());
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn is_version(segment: &str) -> bool {
+    segment.len() > 1
+        && segment.starts_with('v')
+        && segment[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
+
+fn json_response(status: StatusCode, value: &Value) -> HttpResponse {
+    HttpResponse::new(status, value.to_string())
+        .with_header("Content-Type", "application/json")
+        .with_header(
+            "Date",
+            &chrono::Utc::now()
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string(),
+        )
+}
+
+fn paginate<'a, T: Serialize>(
+    items: &'a [T],
+    query: &HashMap<String, String>,
+) -> (Vec<&'a T>, Value) {
+    let number = |name: &str, default: usize| {
+        query
+            .get(name)
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(default)
+    };
+    let page = number("page", 1);
+    let per_page = number("perPage", 100);
+    let total_pages = items.len().div_ceil(per_page).max(1);
+    let page_items = items
+        .iter()
+        .skip((page - 1) * per_page)
+        .take(per_page)
+        .collect();
+    (
+        page_items,
+        json!({
+            "page": page,
+            "perPage": per_page,
+            "total": items.len(),
+            "totalPages": total_pages,
+        }),
+    )
+}
+
+fn parse_webhook(body: &Value) -> std::result::Result<FakeWebhook, FakeError> {
+    let url = body["url"].as_str().unwrap_or_default();
+    if url.is_empty() {
+        return Err(FakeError::invalid("Webhook URL is required"));
+    }
+    Ok(FakeWebhook {
+        url: url.to_string(),
+        events: serde_json::from_value(body["events"].clone()).unwrap_or_default(),
+        secret: body["secret"].as_str().map(str::to_string),
+    })
+}
+
+/// Parse a decimal amount with up to 8 decimals into integer units
+fn parse_amount(amount: &str) -> Option<i128> {
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > 8
+        || !digits(whole)
+        || !digits(fraction)
+    {
+        return None;
+    }
+    let whole: i128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: i128 = format!("{:0<8}", fraction).parse().ok()?;
+    Some(whole * SCALE + fraction)
+}
+
+fn parse_positive(amount: &str) -> std::result::Result<i128, FakeError> {
+    parse_amount(amount)
+        .filter(|amount| *amount > 0)
+        .ok_or_else(|| FakeError::invalid(format!("Invalid amount '{}'", amount)))
+}
+
+fn format_amount(units: i128) -> String {
+    let sign = if units < 0 { "-" } else { "" };
+    let whole = units.abs() / SCALE;
+    let fraction = units.abs() % SCALE;
+    if fraction == 0 {
+        format!("{}{}", sign, whole)
+    } else {
+        let fraction = format!("{:08}", fraction);
+        format!("{}{}.{}", sign, whole, fraction.trim_end_matches('0'))
+    }
+}
+
+fn as_f64(units: i128) -> f64 {
+    units as f64 / SCALE as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        verify_webhook_signature, ClientWebhookEvent, CreateClientWebhookRequest,
+        CreateInvoiceRequest, CreateSpendRequest, CreateWalletRequest, RetryPolicy,
+    };
+
+    fn fake_and_client() -> (FakeServer, CoinPaymentsClient) {
+        let fake = FakeServer::new("fake_client", "fake_secret");
+        let client = fake.client().with_retry_policy(RetryPolicy::none());
+        (fake, client)
+    }
+
+    #[test]
+    fn test_amounts() {
+        assert_eq!(parse_amount("1.5"), Some(150_000_000));
+        assert_eq!(parse_amount(".00000001"), Some(1));
+        assert_eq!(parse_amount("1.123456789"), None);
+        assert_eq!(parse_amount("abc"), None);
+        assert_eq!(format_amount(150_000_000), "1.5");
+        assert_eq!(format_amount(-10_000), "-0.0001");
+        assert_eq!(format_amount(200_000_000), "2");
+    }
+
+    #[tokio::test]
+    async fn test_spend_request_debits_available_balance() {
+        let (fake, client) = fake_and_client();
+        client
+            .create_wallet(CreateWalletRequest::new("hot", "4"))
+            .await
+            .unwrap();
+        fake.deposit("hot", "4", "1.5").unwrap();
+
+        let spend = client
+            .create_spend_request(
+                "hot",
+                "4",
+                CreateSpendRequest::new("0.5").to_address("bc1q"),
+            )
+            .await
+            .unwrap();
+        let wallet = fake.wallet("hot", "4").unwrap();
+        assert_eq!(wallet.balance, "1.5");
+        assert_eq!(wallet.available_balance, "0.9999");
+        assert_eq!(spend.preview.total, "0.5001");
+
+        let transaction = client
+            .confirm_spend_request("hot", "4", &spend.request.id)
+            .await
+            .unwrap();
+        assert_eq!(transaction.transaction_type, TransactionType::ExternalSpend);
+        assert_eq!(fake.wallet("hot", "4").unwrap().balance, "0.9999");
+        let found = client
+            .get_transaction("hot", "4", None, Some(&spend.request.id))
+            .await
+            .unwrap();
+        assert_eq!(found.id, transaction.id);
+
+        let error = client
+            .create_spend_request("hot", "4", CreateSpendRequest::new("5"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, CoinPaymentsError::InsufficientFunds(_)));
+    }
+
+    #[tokio::test]
+    async fn test_invoice_lifecycle_emits_signed_webhooks() {
+        let (fake, client) = fake_and_client();
+        client
+            .create_client_webhook(
+                "fake_client",
+                CreateClientWebhookRequest::new("https://shop.example/hooks").with_events(vec![
+                    ClientWebhookEvent::InvoicePaid,
+                    ClientWebhookEvent::InvoiceCompleted,
+                ]),
+            )
+            .await
+            .unwrap();
+
+        let created = client
+            .create_invoice(
+                CreateInvoiceRequest::new("120", "USD", "Order 1")
+                    .with_payment_currencies(vec!["4".to_string()]),
+            )
+            .await
+            .unwrap();
+        let id = created.invoice.id;
+        assert_eq!(created.payment_info.unwrap()[0].amount, "0.002");
+
+        fake.pay_invoice(&id).unwrap();
+        fake.complete_invoice(&id).unwrap();
+        let invoice = client.get_invoice(&id, None).await.unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Completed);
+        assert_eq!(
+            client.get_invoice_payouts(&id).await.unwrap().payouts.len(),
+            1
+        );
+        assert!(client.cancel_invoice(&id).await.is_err());
+
+        let webhooks = fake.webhooks();
+        assert_eq!(webhooks.len(), 2);
+        let paid: ClientWebhookPayload = webhooks[0].payload().unwrap();
+        assert_eq!(paid.event, ClientWebhookEvent::InvoicePaid);
+        assert!(paid.payment_data.is_some());
+        for webhook in &webhooks {
+            assert!(verify_webhook_signature(
+                "fake_secret",
+                &webhook.headers,
+                webhook.body.as_bytes()
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consolidation_moves_funds() {
+        let (fake, client) = fake_and_client();
+        for label in ["deposits", "main"] {
+            client
+                .create_wallet(CreateWalletRequest::new(label, "4"))
+                .await
+                .unwrap();
+        }
+        fake.deposit("deposits", "4", "0.3").unwrap();
+
+        let info = client
+            .execute_wallet_consolidation(
+                "deposits",
+                "4",
+                "main",
+                crate::ConsolidationRequest {
+                    source_addresses: Vec::new(),
+                    target_wallet_label: "main".to_string(),
+                    amount: None,
+                    note: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(info.amount, "0.3");
+        assert_eq!(fake.wallet("deposits", "4").unwrap().balance, "0");
+        assert_eq!(fake.wallet("main", "4").unwrap().balance, "0.2999");
+        let transactions = client
+            .get_consolidation_transactions("deposits", "4", &info.id)
+            .await
+            .unwrap();
+        assert_eq!(transactions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_signatures_and_serves_http() {
+        let fake = FakeServer::new("fake_client", "fake_secret");
+        let server = fake.serve().await.unwrap();
+
+        let client = CoinPaymentsClient::new("fake_client", "fake_secret")
+            .with_base_url(server.base_url())
+            .with_retry_policy(RetryPolicy::none());
+        let currencies = client.get_currencies(None, None).await.unwrap();
+        assert_eq!(currencies.currencies.len(), 3);
+        let rate = client.get_rate("4", "5057").await.unwrap();
+        assert_eq!(rate.rate_f, 60_000.0);
+
+        let wrong = CoinPaymentsClient::new("fake_client", "wrong_secret")
+            .with_base_url(server.base_url())
+            .with_retry_policy(RetryPolicy::none());
+        assert!(matches!(
+            wrong.get_currencies(None, None).await,
+            Err(CoinPaymentsError::Authentication(_))
+        ));
+    }
+}
@@ -6,13 +6,17 @@
 //! - Wallet consolidation operations
 //! - Transaction history and information
 
+use crate::endpoint::{page_query, wallet_params, wire_value, Endpoint};
 use crate::{CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // === Transaction Types ===
 
 /// Transaction information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Transaction {
     pub id: String,
     pub wallet_id: String,
@@ -31,6 +35,9 @@ pub struct Transaction {
     pub created_at: String,
     pub updated_at: String,
     pub completed_at: Option<String>,
+    /// Fields not known to this version of the crate, kept verbatim
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Transaction types
@@ -63,8 +70,9 @@ pub enum TransactionType {
     ReceiveTestFundsFromPool,
     /// Returning test funds
     ReturnTestFundsToPool,
-    /// Transaction state unknown
-    Unknown,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Transaction statuses
@@ -93,12 +101,14 @@ pub enum TransactionStatus {
     Cancelled,
     /// Tx has been rejected by party
     Rejected,
-    /// Tx status unknown
-    Unknown,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Spend request information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct SpendRequest {
     pub id: String,
     pub wallet_label: String,
@@ -126,10 +136,14 @@ pub enum SpendRequestStatus {
     Cancelled,
     Expired,
     Failed,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Request to create a spend request
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateSpendRequest {
     pub amount: String,
     pub destination_address: Option<String>,
@@ -140,6 +154,7 @@ pub struct CreateSpendRequest {
 
 /// Response for spend request operations
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SpendRequestResponse {
     pub request: SpendRequest,
     pub preview: SpendPreview,
@@ -147,6 +162,7 @@ pub struct SpendRequestResponse {
 
 /// Spend preview information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct SpendPreview {
     pub amount: String,
     pub amount_f: f64,
@@ -160,12 +176,14 @@ pub struct SpendPreview {
 
 /// Spend confirmation request
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct SpendConfirmationRequest {
     pub spend_request_id: String,
 }
 
 /// Response for getting transactions
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetTransactionsResponse {
     pub transactions: Vec<Transaction>,
     pub pagination: Option<TransactionPaginationInfo>,
@@ -173,6 +191,7 @@ pub struct GetTransactionsResponse {
 
 /// Pagination information for transactions
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TransactionPaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -182,6 +201,7 @@ pub struct TransactionPaginationInfo {
 
 /// Transaction count response
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TransactionCountResponse {
     pub count: u32,
     pub pending_count: u32,
@@ -191,6 +211,7 @@ pub struct TransactionCountResponse {
 
 /// Consolidation information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ConsolidationInfo {
     pub id: String,
     pub wallet_label: String,
@@ -215,10 +236,14 @@ pub enum ConsolidationStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Consolidation request
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ConsolidationRequest {
     pub source_addresses: Vec<String>,
     pub target_wallet_label: String,
@@ -228,6 +253,7 @@ pub struct ConsolidationRequest {
 
 /// Consolidation preview request
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ConsolidationPreviewRequest {
     pub source_wallets: Vec<ConsolidationSourceWallet>,
     pub target_wallet_label: String,
@@ -236,6 +262,7 @@ pub struct ConsolidationPreviewRequest {
 
 /// Source wallet for consolidation
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ConsolidationSourceWallet {
     pub wallet_label: String,
     pub currency_id: String,
@@ -244,6 +271,7 @@ pub struct ConsolidationSourceWallet {
 
 /// Consolidation preview response
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ConsolidationPreviewResponse {
     pub total_amount: String,
     pub total_amount_f: f64,
@@ -301,6 +329,430 @@ impl CreateSpendRequest {
     }
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::get_transaction_count`]
+#[derive(Debug, Clone)]
+pub struct GetTransactionCount {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetTransactionCount {
+    /// Count the transactions of a wallet
+    pub fn new(wallet_label: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetTransactionCount {
+    type Request = ();
+    type Response = TransactionCountResponse;
+
+    const NAME: &'static str = "get_transaction_count";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/transactions/count";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_transactions`]
+#[derive(Debug, Clone)]
+pub struct GetTransactions {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Page number
+    pub page: Option<u32>,
+    /// Number of results per page
+    pub per_page: Option<u32>,
+    /// Only transactions with this status
+    pub status: Option<TransactionStatus>,
+    /// Only transactions of this type
+    pub transaction_type: Option<TransactionType>,
+}
+
+impl GetTransactions {
+    /// List the transactions of a wallet
+    pub fn new(wallet_label: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            page: None,
+            per_page: None,
+            status: None,
+            transaction_type: None,
+        }
+    }
+}
+
+impl Endpoint for GetTransactions {
+    type Request = ();
+    type Response = GetTransactionsResponse;
+
+    const NAME: &'static str = "get_transactions";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/transactions";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query_params = page_query(self.page, self.per_page);
+
+        if let Some(status) = &self.status {
+            query_params.push(("status", wire_value(status)));
+        }
+        if let Some(tx_type) = &self.transaction_type {
+            query_params.push(("type", wire_value(tx_type)));
+        }
+        query_params
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_transaction`]
+#[derive(Debug, Clone)]
+pub struct GetTransaction {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Transaction ID
+    pub transaction_id: Option<String>,
+    /// ID of the spend request that created the transaction
+    pub spend_request_id: Option<String>,
+}
+
+impl GetTransaction {
+    /// Get a transaction of a wallet (set `transaction_id` or `spend_request_id`)
+    pub fn new(wallet_label: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            transaction_id: None,
+            spend_request_id: None,
+        }
+    }
+}
+
+impl Endpoint for GetTransaction {
+    type Request = ();
+    type Response = Transaction;
+
+    const NAME: &'static str = "get_transaction";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/transaction";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query_params = Vec::new();
+
+        if let Some(tx_id) = &self.transaction_id {
+            query_params.push(("transactionId", tx_id.clone()));
+        }
+        if let Some(spend_id) = &self.spend_request_id {
+            query_params.push(("spendRequestId", spend_id.clone()));
+        }
+        query_params
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::create_spend_request`]
+#[derive(Debug, Clone)]
+pub struct CreateSpend {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Spend to request
+    pub request: CreateSpendRequest,
+}
+
+impl CreateSpend {
+    /// Request a spend from a wallet
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        request: CreateSpendRequest,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for CreateSpend {
+    type Request = CreateSpendRequest;
+    type Response = SpendRequestResponse;
+
+    const NAME: &'static str = "create_spend_request";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/spend/request";
+    const REQUIRES_IDEMPOTENCY_KEY: bool = true;
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::confirm_spend_request`]
+#[derive(Debug, Clone)]
+pub struct ConfirmSpend {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Spend request to confirm
+    pub request: SpendConfirmationRequest,
+}
+
+impl ConfirmSpend {
+    /// Confirm a spend request
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        spend_request_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            request: SpendConfirmationRequest {
+                spend_request_id: spend_request_id.into(),
+            },
+        }
+    }
+}
+
+impl Endpoint for ConfirmSpend {
+    type Request = SpendConfirmationRequest;
+    type Response = Transaction;
+
+    const NAME: &'static str = "confirm_spend_request";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/spend/confirmation";
+    const REQUIRES_IDEMPOTENCY_KEY: bool = true;
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_wallet_consolidation`]
+#[derive(Debug, Clone)]
+pub struct GetWalletConsolidation {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Page number
+    pub page: Option<u32>,
+    /// Number of results per page
+    pub per_page: Option<u32>,
+}
+
+impl GetWalletConsolidation {
+    /// List the consolidations of a wallet
+    pub fn new(wallet_label: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            page: None,
+            per_page: None,
+        }
+    }
+}
+
+impl Endpoint for GetWalletConsolidation {
+    type Request = ();
+    type Response = Vec<ConsolidationInfo>;
+
+    const NAME: &'static str = "get_wallet_consolidation";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/consolidation";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        page_query(self.page, self.per_page)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::execute_wallet_consolidation`]
+#[derive(Debug, Clone)]
+pub struct ExecuteWalletConsolidation {
+    /// Source wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Target wallet label
+    pub target_wallet_label: String,
+    /// Consolidation parameters
+    pub request: ConsolidationRequest,
+}
+
+impl ExecuteWalletConsolidation {
+    /// Consolidate a wallet into another wallet
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        target_wallet_label: impl Into<String>,
+        request: ConsolidationRequest,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            target_wallet_label: target_wallet_label.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for ExecuteWalletConsolidation {
+    type Request = ConsolidationRequest;
+    type Response = ConsolidationInfo;
+
+    const NAME: &'static str = "execute_wallet_consolidation";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/consolidation/{target_wallet_label}";
+    const REQUIRES_IDEMPOTENCY_KEY: bool = true;
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = wallet_params(&self.wallet_label, &self.currency_id);
+        params.push(("target_wallet_label", self.target_wallet_label.clone()));
+        params
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::execute_multi_wallet_consolidation`]
+#[derive(Debug, Clone)]
+pub struct ExecuteMultiWalletConsolidation {
+    /// Target wallet label
+    pub target_wallet_label: String,
+    /// Consolidation parameters
+    pub request: ConsolidationRequest,
+}
+
+impl ExecuteMultiWalletConsolidation {
+    /// Consolidate several wallets into one wallet
+    pub fn new(target_wallet_label: impl Into<String>, request: ConsolidationRequest) -> Self {
+        Self {
+            target_wallet_label: target_wallet_label.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for ExecuteMultiWalletConsolidation {
+    type Request = ConsolidationRequest;
+    type Response = ConsolidationInfo;
+
+    const NAME: &'static str = "execute_multi_wallet_consolidation";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v3/merchant/wallets/consolidation/{target_wallet_label}";
+    const REQUIRES_IDEMPOTENCY_KEY: bool = true;
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("target_wallet_label", self.target_wallet_label.clone())]
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::preview_consolidation`]
+#[derive(Debug, Clone)]
+pub struct PreviewConsolidation {
+    /// Wallets to preview
+    pub request: ConsolidationPreviewRequest,
+}
+
+impl Endpoint for PreviewConsolidation {
+    type Request = ConsolidationPreviewRequest;
+    type Response = ConsolidationPreviewResponse;
+
+    const NAME: &'static str = "preview_consolidation";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v3/merchant/wallets/consolidation-preview";
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_consolidation_transactions`]
+#[derive(Debug, Clone)]
+pub struct GetConsolidationTransactions {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Consolidation ID
+    pub consolidation_id: String,
+}
+
+impl GetConsolidationTransactions {
+    /// List the transactions of a consolidation
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        consolidation_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            consolidation_id: consolidation_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetConsolidationTransactions {
+    type Request = ();
+    type Response = Vec<Transaction>;
+
+    const NAME: &'static str = "get_consolidation_transactions";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/consolidation-transactions/{consolidation_id}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = wallet_params(&self.wallet_label, &self.currency_id);
+        params.push(("consolidation_id", self.consolidation_id.clone()));
+        params
+    }
+}
+
 impl CoinPaymentsClient {
     /// Get transaction count for a wallet
     ///
@@ -309,20 +761,31 @@ impl CoinPaymentsClient {
     /// * `currency_id` - Currency ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let count = client.get_transaction_count("my-btc-wallet", "4").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn get_transaction_count(
         &self,
         wallet_label: &str,
         currency_id: &str,
     ) -> Result<TransactionCountResponse> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/transactions/count",
-            wallet_label, currency_id
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetTransactionCount::new(wallet_label, currency_id))
+            .await
     }
 
     /// Get transactions for a wallet
@@ -336,10 +799,24 @@ impl CoinPaymentsClient {
     /// * `transaction_type` - Filter by type (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let transactions = client.get_transactions("my-btc-wallet", "4", None, None, None, None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn get_transactions(
         &self,
         wallet_label: &str,
@@ -349,26 +826,14 @@ impl CoinPaymentsClient {
         status: Option<TransactionStatus>,
         transaction_type: Option<TransactionType>,
     ) -> Result<GetTransactionsResponse> {
-        let mut query_params = Vec::new();
-
-        if let Some(page) = page {
-            query_params.push(("page", page.to_string()));
-        }
-        if let Some(per_page) = per_page {
-            query_params.push(("per_page", per_page.to_string()));
-        }
-        if let Some(status) = status {
-            query_params.push(("status", format!("{:?}", status).to_lowercase()));
-        }
-        if let Some(tx_type) = transaction_type {
-            query_params.push(("type", format!("{:?}", tx_type)));
-        }
-
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/transactions",
-            wallet_label, currency_id
-        );
-        self.get_request(&endpoint, &query_params).await
+        self.execute(GetTransactions {
+            page,
+            per_page,
+            status,
+            transaction_type,
+            ..GetTransactions::new(wallet_label, currency_id)
+        })
+        .await
     }
 
     /// Get a specific transaction
@@ -380,10 +845,26 @@ impl CoinPaymentsClient {
     /// * `spend_request_id` - Spend request ID (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let transaction = client.get_transaction("my-btc-wallet", "4", Some("tx_123"), None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                transaction_id = ?transaction_id,
+                spend_request_id = ?spend_request_id
+            )
+        )
+    )]
     pub async fn get_transaction(
         &self,
         wallet_label: &str,
@@ -391,20 +872,12 @@ impl CoinPaymentsClient {
         transaction_id: Option<&str>,
         spend_request_id: Option<&str>,
     ) -> Result<Transaction> {
-        let mut query_params = Vec::new();
-
-        if let Some(tx_id) = transaction_id {
-            query_params.push(("transactionId", tx_id.to_string()));
-        }
-        if let Some(spend_id) = spend_request_id {
-            query_params.push(("spendRequestId", spend_id.to_string()));
-        }
-
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/transaction",
-            wallet_label, currency_id
-        );
-        self.get_request(&endpoint, &query_params).await
+        self.execute(GetTransaction {
+            transaction_id: transaction_id.map(str::to_string),
+            spend_request_id: spend_request_id.map(str::to_string),
+            ..GetTransaction::new(wallet_label, currency_id)
+        })
+        .await
     }
 
     /// Create a spend request
@@ -415,7 +888,9 @@ impl CoinPaymentsClient {
     /// * `request` - Spend request details
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, CreateSpendRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     ///
     /// // Withdrawal
@@ -427,18 +902,28 @@ impl CoinPaymentsClient {
     /// let conversion = CreateSpendRequest::new("0.001")
     ///     .to_currency("61"); // Convert BTC to ETH
     /// let spend = client.create_spend_request("my-btc-wallet", "4", conversion).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                amount = %request.amount
+            )
+        )
+    )]
     pub async fn create_spend_request(
         &self,
         wallet_label: &str,
         currency_id: &str,
         request: CreateSpendRequest,
     ) -> Result<SpendRequestResponse> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/spend/request",
-            wallet_label, currency_id
-        );
-        self.post_request(&endpoint, &request).await
+        self.execute(CreateSpend::new(wallet_label, currency_id, request))
+            .await
     }
 
     /// Confirm a spend request
@@ -449,24 +934,37 @@ impl CoinPaymentsClient {
     /// * `spend_request_id` - ID of the spend request to confirm
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let confirmation = client.confirm_spend_request("my-btc-wallet", "4", "spend_123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                spend_request_id = %spend_request_id
+            )
+        )
+    )]
     pub async fn confirm_spend_request(
         &self,
         wallet_label: &str,
         currency_id: &str,
         spend_request_id: &str,
     ) -> Result<Transaction> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/spend/confirmation",
-            wallet_label, currency_id
-        );
-        let request = SpendConfirmationRequest {
-            spend_request_id: spend_request_id.to_string(),
-        };
-        self.post_request(&endpoint, &request).await
+        self.execute(ConfirmSpend::new(
+            wallet_label,
+            currency_id,
+            spend_request_id,
+        ))
+        .await
     }
 
     /// Get wallet consolidation information
@@ -478,10 +976,24 @@ impl CoinPaymentsClient {
     /// * `per_page` - Results per page (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let consolidations = client.get_wallet_consolidation("my-btc-wallet", "4", None, None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn get_wallet_consolidation(
         &self,
         wallet_label: &str,
@@ -489,20 +1001,12 @@ impl CoinPaymentsClient {
         page: Option<u32>,
         per_page: Option<u32>,
     ) -> Result<Vec<ConsolidationInfo>> {
-        let mut query_params = Vec::new();
-
-        if let Some(page) = page {
-            query_params.push(("page", page.to_string()));
-        }
-        if let Some(per_page) = per_page {
-            query_params.push(("per_page", per_page.to_string()));
-        }
-
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/consolidation",
-            wallet_label, currency_id
-        );
-        self.get_request(&endpoint, &query_params).await
+        self.execute(GetWalletConsolidation {
+            page,
+            per_page,
+            ..GetWalletConsolidation::new(wallet_label, currency_id)
+        })
+        .await
     }
 
     /// Execute wallet consolidation
@@ -514,7 +1018,9 @@ impl CoinPaymentsClient {
     /// * `request` - Consolidation request details
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, ConsolidationRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = ConsolidationRequest {
     ///     source_addresses: vec!["addr1".to_string(), "addr2".to_string()],
@@ -523,7 +1029,20 @@ impl CoinPaymentsClient {
     ///     note: Some("Consolidating funds".to_string()),
     /// };
     /// let consolidation = client.execute_wallet_consolidation("temp-wallet", "4", "main-wallet", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                target_wallet_label = %target_wallet_label
+            )
+        )
+    )]
     pub async fn execute_wallet_consolidation(
         &self,
         wallet_label: &str,
@@ -531,11 +1050,13 @@ impl CoinPaymentsClient {
         target_wallet_label: &str,
         request: ConsolidationRequest,
     ) -> Result<ConsolidationInfo> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/consolidation/{}",
-            wallet_label, currency_id, target_wallet_label
-        );
-        self.post_request(&endpoint, &request).await
+        self.execute(ExecuteWalletConsolidation::new(
+            wallet_label,
+            currency_id,
+            target_wallet_label,
+            request,
+        ))
+        .await
     }
 
     /// Execute multi-wallet consolidation
@@ -545,7 +1066,9 @@ impl CoinPaymentsClient {
     /// * `request` - Multi-wallet consolidation request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, ConsolidationRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = ConsolidationRequest {
     ///     source_addresses: vec!["addr1".to_string(), "addr2".to_string()],
@@ -554,14 +1077,23 @@ impl CoinPaymentsClient {
     ///     note: Some("Multi-wallet consolidation".to_string()),
     /// };
     /// let consolidation = client.execute_multi_wallet_consolidation("main-wallet", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(target_wallet_label = %target_wallet_label))
+    )]
     pub async fn execute_multi_wallet_consolidation(
         &self,
         target_wallet_label: &str,
         request: ConsolidationRequest,
     ) -> Result<ConsolidationInfo> {
-        let endpoint = format!("v3/merchant/wallets/consolidation/{}", target_wallet_label);
-        self.post_request(&endpoint, &request).await
+        self.execute(ExecuteMultiWalletConsolidation::new(
+            target_wallet_label,
+            request,
+        ))
+        .await
     }
 
     /// Preview consolidation operation
@@ -570,7 +1102,9 @@ impl CoinPaymentsClient {
     /// * `request` - Consolidation preview request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, ConsolidationPreviewRequest, ConsolidationSourceWallet};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = ConsolidationPreviewRequest {
     ///     source_wallets: vec![ConsolidationSourceWallet {
@@ -582,13 +1116,15 @@ impl CoinPaymentsClient {
     ///     target_currency_id: "4".to_string(),
     /// };
     /// let preview = client.preview_consolidation(request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn preview_consolidation(
         &self,
         request: ConsolidationPreviewRequest,
     ) -> Result<ConsolidationPreviewResponse> {
-        self.post_request("v3/merchant/wallets/consolidation-preview", &request)
-            .await
+        self.execute(PreviewConsolidation { request }).await
     }
 
     /// Get consolidation transactions
@@ -599,21 +1135,37 @@ impl CoinPaymentsClient {
     /// * `consolidation_id` - Consolidation ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let transactions = client.get_consolidation_transactions("my-btc-wallet", "4", "cons_123").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                consolidation_id = %consolidation_id
+            )
+        )
+    )]
     pub async fn get_consolidation_transactions(
         &self,
         wallet_label: &str,
         currency_id: &str,
         consolidation_id: &str,
     ) -> Result<Vec<Transaction>> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/consolidation-transactions/{}",
-            wallet_label, currency_id, consolidation_id
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetConsolidationTransactions::new(
+            wallet_label,
+            currency_id,
+            consolidation_id,
+        ))
+        .await
     }
 }
 
@@ -731,6 +1283,7 @@ mod tests {
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
             completed_at: Some("2023-01-01T01:00:00Z".to_string()),
+            extra: HashMap::new(),
         }
     }
 
@@ -864,4 +1417,31 @@ mod tests {
         assert_eq!(grouped.get("4").unwrap().len(), 1);
         assert_eq!(grouped.get("61").unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_unknown_transaction_status_and_type() {
+        let status: TransactionStatus = serde_json::from_str(r#""confirmedOnBlockchain""#).unwrap();
+        assert_eq!(status, TransactionStatus::ConfirmedOnBlockchain);
+        let status: TransactionStatus = serde_json::from_str(r#""unknown""#).unwrap();
+        assert_eq!(status, TransactionStatus::Unknown("unknown".to_string()));
+        let tx_type: TransactionType = serde_json::from_str(r#""stakingReward""#).unwrap();
+        assert_eq!(
+            tx_type,
+            TransactionType::Unknown("stakingReward".to_string())
+        );
+
+        let query = GetTransactions {
+            status: Some(TransactionStatus::Unknown("onHold".to_string())),
+            transaction_type: Some(tx_type),
+            ..GetTransactions::new("hot", "4")
+        }
+        .query();
+        assert_eq!(
+            query,
+            vec![
+                ("status", "onHold".to_string()),
+                ("type", "stakingReward".to_string())
+            ]
+        );
+    }
 }
@@ -0,0 +1,278 @@
+//! HTTP transports for CoinPayments API requests
+//!
+//! This module provides functionality for:
+//! - A pluggable [`Transport`] trait: request in, status + headers + body out
+//! - The default reqwest-based transport
+//! - An in-memory mock transport for testing code built on the client
+
+use crate::{CoinPaymentsError, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Method, StatusCode};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Future returned by [`Transport::send`]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse>> + Send + 'a>>;
+
+// === Transport Types ===
+
+/// Fully signed HTTP request ready to be sent
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Absolute URL including the query string
+    pub url: String,
+    /// Request headers (including authentication headers, whose credential values are
+    /// marked sensitive so `Debug` hides them)
+    pub headers: HeaderMap,
+    /// Request body (empty for requests without a body)
+    pub body: Vec<u8>,
+    /// Timeout overriding the transport's default for this request
+    pub timeout: Option<Duration>,
+}
+
+/// Raw HTTP response returned by a transport
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Path of the request URL without scheme, host and query string
+    pub fn path(&self) -> &str {
+        let without_scheme = self
+            .url
+            .split_once("://")
+            .map_or(self.url.as_str(), |(_, rest)| rest);
+        let path = without_scheme
+            .find('/')
+            .map_or("/", |index| &without_scheme[index..]);
+        path.split('?').next().unwrap_or(path)
+    }
+
+    /// Request body as UTF-8 text
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+impl HttpResponse {
+    /// Create a response with the given status and body
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.headers.insert(name, value);
+        }
+        self
+    }
+
+    /// Response body as UTF-8 text
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Sends HTTP requests on behalf of a [`CoinPaymentsClient`](crate::CoinPaymentsClient)
+///
+/// Transport failures should be reported as [`CoinPaymentsError::Http`] or
+/// [`CoinPaymentsError::Network`]; both are retried according to the retry policy.
+pub trait Transport: Send + Sync + std::fmt::Debug {
+    /// Send a request and return the raw response
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+// === Reqwest Transport ===
+
+/// Default transport backed by a `reqwest::Client`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport using the given reqwest client
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, &request.url)
+                .headers(request.headers);
+            if !request.body.is_empty() {
+                builder = builder.body(request.body);
+            }
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?.to_vec();
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+// === Mock Transport ===
+
+/// In-memory transport returning queued responses and recording requests
+///
+/// # Example
+/// ```rust,no_run
+/// # use coinpayments::{CoinPaymentsClient, MockTransport};
+/// # use std::sync::Arc;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let transport = Arc::new(MockTransport::new());
+/// transport.push_json(200, r#"{"message":"pong","timestamp":"2023-01-01T00:00:00Z","version":"1"}"#);
+///
+/// let client = CoinPaymentsClient::new("client_id", "client_secret")
+///     .with_transport(transport.clone());
+/// client.ping().await?;
+/// assert_eq!(transport.requests()[0].path(), "/api/v1/ping");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<Result<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    /// Create a mock transport without queued responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response
+    pub fn push_response(&self, response: HttpResponse) {
+        self.responses.lock().unwrap().push_back(Ok(response));
+    }
+
+    /// Queue a JSON response with the given status code
+    pub fn push_json(&self, status: u16, body: impl Into<String>) {
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        self.push_response(
+            HttpResponse::new(status, body.into()).with_header("Content-Type", "application/json"),
+        );
+    }
+
+    /// Queue a transport failure
+    pub fn push_error(&self, message: impl Into<String>) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(Err(CoinPaymentsError::Network(message.into())));
+    }
+
+    /// Requests sent so far, in order
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of queued responses not yet consumed
+    pub fn remaining(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let description = format!("{} {}", request.method, request.url);
+        self.requests.lock().unwrap().push(request);
+        let response = self.responses.lock().unwrap().pop_front();
+
+        Box::pin(async move {
+            response.unwrap_or_else(|| {
+                Err(CoinPaymentsError::Network(format!(
+                    "No mock response queued for {}",
+                    description
+                )))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str) -> HttpRequest {
+        HttpRequest {
+            method: Method::GET,
+            url: url.to_string(),
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request("https://a-api.coinpayments.net/api/v1/ping?x=1").path(),
+            "/api/v1/ping"
+        );
+        assert_eq!(request("http://localhost:1234").path(), "/");
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport_replays_in_order() {
+        let transport = MockTransport::new();
+        transport.push_json(503, "{}");
+        transport.push_json(200, r#"{"ok":true}"#);
+
+        let first = transport.send(request("http://test/a")).await.unwrap();
+        let second = transport.send(request("http://test/b")).await.unwrap();
+        assert_eq!(first.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(second.text(), r#"{"ok":true}"#);
+        assert_eq!(transport.remaining(), 0);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path(), "/b");
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport_without_responses_fails() {
+        let transport = MockTransport::new();
+        transport.push_error("connection reset");
+
+        assert!(matches!(
+            transport.send(request("http://test/a")).await,
+            Err(CoinPaymentsError::Network(_))
+        ));
+        assert!(transport.send(request("http://test/b")).await.is_err());
+    }
+}
@@ -24,6 +24,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 ///
 /// # Example
 /// ```rust
+/// # use coinpayments::generate_hmac_signature;
 /// let signature = generate_hmac_signature("private_key", "data_to_sign");
 /// ```
 pub fn generate_hmac_signature(private_key: &str, data: &str) -> String {
@@ -73,8 +74,13 @@ pub fn create_auth_headers(
 // === Validation Utilities ===
 
 /// Validate email address format
+///
+/// Requires a non-empty local part and a domain containing a dot.
 pub fn is_valid_email(email: &str) -> bool {
-    email.contains('@') && email.contains('.') && email.len() > 5
+    match email.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && domain.contains('.') && email.len() > 5,
+        None => false,
+    }
 }
 
 /// Validate currency ID format
@@ -93,7 +99,7 @@ pub fn is_valid_wallet_label(label: &str) -> bool {
 
 /// Validate amount format (positive number)
 pub fn is_valid_amount(amount: &str) -> bool {
-    amount.parse::<f64>().map_or(false, |f| f > 0.0)
+    amount.parse::<f64>().is_ok_and(|f| f > 0.0)
 }
 
 /// Validate Bitcoin address format (basic check)
@@ -215,11 +221,15 @@ pub fn build_query_string(params: &[(&str, String)]) -> String {
     format!("?{}", query_parts.join("&"))
 }
 
+/// Default `User-Agent` sent by the client
+pub const DEFAULT_USER_AGENT: &str = "coinpayments-rust/1.0";
+
 /// Create HTTP client with default settings
 pub fn create_http_client() -> Result<Client> {
     Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(10))
         .timeout(std::time::Duration::from_secs(30))
-        .user_agent("coinpayments-rust/1.0")
+        .user_agent(DEFAULT_USER_AGENT)
         .build()
         .map_err(CoinPaymentsError::Http)
 }
@@ -248,7 +258,7 @@ pub fn extract_rate_limit_info(headers: &reqwest::header::HeaderMap) -> Option<R
         .ok()?;
 
     Some(RateLimitInfo {
-        calls_made: limit - remaining,
+        calls_made: limit.saturating_sub(remaining),
         calls_left: remaining,
         reset_time: reset,
     })
@@ -304,7 +314,7 @@ pub fn validate_webhook_signature(
 
 /// Calculate pagination info
 pub fn calculate_pagination(total: u32, page: u32, per_page: u32) -> PaginationInfo {
-    let total_pages = (total + per_page - 1) / per_page; // Ceiling division
+    let total_pages = total.div_ceil(per_page);
 
     PaginationInfo {
         page,
@@ -318,6 +328,7 @@ pub fn calculate_pagination(total: u32, page: u32, per_page: u32) -> PaginationI
 
 /// Pagination information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct PaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -334,7 +345,7 @@ pub mod test_utils {
     use super::*;
 
     /// Create a mock HTTP response for testing
-    pub fn create_mock_response(status: u16, body: &str) -> Result<serde_json::Value> {
+    pub fn create_mock_response(_status: u16, body: &str) -> Result<serde_json::Value> {
         serde_json::from_str(body).map_err(CoinPaymentsError::Json)
     }
 
@@ -375,6 +386,14 @@ mod tests {
         assert!(!is_valid_email("test@"));
     }
 
+    #[test]
+    fn test_is_valid_email_checks_each_side_of_at() {
+        // The dot has to be in the domain, and the local part must not be empty
+        assert!(is_valid_email("first.last@example.com"));
+        assert!(!is_valid_email("first.last@localhost"));
+        assert!(!is_valid_email("@mail.example.com"));
+    }
+
     #[test]
     fn test_is_valid_currency_id() {
         assert!(is_valid_currency_id("4"));
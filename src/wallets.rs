@@ -5,13 +5,17 @@
 //! - Managing wallet addresses (temporary and permanent)
 //! - Wallet operations and information retrieval
 
+use crate::endpoint::{page_query, wallet_params, wire_value, Endpoint};
 use crate::{CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // === Wallet Types ===
 
 /// Wallet information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Wallet {
     pub id: String,
     pub label: String,
@@ -27,6 +31,9 @@ pub struct Wallet {
     pub status: WalletStatus,
     pub created_at: String,
     pub updated_at: String,
+    /// Fields not known to this version of the crate, kept verbatim
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Wallet status
@@ -37,6 +44,9 @@ pub enum WalletStatus {
     Inactive,
     Frozen,
     Closed,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Address type for wallets
@@ -47,10 +57,14 @@ pub enum AddressType {
     Temporary,
     /// Permanent addresses (persistent)
     Permanent,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Wallet address information
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletAddress {
     pub id: String,
     pub label: String,
@@ -68,6 +82,7 @@ pub struct WalletAddress {
 
 /// Request to create or retrieve a wallet
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateWalletRequest {
     pub label: String,
     pub currency_id: String,
@@ -78,6 +93,7 @@ pub struct CreateWalletRequest {
 
 /// Response for wallet operations
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletResponse {
     pub wallet: Wallet,
     pub addresses: Option<Vec<WalletAddress>>,
@@ -85,6 +101,7 @@ pub struct WalletResponse {
 
 /// Response for getting wallets
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetWalletsResponse {
     pub wallets: Vec<Wallet>,
     pub pagination: Option<WalletPaginationInfo>,
@@ -92,6 +109,7 @@ pub struct GetWalletsResponse {
 
 /// Pagination information for wallets
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletPaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -101,6 +119,7 @@ pub struct WalletPaginationInfo {
 
 /// Wallet count response
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletCountResponse {
     pub count: u32,
     pub active_count: u32,
@@ -109,6 +128,7 @@ pub struct WalletCountResponse {
 
 /// Response for getting wallet addresses
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetAddressesResponse {
     pub addresses: Vec<WalletAddress>,
     pub pagination: Option<AddressPaginationInfo>,
@@ -116,6 +136,7 @@ pub struct GetAddressesResponse {
 
 /// Pagination information for addresses
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddressPaginationInfo {
     pub page: u32,
     pub per_page: u32,
@@ -125,6 +146,7 @@ pub struct AddressPaginationInfo {
 
 /// Address count response
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddressCountResponse {
     pub count: u32,
     pub activated_count: u32,
@@ -133,6 +155,7 @@ pub struct AddressCountResponse {
 
 /// Webhook configuration for wallet/address
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct WebhookConfig {
     pub url: String,
     pub events: Vec<WebhookEvent>,
@@ -151,6 +174,9 @@ pub enum WebhookEvent {
     SameUserReceive,
     AccountBasedExternalTokenReceive,
     AccountBasedTokenSpend,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl Default for CreateWalletRequest {
@@ -194,6 +220,284 @@ impl CreateWalletRequest {
     }
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::get_wallets`]
+#[derive(Debug, Clone, Default)]
+pub struct GetWallets {
+    /// Page number
+    pub page: Option<u32>,
+    /// Number of results per page
+    pub per_page: Option<u32>,
+    /// Only wallets of this currency
+    pub currency_id: Option<String>,
+    /// Only wallets with this status
+    pub status: Option<WalletStatus>,
+}
+
+impl Endpoint for GetWallets {
+    type Request = ();
+    type Response = GetWalletsResponse;
+
+    const NAME: &'static str = "get_wallets";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets";
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query_params = page_query(self.page, self.per_page);
+
+        if let Some(currency_id) = &self.currency_id {
+            query_params.push(("currencyId", currency_id.clone()));
+        }
+        if let Some(status) = &self.status {
+            query_params.push(("status", wire_value(status)));
+        }
+        query_params
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::create_wallet`]
+#[derive(Debug, Clone)]
+pub struct CreateWallet {
+    /// Wallet to create
+    pub request: CreateWalletRequest,
+}
+
+impl Endpoint for CreateWallet {
+    type Request = CreateWalletRequest;
+    type Response = WalletResponse;
+
+    const NAME: &'static str = "create_wallet";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str = "v3/merchant/wallets";
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_wallet_count`]
+#[derive(Debug, Clone, Default)]
+pub struct GetWalletCount;
+
+impl Endpoint for GetWalletCount {
+    type Request = ();
+    type Response = WalletCountResponse;
+
+    const NAME: &'static str = "get_wallet_count";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets/count";
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_wallet_addresses`]
+#[derive(Debug, Clone)]
+pub struct GetWalletAddresses {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Page number
+    pub page: Option<u32>,
+    /// Number of results per page
+    pub per_page: Option<u32>,
+}
+
+impl GetWalletAddresses {
+    /// List the addresses of a wallet
+    pub fn new(wallet_label: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            page: None,
+            per_page: None,
+        }
+    }
+}
+
+impl Endpoint for GetWalletAddresses {
+    type Request = ();
+    type Response = GetAddressesResponse;
+
+    const NAME: &'static str = "get_wallet_addresses";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/addresses";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        page_query(self.page, self.per_page)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_wallet_address_count`]
+#[derive(Debug, Clone)]
+pub struct GetWalletAddressCount {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+}
+
+impl GetWalletAddressCount {
+    /// Count the addresses of a wallet
+    pub fn new(wallet_label: impl Into<String>, currency_id: impl Into<String>) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+        }
+    }
+}
+
+impl Endpoint for GetWalletAddressCount {
+    type Request = ();
+    type Response = AddressCountResponse;
+
+    const NAME: &'static str = "get_wallet_address_count";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/addresses/count";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::get_address_by_label`]
+#[derive(Debug, Clone)]
+pub struct GetAddressByLabel {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Address label
+    pub address_label: String,
+}
+
+impl GetAddressByLabel {
+    /// Get a wallet address by its label
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        address_label: impl Into<String>,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            address_label: address_label.into(),
+        }
+    }
+}
+
+impl Endpoint for GetAddressByLabel {
+    type Request = ();
+    type Response = WalletAddress;
+
+    const NAME: &'static str = "get_address_by_label";
+    const METHOD: Method = Method::GET;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/addresses/{address_label}";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = wallet_params(&self.wallet_label, &self.currency_id);
+        params.push(("address_label", self.address_label.clone()));
+        params
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::update_wallet_webhook`]
+#[derive(Debug, Clone)]
+pub struct UpdateWalletWebhook {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Webhook configuration
+    pub webhook_config: WebhookConfig,
+}
+
+impl UpdateWalletWebhook {
+    /// Update the webhook of a wallet
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        webhook_config: WebhookConfig,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            webhook_config,
+        }
+    }
+}
+
+impl Endpoint for UpdateWalletWebhook {
+    type Request = WebhookConfig;
+    type Response = ();
+
+    const NAME: &'static str = "update_wallet_webhook";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/webhook";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.webhook_config)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::update_address_webhook`]
+#[derive(Debug, Clone)]
+pub struct UpdateAddressWebhook {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Address label
+    pub address_label: String,
+    /// Webhook configuration
+    pub webhook_config: WebhookConfig,
+}
+
+impl UpdateAddressWebhook {
+    /// Update the webhook of a wallet address
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        address_label: impl Into<String>,
+        webhook_config: WebhookConfig,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            address_label: address_label.into(),
+            webhook_config,
+        }
+    }
+}
+
+impl Endpoint for UpdateAddressWebhook {
+    type Request = WebhookConfig;
+    type Response = ();
+
+    const NAME: &'static str = "update_address_webhook";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/addresses/{address_label}/webhook";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = wallet_params(&self.wallet_label, &self.currency_id);
+        params.push(("address_label", self.address_label.clone()));
+        params
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.webhook_config)
+    }
+}
+
 impl CoinPaymentsClient {
     /// Get list of merchant wallets
     ///
@@ -204,10 +508,18 @@ impl CoinPaymentsClient {
     /// * `status` - Filter by wallet status (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let wallets = client.get_wallets(None, None, None, None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(currency_id = ?currency_id))
+    )]
     pub async fn get_wallets(
         &self,
         page: Option<u32>,
@@ -215,28 +527,13 @@ impl CoinPaymentsClient {
         currency_id: Option<&str>,
         status: Option<WalletStatus>,
     ) -> Result<GetWalletsResponse> {
-        let mut query_params = Vec::new();
-
-        if let Some(page) = page {
-            query_params.push(("page", page.to_string()));
-        }
-        if let Some(per_page) = per_page {
-            query_params.push(("per_page", per_page.to_string()));
-        }
-        if let Some(currency_id) = currency_id {
-            query_params.push(("currency_id", currency_id.to_string()));
-        }
-        if let Some(status) = status {
-            let status_str = match status {
-                WalletStatus::Active => "active",
-                WalletStatus::Inactive => "inactive",
-                WalletStatus::Frozen => "frozen",
-                WalletStatus::Closed => "closed",
-            };
-            query_params.push(("status", status_str.to_string()));
-        }
-
-        self.get_request("v3/merchant/wallets", &query_params).await
+        self.execute(GetWallets {
+            page,
+            per_page,
+            currency_id: currency_id.map(str::to_string),
+            status,
+        })
+        .await
     }
 
     /// Create or retrieve a wallet by external IDs
@@ -245,25 +542,35 @@ impl CoinPaymentsClient {
     /// * `request` - Wallet creation request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, CreateWalletRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = CreateWalletRequest::new("my-btc-wallet", "4")
     ///     .with_permanent_addresses(true);
     /// let wallet = client.create_wallet(request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn create_wallet(&self, request: CreateWalletRequest) -> Result<WalletResponse> {
-        self.put_request("v3/merchant/wallets", &request).await
+        self.execute(CreateWallet { request }).await
     }
 
     /// Get wallet count
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let count = client.get_wallet_count().await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn get_wallet_count(&self) -> Result<WalletCountResponse> {
-        self.get_request("v3/merchant/wallets/count", &[]).await
+        self.execute(GetWalletCount).await
     }
 
     /// Get wallet addresses
@@ -275,10 +582,24 @@ impl CoinPaymentsClient {
     /// * `per_page` - Results per page (optional)
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let addresses = client.get_wallet_addresses("my-btc-wallet", "4", None, None).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn get_wallet_addresses(
         &self,
         wallet_label: &str,
@@ -286,20 +607,12 @@ impl CoinPaymentsClient {
         page: Option<u32>,
         per_page: Option<u32>,
     ) -> Result<GetAddressesResponse> {
-        let mut query_params = Vec::new();
-
-        if let Some(page) = page {
-            query_params.push(("page", page.to_string()));
-        }
-        if let Some(per_page) = per_page {
-            query_params.push(("per_page", per_page.to_string()));
-        }
-
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/addresses",
-            wallet_label, currency_id
-        );
-        self.get_request(&endpoint, &query_params).await
+        self.execute(GetWalletAddresses {
+            page,
+            per_page,
+            ..GetWalletAddresses::new(wallet_label, currency_id)
+        })
+        .await
     }
 
     /// Get wallet address count
@@ -309,20 +622,31 @@ impl CoinPaymentsClient {
     /// * `currency_id` - Currency ID
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let count = client.get_wallet_address_count("my-btc-wallet", "4").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn get_wallet_address_count(
         &self,
         wallet_label: &str,
         currency_id: &str,
     ) -> Result<AddressCountResponse> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/addresses/count",
-            wallet_label, currency_id
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetWalletAddressCount::new(wallet_label, currency_id))
+            .await
     }
 
     /// Get specific address by label
@@ -333,21 +657,37 @@ impl CoinPaymentsClient {
     /// * `address_label` - Address label
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::CoinPaymentsClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let address = client.get_address_by_label("my-btc-wallet", "4", "address-1").await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                address_label = %address_label
+            )
+        )
+    )]
     pub async fn get_address_by_label(
         &self,
         wallet_label: &str,
         currency_id: &str,
         address_label: &str,
     ) -> Result<WalletAddress> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/addresses/{}",
-            wallet_label, currency_id, address_label
-        );
-        self.get_request(&endpoint, &[]).await
+        self.execute(GetAddressByLabel::new(
+            wallet_label,
+            currency_id,
+            address_label,
+        ))
+        .await
     }
 
     /// Update wallet webhook
@@ -358,26 +698,41 @@ impl CoinPaymentsClient {
     /// * `webhook_config` - Webhook configuration
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, WebhookConfig, WebhookEvent};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let webhook = WebhookConfig {
     ///     url: "https://example.com/webhook".to_string(),
-    ///     events: vec![WebhookEvent::ExternalReceive],
+    ///     events: vec![WebhookEvent::UtxoExternalReceive],
     ///     secret: Some("webhook_secret".to_string()),
     /// };
     /// client.update_wallet_webhook("my-btc-wallet", "4", webhook).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn update_wallet_webhook(
         &self,
         wallet_label: &str,
         currency_id: &str,
         webhook_config: WebhookConfig,
     ) -> Result<()> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/webhook",
-            wallet_label, currency_id
-        );
-        self.put_request(&endpoint, &webhook_config).await
+        self.execute(UpdateWalletWebhook::new(
+            wallet_label,
+            currency_id,
+            webhook_config,
+        ))
+        .await
     }
 
     /// Update address webhook
@@ -389,15 +744,30 @@ impl CoinPaymentsClient {
     /// * `webhook_config` - Webhook configuration
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, WebhookConfig, WebhookEvent};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let webhook = WebhookConfig {
     ///     url: "https://example.com/webhook".to_string(),
-    ///     events: vec![WebhookEvent::ExternalReceive],
+    ///     events: vec![WebhookEvent::UtxoExternalReceive],
     ///     secret: Some("webhook_secret".to_string()),
     /// };
     /// client.update_address_webhook("my-btc-wallet", "4", "address-1", webhook).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                address_label = %address_label
+            )
+        )
+    )]
     pub async fn update_address_webhook(
         &self,
         wallet_label: &str,
@@ -405,11 +775,13 @@ impl CoinPaymentsClient {
         address_label: &str,
         webhook_config: WebhookConfig,
     ) -> Result<()> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/addresses/{}/webhook",
-            wallet_label, currency_id, address_label
-        );
-        self.put_request(&endpoint, &webhook_config).await
+        self.execute(UpdateAddressWebhook::new(
+            wallet_label,
+            currency_id,
+            address_label,
+            webhook_config,
+        ))
+        .await
     }
 }
 
@@ -484,6 +856,7 @@ mod tests {
             status: WalletStatus::Active,
             created_at: "2023-01-01T00:00:00Z".to_string(),
             updated_at: "2023-01-01T00:00:00Z".to_string(),
+            extra: HashMap::new(),
         }
     }
 
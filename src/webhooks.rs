@@ -6,14 +6,18 @@
 //! - Webhook authentication and verification
 //! - Event handling and payload processing
 
+use crate::endpoint::{wallet_params, Endpoint};
 use crate::{CoinPaymentsClient, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 // === Webhook Types ===
 
 /// Client webhook configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ClientWebhook {
     pub id: String,
     pub client_id: String,
@@ -45,10 +49,14 @@ pub enum ClientWebhookEvent {
     PaymentCreated,
     /// Triggered when temporary address for payment is no longer available
     PaymentTimedOut,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Wallet webhook configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletWebhook {
     pub wallet_id: String,
     pub wallet_label: String,
@@ -81,10 +89,14 @@ pub enum WalletWebhookEvent {
     AccountBasedExternalTokenReceive,
     /// Sending account-based tokens to external address
     AccountBasedTokenSpend,
+    /// Value not known to this version of the crate, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Address webhook configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct AddressWebhook {
     pub address_id: String,
     pub address_label: String,
@@ -100,6 +112,7 @@ pub struct AddressWebhook {
 
 /// Request to create a client webhook
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateClientWebhookRequest {
     pub url: String,
     pub events: Vec<ClientWebhookEvent>,
@@ -109,6 +122,7 @@ pub struct CreateClientWebhookRequest {
 
 /// Request to update webhook configuration
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateWebhookRequest {
     pub url: String,
     pub events: Vec<String>,
@@ -118,6 +132,7 @@ pub struct UpdateWebhookRequest {
 
 /// Webhook payload for client events (invoices)
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ClientWebhookPayload {
     pub event: ClientWebhookEvent,
     pub invoice_id: String,
@@ -128,10 +143,14 @@ pub struct ClientWebhookPayload {
     pub created_at: String,
     pub payment_data: Option<PaymentData>,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Fields not known to this version of the crate, kept verbatim
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Payment data in webhook payload
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct PaymentData {
     pub currency_id: String,
     pub address: String,
@@ -143,6 +162,7 @@ pub struct PaymentData {
 
 /// Webhook payload for wallet events
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct WalletWebhookPayload {
     pub event: WalletWebhookEvent,
     pub wallet_id: String,
@@ -158,6 +178,9 @@ pub struct WalletWebhookPayload {
     pub status: String,
     pub created_at: String,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Fields not known to this version of the crate, kept verbatim
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Webhook authentication headers
@@ -207,6 +230,219 @@ impl CreateClientWebhookRequest {
     }
 }
 
+// === Endpoints ===
+
+/// Endpoint of [`CoinPaymentsClient::create_client_webhook`]
+#[derive(Debug, Clone)]
+pub struct CreateClientWebhook {
+    /// Client ID
+    pub client_id: String,
+    /// Webhook to create
+    pub request: CreateClientWebhookRequest,
+}
+
+impl CreateClientWebhook {
+    /// Create a webhook for a client
+    pub fn new(client_id: impl Into<String>, request: CreateClientWebhookRequest) -> Self {
+        Self {
+            client_id: client_id.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for CreateClientWebhook {
+    type Request = CreateClientWebhookRequest;
+    type Response = ClientWebhook;
+
+    const NAME: &'static str = "create_client_webhook";
+    const METHOD: Method = Method::POST;
+    const PATH: &'static str = "v1/merchant/clients/{client_id}/webhooks";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("client_id", self.client_id.clone())]
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::update_wallet_webhook_v2`]
+#[derive(Debug, Clone)]
+pub struct UpdateWalletWebhookV2 {
+    /// Wallet ID
+    pub wallet_id: String,
+    /// New webhook configuration
+    pub request: UpdateWebhookRequest,
+}
+
+impl UpdateWalletWebhookV2 {
+    /// Update the webhook of a wallet
+    pub fn new(wallet_id: impl Into<String>, request: UpdateWebhookRequest) -> Self {
+        Self {
+            wallet_id: wallet_id.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for UpdateWalletWebhookV2 {
+    type Request = UpdateWebhookRequest;
+    type Response = ();
+
+    const NAME: &'static str = "update_wallet_webhook_v2";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str = "v2/merchant/wallets/{wallet_id}/webhook";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![("wallet_id", self.wallet_id.clone())]
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::update_address_webhook_v2`]
+#[derive(Debug, Clone)]
+pub struct UpdateAddressWebhookV2 {
+    /// Wallet ID
+    pub wallet_id: String,
+    /// Address ID
+    pub address_id: String,
+    /// New webhook configuration
+    pub request: UpdateWebhookRequest,
+}
+
+impl UpdateAddressWebhookV2 {
+    /// Update the webhook of a wallet address
+    pub fn new(
+        wallet_id: impl Into<String>,
+        address_id: impl Into<String>,
+        request: UpdateWebhookRequest,
+    ) -> Self {
+        Self {
+            wallet_id: wallet_id.into(),
+            address_id: address_id.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for UpdateAddressWebhookV2 {
+    type Request = UpdateWebhookRequest;
+    type Response = ();
+
+    const NAME: &'static str = "update_address_webhook_v2";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str = "v2/merchant/wallets/{wallet_id}/addresses/{address_id}/webhook";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("wallet_id", self.wallet_id.clone()),
+            ("address_id", self.address_id.clone()),
+        ]
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::update_wallet_webhook_v3`]
+#[derive(Debug, Clone)]
+pub struct UpdateWalletWebhookV3 {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// New webhook configuration
+    pub request: UpdateWebhookRequest,
+}
+
+impl UpdateWalletWebhookV3 {
+    /// Update the webhook of a wallet
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        request: UpdateWebhookRequest,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for UpdateWalletWebhookV3 {
+    type Request = UpdateWebhookRequest;
+    type Response = ();
+
+    const NAME: &'static str = "update_wallet_webhook_v3";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str = "v3/merchant/wallets/{wallet_label}/{currency_id}/webhook";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        wallet_params(&self.wallet_label, &self.currency_id)
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
+/// Endpoint of [`CoinPaymentsClient::update_address_webhook_v3`]
+#[derive(Debug, Clone)]
+pub struct UpdateAddressWebhookV3 {
+    /// Wallet label
+    pub wallet_label: String,
+    /// Currency ID
+    pub currency_id: String,
+    /// Address label
+    pub address_label: String,
+    /// New webhook configuration
+    pub request: UpdateWebhookRequest,
+}
+
+impl UpdateAddressWebhookV3 {
+    /// Update the webhook of a wallet address
+    pub fn new(
+        wallet_label: impl Into<String>,
+        currency_id: impl Into<String>,
+        address_label: impl Into<String>,
+        request: UpdateWebhookRequest,
+    ) -> Self {
+        Self {
+            wallet_label: wallet_label.into(),
+            currency_id: currency_id.into(),
+            address_label: address_label.into(),
+            request,
+        }
+    }
+}
+
+impl Endpoint for UpdateAddressWebhookV3 {
+    type Request = UpdateWebhookRequest;
+    type Response = ();
+
+    const NAME: &'static str = "update_address_webhook_v3";
+    const METHOD: Method = Method::PUT;
+    const PATH: &'static str =
+        "v3/merchant/wallets/{wallet_label}/{currency_id}/addresses/{address_label}/webhook";
+
+    fn path_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = wallet_params(&self.wallet_label, &self.currency_id);
+        params.push(("address_label", self.address_label.clone()));
+        params
+    }
+
+    fn body(&self) -> Option<&Self::Request> {
+        Some(&self.request)
+    }
+}
+
 impl CoinPaymentsClient {
     /// Create a client webhook
     ///
@@ -215,7 +451,9 @@ impl CoinPaymentsClient {
     /// * `request` - Webhook creation request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{ClientWebhookEvent, CoinPaymentsClient, CreateClientWebhookRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = CreateClientWebhookRequest::new("https://example.com/webhook")
     ///     .with_events(vec![
@@ -224,14 +462,20 @@ impl CoinPaymentsClient {
     ///     ])
     ///     .with_secret("webhook_secret");
     /// let webhook = client.create_client_webhook("client_123", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(client_id = %client_id))
+    )]
     pub async fn create_client_webhook(
         &self,
         client_id: &str,
         request: CreateClientWebhookRequest,
     ) -> Result<ClientWebhook> {
-        let endpoint = format!("v1/merchant/clients/{}/webhooks", client_id);
-        self.post_request(&endpoint, &request).await
+        self.execute(CreateClientWebhook::new(client_id, request))
+            .await
     }
 
     /// Update wallet webhook (v2 API - by ID)
@@ -241,7 +485,9 @@ impl CoinPaymentsClient {
     /// * `request` - Webhook update request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, UpdateWebhookRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = UpdateWebhookRequest {
     ///     url: "https://example.com/webhook".to_string(),
@@ -250,14 +496,20 @@ impl CoinPaymentsClient {
     ///     is_active: Some(true),
     /// };
     /// client.update_wallet_webhook_v2("wallet_123", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet_id = %wallet_id))
+    )]
     pub async fn update_wallet_webhook_v2(
         &self,
         wallet_id: &str,
         request: UpdateWebhookRequest,
     ) -> Result<()> {
-        let endpoint = format!("v2/merchant/wallets/{}/webhook", wallet_id);
-        self.put_request(&endpoint, &request).await
+        self.execute(UpdateWalletWebhookV2::new(wallet_id, request))
+            .await
     }
 
     /// Update address webhook (v2 API - by ID)
@@ -268,7 +520,9 @@ impl CoinPaymentsClient {
     /// * `request` - Webhook update request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, UpdateWebhookRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = UpdateWebhookRequest {
     ///     url: "https://example.com/webhook".to_string(),
@@ -277,18 +531,21 @@ impl CoinPaymentsClient {
     ///     is_active: Some(true),
     /// };
     /// client.update_address_webhook_v2("wallet_123", "addr_456", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet_id = %wallet_id, address_id = %address_id))
+    )]
     pub async fn update_address_webhook_v2(
         &self,
         wallet_id: &str,
         address_id: &str,
         request: UpdateWebhookRequest,
     ) -> Result<()> {
-        let endpoint = format!(
-            "v2/merchant/wallets/{}/addresses/{}/webhook",
-            wallet_id, address_id
-        );
-        self.put_request(&endpoint, &request).await
+        self.execute(UpdateAddressWebhookV2::new(wallet_id, address_id, request))
+            .await
     }
 
     /// Update wallet webhook (v3 API - by label)
@@ -299,7 +556,9 @@ impl CoinPaymentsClient {
     /// * `request` - Webhook update request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, UpdateWebhookRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = UpdateWebhookRequest {
     ///     url: "https://example.com/webhook".to_string(),
@@ -308,18 +567,31 @@ impl CoinPaymentsClient {
     ///     is_active: Some(true),
     /// };
     /// client.update_wallet_webhook_v3("my-btc-wallet", "4", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id
+            )
+        )
+    )]
     pub async fn update_wallet_webhook_v3(
         &self,
         wallet_label: &str,
         currency_id: &str,
         request: UpdateWebhookRequest,
     ) -> Result<()> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/webhook",
-            wallet_label, currency_id
-        );
-        self.put_request(&endpoint, &request).await
+        self.execute(UpdateWalletWebhookV3::new(
+            wallet_label,
+            currency_id,
+            request,
+        ))
+        .await
     }
 
     /// Update address webhook (v3 API - by label)
@@ -331,7 +603,9 @@ impl CoinPaymentsClient {
     /// * `request` - Webhook update request
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
+    /// # use coinpayments::{CoinPaymentsClient, UpdateWebhookRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = CoinPaymentsClient::new("client_id", "client_secret");
     /// let request = UpdateWebhookRequest {
     ///     url: "https://example.com/webhook".to_string(),
@@ -340,7 +614,20 @@ impl CoinPaymentsClient {
     ///     is_active: Some(true),
     /// };
     /// client.update_address_webhook_v3("my-btc-wallet", "4", "address-1", request).await?;
+    /// # Ok(())
+    /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                wallet_label = %wallet_label,
+                currency_id = %currency_id,
+                address_label = %address_label
+            )
+        )
+    )]
     pub async fn update_address_webhook_v3(
         &self,
         wallet_label: &str,
@@ -348,11 +635,13 @@ impl CoinPaymentsClient {
         address_label: &str,
         request: UpdateWebhookRequest,
     ) -> Result<()> {
-        let endpoint = format!(
-            "v3/merchant/wallets/{}/{}/addresses/{}/webhook",
-            wallet_label, currency_id, address_label
-        );
-        self.put_request(&endpoint, &request).await
+        self.execute(UpdateAddressWebhookV3::new(
+            wallet_label,
+            currency_id,
+            address_label,
+            request,
+        ))
+        .await
     }
 }
 
@@ -366,13 +655,17 @@ impl CoinPaymentsClient {
 /// * `payload` - Raw webhook payload body
 ///
 /// # Example
-/// ```rust
+/// ```rust,no_run
+/// # use coinpayments::{verify_webhook_signature, WebhookHeaders};
+/// # fn example() {
+/// # let payload_body = b"{}".to_vec();
 /// let headers = WebhookHeaders {
 ///     client_id: "your_client_id".to_string(),
 ///     timestamp: "2023-01-01T00:00:00Z".to_string(),
 ///     signature: "received_signature".to_string(),
 /// };
 /// let is_valid = verify_webhook_signature("private_key", &headers, &payload_body);
+/// # }
 /// ```
 pub fn verify_webhook_signature(
     private_key: &str,
@@ -407,9 +700,14 @@ pub fn verify_webhook_signature(
 /// * `header_map` - HTTP headers map
 ///
 /// # Example
-/// ```rust
+/// ```rust,no_run
+/// # use coinpayments::parse_webhook_headers;
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let request_headers = std::collections::HashMap::new();
 /// // Using with axum or other web framework
 /// let headers = parse_webhook_headers(&request_headers)?;
+/// # Ok(())
+/// # }
 /// ```
 pub fn parse_webhook_headers(header_map: &HashMap<String, String>) -> Result<WebhookHeaders> {
     let client_id = header_map
@@ -467,8 +765,10 @@ pub fn is_webhook_timestamp_valid(timestamp: &str, tolerance_seconds: u64) -> bo
 // === Helper Functions ===
 
 /// Convert client webhook event to string
-pub fn client_event_to_string(event: &ClientWebhookEvent) -> &'static str {
-    match event {
+///
+/// Known events borrow a static name; unknown events return their raw value.
+pub fn client_event_to_string(event: &ClientWebhookEvent) -> Cow<'static, str> {
+    let name = match event {
         ClientWebhookEvent::InvoiceCreated => "invoiceCreated",
         ClientWebhookEvent::InvoicePending => "invoicePending",
         ClientWebhookEvent::InvoicePaid => "invoicePaid",
@@ -477,12 +777,16 @@ pub fn client_event_to_string(event: &ClientWebhookEvent) -> &'static str {
         ClientWebhookEvent::InvoiceTimedOut => "invoiceTimedOut",
         ClientWebhookEvent::PaymentCreated => "paymentCreated",
         ClientWebhookEvent::PaymentTimedOut => "paymentTimedOut",
-    }
+        ClientWebhookEvent::Unknown(event) => return Cow::Owned(event.clone()),
+    };
+    Cow::Borrowed(name)
 }
 
 /// Convert wallet webhook event to string
-pub fn wallet_event_to_string(event: &WalletWebhookEvent) -> &'static str {
-    match event {
+///
+/// Known events borrow a static name; unknown events return their raw value.
+pub fn wallet_event_to_string(event: &WalletWebhookEvent) -> Cow<'static, str> {
+    let name = match event {
         WalletWebhookEvent::InternalReceive => "internalReceive",
         WalletWebhookEvent::UtxoExternalReceive => "utxoExternalReceive",
         WalletWebhookEvent::AccountBasedExternalReceive => "accountBasedExternalReceive",
@@ -491,7 +795,9 @@ pub fn wallet_event_to_string(event: &WalletWebhookEvent) -> &'static str {
         WalletWebhookEvent::SameUserReceive => "sameUserReceive",
         WalletWebhookEvent::AccountBasedExternalTokenReceive => "accountBasedExternalTokenReceive",
         WalletWebhookEvent::AccountBasedTokenSpend => "accountBasedTokenSpend",
-    }
+        WalletWebhookEvent::Unknown(event) => return Cow::Owned(event.clone()),
+    };
+    Cow::Borrowed(name)
 }
 
 /// Filter events by type
@@ -579,6 +885,12 @@ mod tests {
             wallet_event_to_string(&WalletWebhookEvent::ExternalSpend),
             "externalSpend"
         );
+
+        // Known names are static and outlive the event
+        let name = wallet_event_to_string(&WalletWebhookEvent::InternalSpend);
+        assert!(matches!(name, Cow::Borrowed("internalSpend")));
+        let unknown = WalletWebhookEvent::Unknown("stakingReward".to_string());
+        assert!(matches!(wallet_event_to_string(&unknown), Cow::Owned(_)));
     }
 
     #[test]
@@ -618,4 +930,38 @@ mod tests {
         assert_eq!(webhook_headers.timestamp, "2023-01-01T00:00:00Z");
         assert_eq!(webhook_headers.signature, "signature_123");
     }
+
+    #[test]
+    fn test_webhook_payload_keeps_unknown_values() {
+        let payload: WalletWebhookPayload = serde_json::from_value(serde_json::json!({
+            "event": "stakingReward",
+            "walletId": "wallet_1",
+            "walletLabel": "hot",
+            "addressId": null,
+            "address": "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            "currencyId": "4",
+            "transactionId": "tx_1",
+            "amount": "0.5",
+            "fee": null,
+            "txid": null,
+            "confirmations": 3,
+            "status": "completed",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "metadata": null,
+            "rewardEpoch": 412
+        }))
+        .unwrap();
+
+        assert_eq!(
+            payload.event,
+            WalletWebhookEvent::Unknown("stakingReward".to_string())
+        );
+        assert_eq!(wallet_event_to_string(&payload.event), "stakingReward");
+        assert_eq!(payload.extra["rewardEpoch"], 412);
+
+        // Unknown values and extra fields survive a round trip
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["event"], "stakingReward");
+        assert_eq!(json["rewardEpoch"], 412);
+    }
 }
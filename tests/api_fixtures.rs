@@ -0,0 +1,283 @@
+//! Wire-format fixtures for the built-in endpoints
+//!
+//! Each file in `tests/fixtures` holds a hand-written request body (`null` for endpoints
+//! that send none) and response body, named after the endpoint. The fixtures are not captured
+//! from the live API; they pin the wire format the models produce. Executing an endpoint
+//! against its fixture must send exactly the fixture's request body, and the decoded response
+//! must serialize back to the fixture JSON, so no field is dropped, renamed or left in `extra`.
+
+use coinpayments::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+fn fixtures_dir() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn fixture(name: &str) -> Value {
+    let path = fixtures_dir().join(format!("{}.json", name));
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+    serde_json::from_str(&text).unwrap()
+}
+
+/// Execute `endpoint` against its fixture and check the request and response bodies
+async fn round_trip<E>(endpoint: E) -> E::Response
+where
+    E: Endpoint,
+    E::Response: Serialize,
+{
+    let fixture = fixture(E::NAME);
+    let transport = Arc::new(MockTransport::new());
+    transport.push_json(200, fixture["response"].to_string());
+    let client = CoinPaymentsClient::new("test_client", "test_secret")
+        .with_transport(transport.clone())
+        .with_retry_policy(RetryPolicy::none());
+
+    let response = client
+        .execute(endpoint)
+        .await
+        .unwrap_or_else(|error| panic!("{}: {}", E::NAME, error));
+
+    let body = transport.requests()[0].body_text();
+    let sent = match body.as_str() {
+        "" => Value::Null,
+        body => serde_json::from_str(body).unwrap(),
+    };
+    assert_eq!(sent, fixture["request"], "{} request body", E::NAME);
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        fixture["response"],
+        "{} response body",
+        E::NAME
+    );
+    response
+}
+
+fn webhook_config() -> WebhookConfig {
+    WebhookConfig {
+        url: "https://shop.example/hooks/wallet".to_string(),
+        events: vec![
+            WebhookEvent::UtxoExternalReceive,
+            WebhookEvent::ExternalSpend,
+        ],
+        secret: Some("whsec_wallet".to_string()),
+    }
+}
+
+fn update_webhook_request() -> UpdateWebhookRequest {
+    UpdateWebhookRequest {
+        url: "https://shop.example/hooks/wallet".to_string(),
+        events: vec!["externalSpend".to_string()],
+        secret: None,
+        is_active: Some(true),
+    }
+}
+
+fn consolidation_request() -> ConsolidationRequest {
+    ConsolidationRequest {
+        source_addresses: vec![
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+            "bc1q9h6yzs3n8kj4e3qkqz5x0q8l9c2y7r3f4ejmhp".to_string(),
+        ],
+        target_wallet_label: "cold".to_string(),
+        amount: None,
+        note: Some("Nightly sweep".to_string()),
+    }
+}
+
+#[test]
+fn test_every_builtin_endpoint_has_a_fixture() {
+    let mut endpoints: Vec<String> = builtin_endpoints()
+        .iter()
+        .map(|endpoint| endpoint.name.to_string())
+        .collect();
+    let mut fixtures: Vec<String> = std::fs::read_dir(fixtures_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .collect();
+    endpoints.sort();
+    fixtures.sort();
+
+    assert_eq!(endpoints, fixtures);
+}
+
+#[tokio::test]
+async fn test_client_and_currency_fixtures() {
+    round_trip(Ping).await;
+    round_trip(GetClientInfo).await;
+
+    let currencies = round_trip(GetCurrencies::default()).await;
+    assert!(currencies.currencies.iter().all(|c| c.extra.is_empty()));
+    assert!(round_trip(GetCurrencyById::new("4")).await.extra.is_empty());
+    round_trip(GetMerchantCurrencies).await;
+    round_trip(GetLatestBlockNumber::new("4")).await;
+    round_trip(GetRequiredConfirmations).await;
+    round_trip(GetCurrencyConversions).await;
+    round_trip(GetCurrencyLimits::new("4", "61")).await;
+}
+
+#[tokio::test]
+async fn test_rate_and_fee_fixtures() {
+    round_trip(GetRates::default()).await;
+    round_trip(CalculateBlockchainFee::new(
+        "4",
+        FeeCalculationRequest::new("4", fees::TransactionType::Send)
+            .with_amount("0.5")
+            .with_priority(FeePriority::Fast),
+    ))
+    .await;
+    round_trip(GetGasFee::new("61")).await;
+    round_trip(GetNetworkStatus::new("4")).await;
+}
+
+#[tokio::test]
+async fn test_wallet_fixtures() {
+    let wallets = round_trip(GetWallets::default()).await;
+    assert!(wallets.wallets.iter().all(|w| w.extra.is_empty()));
+    round_trip(CreateWallet {
+        request: CreateWalletRequest::new("hot", "4")
+            .with_permanent_addresses(true)
+            .with_webhook("https://shop.example/hooks/wallet"),
+    })
+    .await;
+    round_trip(GetWalletCount).await;
+    round_trip(GetWalletAddresses::new("hot", "4")).await;
+    round_trip(GetWalletAddressCount::new("hot", "4")).await;
+    round_trip(GetAddressByLabel::new("hot", "4", "customer-42")).await;
+    round_trip(UpdateWalletWebhook::new("hot", "4", webhook_config())).await;
+    round_trip(UpdateAddressWebhook::new(
+        "hot",
+        "4",
+        "customer-42",
+        webhook_config(),
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn test_transaction_fixtures() {
+    round_trip(GetTransactionCount::new("hot", "4")).await;
+    let transactions = round_trip(GetTransactions::new("hot", "4")).await;
+    assert!(transactions.transactions.iter().all(|t| t.extra.is_empty()));
+    let transaction = round_trip(GetTransaction {
+        transaction_id: Some("tx_1".to_string()),
+        ..GetTransaction::new("hot", "4")
+    })
+    .await;
+    assert!(transaction.extra.is_empty());
+    round_trip(CreateSpend::new(
+        "hot",
+        "4",
+        CreateSpendRequest::new("0.1")
+            .to_address("bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh")
+            .with_note("Payout 42"),
+    ))
+    .await;
+    round_trip(ConfirmSpend::new("hot", "4", "spend_1")).await;
+    round_trip(GetWalletConsolidation::new("hot", "4")).await;
+    round_trip(ExecuteWalletConsolidation::new(
+        "hot",
+        "4",
+        "cold",
+        consolidation_request(),
+    ))
+    .await;
+    round_trip(ExecuteMultiWalletConsolidation::new(
+        "cold",
+        consolidation_request(),
+    ))
+    .await;
+    round_trip(PreviewConsolidation {
+        request: ConsolidationPreviewRequest {
+            source_wallets: vec![ConsolidationSourceWallet {
+                wallet_label: "hot".to_string(),
+                currency_id: "4".to_string(),
+                addresses: consolidation_request().source_addresses,
+            }],
+            target_wallet_label: "cold".to_string(),
+            target_currency_id: "4".to_string(),
+        },
+    })
+    .await;
+    round_trip(GetConsolidationTransactions::new("hot", "4", "cons_1")).await;
+}
+
+#[tokio::test]
+async fn test_invoice_fixtures() {
+    let created = round_trip(CreateInvoice {
+        request: CreateInvoiceRequest::new("100.00", "USD", "Premium subscription")
+            .with_invoice_number("INV-2024-001")
+            .with_buyer("customer@example.com", Some("Jane Doe".to_string()))
+            .with_ipn_url("https://shop.example/ipn")
+            .with_payment_currencies(vec!["4".to_string(), "61".to_string()]),
+    })
+    .await;
+    assert!(created.invoice.extra.is_empty());
+    round_trip(CancelInvoice::new("inv_1")).await;
+    let invoices = round_trip(GetInvoices::default()).await;
+    assert_eq!(invoices.invoices[0].status, InvoiceStatus::TimedOut);
+    round_trip(GetInvoicePaymentInfo::new("inv_1", "4")).await;
+    round_trip(GetInvoicePaymentStatus::new("inv_1", "4")).await;
+    assert!(round_trip(GetInvoice::new("inv_1")).await.extra.is_empty());
+    round_trip(GetInvoicePayouts::new("inv_1")).await;
+    round_trip(GetInvoiceHistory::new("inv_1")).await;
+}
+
+#[tokio::test]
+async fn test_webhook_fixtures() {
+    round_trip(CreateClientWebhook::new(
+        "cl_1",
+        CreateClientWebhookRequest::new("https://shop.example/hooks/invoices")
+            .with_events(vec![
+                ClientWebhookEvent::InvoiceCreated,
+                ClientWebhookEvent::InvoicePaid,
+                ClientWebhookEvent::InvoiceTimedOut,
+            ])
+            .with_secret("whsec_invoices")
+            .active(true),
+    ))
+    .await;
+    round_trip(UpdateWalletWebhookV2::new(
+        "wal_1",
+        update_webhook_request(),
+    ))
+    .await;
+    round_trip(UpdateAddressWebhookV2::new(
+        "wal_1",
+        "addr_1",
+        update_webhook_request(),
+    ))
+    .await;
+    round_trip(UpdateWalletWebhookV3::new(
+        "hot",
+        "4",
+        update_webhook_request(),
+    ))
+    .await;
+    round_trip(UpdateAddressWebhookV3::new(
+        "hot",
+        "4",
+        "customer-42",
+        update_webhook_request(),
+    ))
+    .await;
+}
+
+#[test]
+fn test_webhook_payload_fixtures() {
+    let body = fixture("webhooks/invoice_paid");
+    let payload: ClientWebhookPayload = serde_json::from_value(body.clone()).unwrap();
+    assert_eq!(payload.event, ClientWebhookEvent::InvoicePaid);
+    assert!(payload.extra.is_empty());
+    assert_eq!(serde_json::to_value(&payload).unwrap(), body);
+
+    let body = fixture("webhooks/wallet_external_receive");
+    let payload: WalletWebhookPayload = serde_json::from_value(body.clone()).unwrap();
+    assert_eq!(payload.event, WalletWebhookEvent::UtxoExternalReceive);
+    assert!(payload.extra.is_empty());
+    assert_eq!(serde_json::to_value(&payload).unwrap(), body);
+}